@@ -0,0 +1,26 @@
+//! Records the compiler and the profile used to build the crate, so that they can be saved
+//! together with the measurements (see `environment.rs`).
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    println!(
+        "cargo:rustc-env=CHRONO_PROBE_RUSTC_VERSION={}",
+        version.trim()
+    );
+    println!(
+        "cargo:rustc-env=CHRONO_PROBE_OPT_LEVEL={}",
+        env::var("OPT_LEVEL").unwrap_or_default()
+    );
+    println!("cargo:rerun-if-env-changed=RUSTC");
+    println!("cargo:rerun-if-changed=build.rs");
+}
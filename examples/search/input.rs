@@ -11,7 +11,7 @@ pub struct SearchInput {
 // We implement the Input trait for SearchInput.
 impl Input for SearchInput {
     // We want to be able to choose between different input generators, so we need a Builder.
-    type Builder = Generator;
+    type Builder<'a> = Generator;
 
     // Return the size of the input.
     fn get_size(&self) -> usize {
@@ -19,7 +19,7 @@ impl Input for SearchInput {
     }
 
     // Generate a random input of the given size.
-    fn generate_input(size: usize, builder: &Self::Builder) -> Self {
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
         match builder {
             Generator::Fast => generate_order_vector_fast(size, u32::MIN, u32::MAX),
             Generator::Uniform => generate_order_vector(size, u32::MIN, u32::MAX),
@@ -9,6 +9,7 @@ use chrono_probe::{
     plot::time_plot,
 };
 use chrono_probe::plot::PlotConfig;
+use chrono_probe::runs::RunManager;
 
 use crate::algorithms::{binary_search_input, linear_search_input};
 use crate::input::Generator;
@@ -16,10 +17,13 @@ use crate::input::Generator;
 mod algorithms;
 mod input;
 
+// The type of the algorithms we want to measure
+type SearchAlgorithm = fn(&input::SearchInput) -> Option<usize>;
+
 fn main() {
     // Create a distribution for the length of the vectors
     // Here we use an uniform distribution with a minimum of 10 and a maximum of 100_000
-    let length_distribution = Uniform::new(10..=100_000);
+    let length_distribution = Uniform::new(10..=100_000).unwrap();
 
     // Create the builder for the vectors
     // Here we choose to use the fast generator method in order to generate ordered vectors
@@ -29,7 +33,7 @@ fn main() {
     let vectors = vector_builder.build(200);
 
     // Create a slice of the algorithms we want to measure
-    let algorithms: &[(fn(&input::SearchInput) -> Option<usize>, &str); 2] = &[
+    let algorithms: &[(SearchAlgorithm, &str); 2] = &[
         (linear_search_input, "Linear search"),
         (binary_search_input, "Binary search"),
     ];
@@ -37,7 +41,11 @@ fn main() {
     // Measure the algorithms on the vectors, given a relative error of 0.001
     let results = measure(&vectors, algorithms, 0.001);
 
-    let file_name = "results/search.svg";
+    // Save the results in a new directory under results/search
+    let run = RunManager::new("search").start().unwrap();
+    run.save_measurements(&results).unwrap();
+
+    let file_name = run.path("search.svg");
 
     // Here we print the linear regression of the log-log scale of the results
     for result in results.clone().measurements {
@@ -53,5 +61,6 @@ fn main() {
         .with_caption("The time plot of searching algorithms in an ordered vector");
 
     // Plot the results
-    time_plot(file_name, results, &config);
+    time_plot(&file_name.to_string_lossy(), results, &config).unwrap();
+    println!("Result has been saved to {}", file_name.display());
 }
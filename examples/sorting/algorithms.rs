@@ -6,7 +6,7 @@ pub fn merge_sort_input(v: &mut InputVec) {
 }
 
 /// The marge sort algorithm.
-pub fn merge_sort<T: Ord + Clone>(v: &mut Vec<T>) {
+pub fn merge_sort<T: Ord + Clone>(v: &mut [T]) {
     let n = v.len();
     if n > 1 {
         let mid = n / 2;
@@ -18,7 +18,7 @@ pub fn merge_sort<T: Ord + Clone>(v: &mut Vec<T>) {
     }
 }
 
-fn merge<T: Ord + Clone>(v: &mut [T], left: &Vec<T>, right: &Vec<T>) {
+fn merge<T: Ord + Clone>(v: &mut [T], left: &[T], right: &[T]) {
     let mut i = 0;
     let mut j = 0;
     let mut k = 0;
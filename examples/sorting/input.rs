@@ -34,7 +34,7 @@ impl DerefMut for InputVec {
 // Here we implement the Input trait for InputVec.
 impl Input for InputVec {
     // We don't need to choose between different input generators, so we don't need a Builder.
-    type Builder = ();
+    type Builder<'a> = ();
 
     // Return the size of the input.
     fn get_size(&self) -> usize {
@@ -42,7 +42,7 @@ impl Input for InputVec {
     }
 
     // Generate a random input of the given size.
-    fn generate_input(size: usize, _builder: &Self::Builder) -> Self {
+    fn generate_input(size: usize, _builder: &Self::Builder<'_>) -> Self {
         let mut rng = thread_rng();
         let mut v = Vec::with_capacity(size);
         for _ in 0..size {
@@ -0,0 +1,247 @@
+//! # Adaptive
+//!
+//! This module provides an iterative way to choose the input sizes, concentrating the
+//! measurements where the behavior of the algorithms is interesting.
+//!
+//! An [`AdaptiveRefinement`] starts by measuring the algorithms on a coarse schedule of sizes,
+//! generated by the distribution of the [`InputBuilder`]. Then, for a given number of rounds, it
+//! looks for the intervals between two consecutive sizes where the time curve of some algorithm
+//! bends the most (i.e. where it deviates the most from a straight line) or is the noisiest (i.e.
+//! where the times of a size vary the most), and measures the algorithms on new sizes in the
+//! middle of those intervals. The results of all the rounds are
+//! merged in a single [`Measurements`].
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::adaptive::AdaptiveRefinement;
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::vec::{U32Vec, VecOrder};
+//! use chrono_probe::input::InputBuilder;
+//!
+//! fn sort(vec: &mut U32Vec) {
+//!     vec.sort();
+//! }
+//!
+//! let builder: InputBuilder<U32Vec, _> =
+//!     InputBuilder::new(Uniform::new(10..=1000).unwrap(), VecOrder::Random);
+//!
+//! // 5 initial sizes, then 2 rounds adding 3 sizes each
+//! let results = AdaptiveRefinement::new(5)
+//!     .with_rounds(2)
+//!     .with_points_per_round(3)
+//!     .measure_mut(&builder, &[(sort, "sort")], 0.01);
+//! assert_eq!(results.measurements[0].measurement.len(), 11);
+//! ```
+
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::input::distribution::Distribution;
+use crate::input::{Input, InputBuilder, InputSet};
+use crate::measurements::{measure, measure_mut, Measurements};
+use crate::progress::DefaultProgress;
+
+/// The configuration of an adaptive refinement of the input sizes.
+pub struct AdaptiveRefinement {
+    initial: usize,
+    rounds: usize,
+    points_per_round: usize,
+    repetitions: usize,
+}
+
+impl AdaptiveRefinement {
+    /// Creates a new [`AdaptiveRefinement`], with 3 rounds adding `initial` / 2 sizes each.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - The number of sizes of the initial schedule.
+    pub fn new(initial: usize) -> AdaptiveRefinement {
+        assert!(
+            initial > 0,
+            "The number of initial sizes must be greater than 0"
+        );
+        AdaptiveRefinement {
+            initial,
+            rounds: 3,
+            points_per_round: (initial / 2).max(1),
+            repetitions: 1,
+        }
+    }
+
+    /// Sets the number of refinement rounds.
+    pub fn with_rounds(mut self, rounds: usize) -> AdaptiveRefinement {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Sets the maximum number of sizes added at each round.
+    pub fn with_points_per_round(mut self, points_per_round: usize) -> AdaptiveRefinement {
+        assert!(
+            points_per_round > 0,
+            "The number of sizes added at each round must be greater than 0"
+        );
+        self.points_per_round = points_per_round;
+        self
+    }
+
+    /// Sets the number of repetitions for each input size.
+    pub fn with_repetitions(mut self, repetitions: usize) -> AdaptiveRefinement {
+        self.repetitions = repetitions;
+        self
+    }
+
+    /// Measures the time it takes to run different functions, refining the input sizes.
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - The builder used to generate the inputs
+    /// * `algorithms` - The vector of functions to measure
+    /// * `relative_error` - The required relative error of the measurements
+    ///
+    pub fn measure<I, D, O, Alg>(
+        &self,
+        builder: &InputBuilder<I, D>,
+        algorithms: &[(Alg, &str)],
+        relative_error: f32,
+    ) -> Measurements
+    where
+        I: Input,
+        D: Distribution,
+        Alg: Fn(&I) -> O,
+    {
+        self.run(builder, |inputs| {
+            measure(inputs, algorithms, relative_error)
+        })
+    }
+
+    /// Measures the time it takes to run different functions mutating their input, refining the
+    /// input sizes.
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - The builder used to generate the inputs
+    /// * `algorithms` - The vector of functions to measure
+    /// * `relative_error` - The required relative error of the measurements
+    ///
+    pub fn measure_mut<I, D, O, Alg>(
+        &self,
+        builder: &InputBuilder<I, D>,
+        algorithms: &[(Alg, &str)],
+        relative_error: f32,
+    ) -> Measurements
+    where
+        I: Input + Clone,
+        D: Distribution,
+        Alg: Fn(&mut I) -> O,
+    {
+        self.run(builder, |inputs| {
+            measure_mut(inputs, algorithms, relative_error)
+        })
+    }
+
+    /// Measures the initial schedule and refines it, obtaining the measurements of each set of
+    /// inputs with the given function.
+    fn run<I, D, M>(&self, builder: &InputBuilder<I, D>, mut measure_inputs: M) -> Measurements
+    where
+        I: Input,
+        D: Distribution,
+        M: FnMut(&InputSet<I>) -> Measurements,
+    {
+        let inputs = builder.build_with_repetitions(self.initial, self.repetitions);
+        let mut results = measure_inputs(&inputs);
+
+        for round in 1..=self.rounds {
+            let sizes = self.refine(&results);
+            if sizes.is_empty() {
+                break;
+            }
+            // Use a different seed at each round, so that the new inputs are reproducible but
+            // independent of the ones of the previous rounds
+            let mut rng = builder
+                .seed
+                .map(|seed| StdRng::seed_from_u64(seed.wrapping_add(round as u64)));
+            let inputs = builder.build_sizes(
+                &sizes,
+                &|_| self.repetitions,
+                rng.as_mut(),
+                &mut DefaultProgress::default(),
+            );
+            results = results.merge(measure_inputs(&inputs));
+        }
+        results
+    }
+
+    /// Returns the new sizes to measure: the middle of the intervals between consecutive sizes
+    /// where the time curves deviate the most from a straight line, or vary the most between
+    /// repeated measurements.
+    fn refine(&self, results: &Measurements) -> Vec<usize> {
+        // The score of each interval, as the maximum over the algorithms
+        let mut scores: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for measurement in &results.measurements {
+            // The times of each size, sorted by size (the points of the merged rounds aren't)
+            let mut times: BTreeMap<usize, Vec<(f64, f64)>> = BTreeMap::new();
+            for point in &measurement.measurement {
+                let stddev = point.stddev.unwrap_or_default().as_secs_f64();
+                times
+                    .entry(point.size)
+                    .or_default()
+                    .push((point.time.as_secs_f64(), stddev));
+            }
+            // The size, the mean time and the relative standard deviation of the time of each
+            // size, including both the spread of the repetitions and the one of each point
+            let points: Vec<(f64, f64, f64)> = times
+                .into_iter()
+                .map(|(size, times)| {
+                    let n = times.len() as f64;
+                    let mean = times.iter().map(|(time, _)| time).sum::<f64>() / n;
+                    let spread = times
+                        .iter()
+                        .map(|(time, stddev)| (time - mean).powi(2) + stddev.powi(2))
+                        .sum::<f64>()
+                        / n;
+                    let noise = if mean > 0.0 {
+                        spread.sqrt() / mean
+                    } else {
+                        0.0
+                    };
+                    (size as f64, mean, noise)
+                })
+                .collect();
+            // The relative deviation of each point from the line through its neighbours
+            let mut deviations = vec![0.0; points.len()];
+            for i in 1..points.len().saturating_sub(1) {
+                let (x0, y0, _) = points[i - 1];
+                let (x1, y1, _) = points[i];
+                let (x2, y2, _) = points[i + 1];
+                if x2 > x0 && y1 > 0.0 {
+                    let expected = y0 + (y2 - y0) * (x1 - x0) / (x2 - x0);
+                    deviations[i] = (y1 - expected).abs() / y1;
+                }
+            }
+            for i in 0..points.len().saturating_sub(1) {
+                let (a, b) = (points[i].0 as usize, points[i + 1].0 as usize);
+                if b > a + 1 {
+                    let deviation = deviations[i].max(deviations[i + 1]);
+                    let noise = points[i].2.max(points[i + 1].2);
+                    let score = deviation + noise;
+                    let entry = scores.entry((a, b)).or_insert(0.0);
+                    *entry = entry.max(score);
+                }
+            }
+        }
+
+        let mut intervals: Vec<((usize, usize), f64)> = scores.into_iter().collect();
+        intervals.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let mut sizes: Vec<usize> = intervals
+            .into_iter()
+            .take(self.points_per_round)
+            .map(|((a, b), _)| a + (b - a) / 2)
+            .collect();
+        sizes.sort_unstable();
+        sizes.dedup();
+        sizes
+    }
+}
@@ -0,0 +1,295 @@
+//! # Analysis
+//!
+//! This module contains the tools to analyze the results of the measurements.
+//!
+//! A [`Fit`] is a power law t = a·n<sup>b</sup> fitted to the points of a [`Measurement`] with the
+//! least squares method in a double logarithmic scale. The exponent b estimates the degree of the
+//! polynomial complexity of the algorithm, and the fitted curve can be drawn over the measured
+//! points as a trend line (see [`SeriesStyle`](crate::plot::SeriesStyle)).
+//!
+//! A [`Complexity`] is one of the canonical growth rates (e.g. n log n), that can be compared with
+//! the measurements, for example drawing it on the plot with
+//! [`PlotConfig::with_reference_curves`](crate::plot::PlotConfig::with_reference_curves), or
+//! inferred from them with [`Complexity::infer`].
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use chrono_probe::analysis::Fit;
+//! use chrono_probe::measurements::{Measurement, Point};
+//!
+//! // A quadratic algorithm
+//! let points = (1..=10)
+//!     .map(|i| Point::new(i * 100, Duration::from_nanos((i * i) as u64 * 1000)))
+//!     .collect();
+//! let measurement = Measurement::new("quadratic", points);
+//!
+//! let fit = Fit::power_law(&measurement);
+//! assert!((fit.exponent - 2.0).abs() < 1e-6);
+//! assert!(fit.r_squared > 0.999);
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::measurements::Measurement;
+
+/// A power law t = a·n<sup>b</sup> fitted to the points of a measurement, where n is the size of
+/// the input and t the time in seconds.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    /// The coefficient a of the power law, in seconds
+    pub coefficient: f64,
+    /// The exponent b of the power law
+    pub exponent: f64,
+    /// The coefficient of determination of the fit in the double logarithmic scale, 1 for a
+    /// perfect fit
+    pub r_squared: f64,
+    /// The standard error of the exponent, infinite if the fit has only two points
+    pub exponent_error: f64,
+}
+
+impl Fit {
+    /// Fits a power law to the points of the given measurement, using the size of the inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `measurement` - The measurement to fit.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the measurement has less than two points with different sizes.
+    pub fn power_law(measurement: &Measurement) -> Fit {
+        Fit::from_points(
+            measurement
+                .measurement
+                .iter()
+                .map(|point| (point.size as f64, point.time)),
+        )
+    }
+
+    /// Fits a power law to the points of the given measurement like [`Fit::power_law`], if it
+    /// has at least two points with different sizes. The points with a size or a time equal to
+    /// 0 are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `measurement` - The measurement to fit.
+    pub fn try_power_law(measurement: &Measurement) -> Option<Fit> {
+        Fit::try_from_points(
+            measurement
+                .measurement
+                .iter()
+                .map(|point| (point.size as f64, point.time)),
+        )
+    }
+
+    /// Fits a power law to the given (size, time) points like [`Fit::from_points`], if there are
+    /// at least two points with different sizes, ignoring the ones with a size or a time equal
+    /// to 0.
+    pub(crate) fn try_from_points<P>(points: P) -> Option<Fit>
+    where
+        P: IntoIterator<Item = (f64, Duration)>,
+    {
+        let points: Vec<(f64, Duration)> = points
+            .into_iter()
+            .filter(|(size, time)| *size > 0.0 && !time.is_zero())
+            .collect();
+        let first = points.first()?.0;
+        points
+            .iter()
+            .any(|(size, _)| *size != first)
+            .then(|| Fit::from_points(points))
+    }
+
+    /// Fits a power law to the given (size, time) points. The points with a size or a time equal
+    /// to 0 are ignored, as their logarithm is not defined.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if there are less than two points with different sizes.
+    pub(crate) fn from_points<P>(points: P) -> Fit
+    where
+        P: IntoIterator<Item = (f64, Duration)>,
+    {
+        let points: Vec<(f64, f64)> = points
+            .into_iter()
+            .filter(|(size, time)| *size > 0.0 && !time.is_zero())
+            .map(|(size, time)| (size.ln(), time.as_secs_f64().ln()))
+            .collect();
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        let sxy: f64 = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let syy: f64 = points.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+        assert!(
+            sxx > 0.0,
+            "At least two points with different sizes are needed to fit a power law"
+        );
+
+        let exponent = sxy / sxx;
+        let r_squared = if syy > 0.0 {
+            (sxy * sxy) / (sxx * syy)
+        } else {
+            1.0
+        };
+        // The residual variance has n - 2 degrees of freedom
+        let exponent_error = if n > 2.0 {
+            ((syy - exponent * sxy).max(0.0) / ((n - 2.0) * sxx)).sqrt()
+        } else {
+            f64::INFINITY
+        };
+        Fit {
+            coefficient: (mean_y - exponent * mean_x).exp(),
+            exponent,
+            r_squared,
+            exponent_error,
+        }
+    }
+
+    /// Returns the time predicted by the fit for the given size.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size of the input.
+    pub fn predict(&self, size: f64) -> Duration {
+        Duration::try_from_secs_f64(self.coefficient * size.powf(self.exponent))
+            .unwrap_or(Duration::MAX)
+    }
+}
+
+impl Display for Fit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "t = {:.3e}·n^{:.3} s, R² = {:.4}",
+            self.coefficient, self.exponent, self.r_squared
+        )
+    }
+}
+
+/// A canonical growth rate of the time complexity of an algorithm.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    /// Constant time, O(1)
+    Constant,
+    /// Logarithmic time, O(log n)
+    LogN,
+    /// Linear time, O(n)
+    N,
+    /// Linearithmic time, O(n log n)
+    NLogN,
+    /// Quadratic time, O(n<sup>2</sup>)
+    NSquared,
+    /// Cubic time, O(n<sup>3</sup>)
+    NCubed,
+}
+
+impl Complexity {
+    /// All the growth rates, from the slowest to the fastest.
+    const ALL: [Complexity; 6] = [
+        Complexity::Constant,
+        Complexity::LogN,
+        Complexity::N,
+        Complexity::NLogN,
+        Complexity::NSquared,
+        Complexity::NCubed,
+    ];
+
+    /// Infers the growth rate closest to the points of the given measurement, using the size of
+    /// the inputs: the one whose curve, scaled by the best constant factor, has the smallest
+    /// squared error from the times in a double logarithmic scale.
+    ///
+    /// Returns `None` if the measurement has less than two points with a size greater than 1 and
+    /// a time greater than 0.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use chrono_probe::analysis::Complexity;
+    /// use chrono_probe::measurements::{Measurement, Point};
+    ///
+    /// let points = (1..=10)
+    ///     .map(|i| {
+    ///         let size = i * 1000;
+    ///         Point::new(size, Duration::from_nanos((size as f64 * (size as f64).log2()) as u64))
+    ///     })
+    ///     .collect();
+    /// let measurement = Measurement::new("linearithmic", points);
+    ///
+    /// assert_eq!(Complexity::infer(&measurement), Some(Complexity::NLogN));
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `measurement` - The measurement whose growth rate is inferred.
+    pub fn infer(measurement: &Measurement) -> Option<Complexity> {
+        let points: Vec<(f64, f64)> = measurement
+            .measurement
+            .iter()
+            .filter(|point| point.size > 1 && !point.time.is_zero())
+            .map(|point| (point.size as f64, point.time.as_secs_f64().ln()))
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+        let error = |complexity: &Complexity| {
+            // ln t - ln g(n) is the logarithm of the constant factor, estimated by its mean
+            let offsets: Vec<f64> = points
+                .iter()
+                .map(|(size, time)| time - complexity.evaluate(*size).ln())
+                .collect();
+            let mean = offsets.iter().sum::<f64>() / offsets.len() as f64;
+            offsets
+                .iter()
+                .map(|offset| (offset - mean).powi(2))
+                .sum::<f64>()
+        };
+        Complexity::ALL
+            .iter()
+            .min_by(|a, b| error(a).total_cmp(&error(b)))
+            .copied()
+    }
+
+    /// Returns the growth rate in big O notation, without the O, e.g. "n log n".
+    pub(crate) fn growth(&self) -> &'static str {
+        match self {
+            Complexity::Constant => "1",
+            Complexity::LogN => "log n",
+            Complexity::N => "n",
+            Complexity::NLogN => "n log n",
+            Complexity::NSquared => "n²",
+            Complexity::NCubed => "n³",
+        }
+    }
+
+    /// Returns the value of the growth rate for the given size, up to a constant factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The size of the input.
+    pub fn evaluate(&self, n: f64) -> f64 {
+        match self {
+            Complexity::Constant => 1.0,
+            Complexity::LogN => n.log2(),
+            Complexity::N => n,
+            Complexity::NLogN => n * n.log2(),
+            Complexity::NSquared => n * n,
+            Complexity::NCubed => n * n * n,
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "O({})", self.growth())
+    }
+}
@@ -0,0 +1,108 @@
+//! # Environment
+//!
+//! This module describes the machine and the build on which the measurements were taken, since
+//! the times measured on different machines, or with different compilers or optimization levels,
+//! can't be compared.
+//!
+//! The [`Environment`] is captured automatically by the measure functions and saved in the
+//! [`Measurements`](crate::measurements::Measurements), so it is kept in their files. The
+//! compiler, the optimization level and the architecture are always known, while the model, the
+//! cores and the frequency of the CPU and the version of the operating system are detected with
+//! [sysinfo](https://docs.rs/sysinfo) only if the `sysinfo` feature is enabled. Without it, the
+//! model of the CPU is still read from `/proc/cpuinfo` where available.
+
+use serde::{Deserialize, Serialize};
+
+/// The machine and the build on which some measurements were taken.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    /// The model of the CPU, if known
+    pub cpu_model: Option<String>,
+    /// The number of logical cores available to the process, if known
+    pub logical_cores: Option<usize>,
+    /// The number of physical cores of the CPU, if known
+    pub physical_cores: Option<usize>,
+    /// The frequency of the CPU reported by the operating system, in MHz, if known
+    pub cpu_frequency_mhz: Option<u64>,
+    /// The operating system, with its version if known
+    pub os: String,
+    /// The architecture of the CPU, e.g. `x86_64`
+    pub arch: String,
+    /// The version of the Rust compiler that built chrono-probe, if known
+    pub rustc_version: Option<String>,
+    /// The optimization level of chrono-probe, e.g. `3` for the release profile
+    pub opt_level: String,
+    /// Whether the debug assertions were enabled
+    pub debug_assertions: bool,
+    /// The version of chrono-probe
+    pub chrono_probe_version: String,
+}
+
+impl Environment {
+    /// Captures the [`Environment`] of the running process.
+    ///
+    /// The information about the CPU and the operating system is only detected if the `sysinfo`
+    /// feature is enabled, otherwise only the number of logical cores, the name of the operating
+    /// system and, where `/proc/cpuinfo` is available, the model of the CPU are known.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono_probe::environment::Environment;
+    ///
+    /// let environment = Environment::capture();
+    /// println!("Measured on {} ({})", environment.os, environment.arch);
+    /// ```
+    pub fn capture() -> Environment {
+        let rustc_version = env!("CHRONO_PROBE_RUSTC_VERSION");
+        let environment = Environment {
+            cpu_model: cpuinfo_model(),
+            logical_cores: std::thread::available_parallelism()
+                .ok()
+                .map(|cores| cores.get()),
+            physical_cores: None,
+            cpu_frequency_mhz: None,
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            rustc_version: (!rustc_version.is_empty()).then(|| rustc_version.to_string()),
+            opt_level: env!("CHRONO_PROBE_OPT_LEVEL").to_string(),
+            debug_assertions: cfg!(debug_assertions),
+            chrono_probe_version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        #[cfg(feature = "sysinfo")]
+        let environment = environment.with_system();
+        environment
+    }
+
+    /// Fills in the information about the CPU and the operating system detected by sysinfo.
+    #[cfg(feature = "sysinfo")]
+    fn with_system(mut self) -> Environment {
+        use sysinfo::System;
+
+        let mut system = System::new();
+        system.refresh_cpu_all();
+        if let Some(cpu) = system.cpus().first() {
+            let brand = cpu.brand().trim();
+            if !brand.is_empty() {
+                self.cpu_model = Some(brand.to_string());
+            }
+            if cpu.frequency() > 0 {
+                self.cpu_frequency_mhz = Some(cpu.frequency());
+            }
+        }
+        self.physical_cores = System::physical_core_count();
+        if let Some(os) = System::long_os_version() {
+            self.os = os;
+        }
+        self
+    }
+}
+
+/// Returns the model of the CPU, read from `/proc/cpuinfo` where available.
+fn cpuinfo_model() -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "model name").then(|| value.trim().to_owned())
+    })
+}
@@ -0,0 +1,153 @@
+//! Combinators to compose existing input generators.
+//!
+//! Writing a new [`Input`] implementation for every small variation of an existing generator
+//! is tedious. This module provides two combinators on [`InputBuilder`]:
+//!
+//! * [`InputBuilder::map`]: applies a transformation to every generated input.
+//! * [`InputBuilder::zip`]: generates a pair of inputs of the same size.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::geometry::{PointSet, PointSetGenerator};
+//! use chrono_probe::input::InputBuilder;
+//!
+//! // Generate point sets whose points are sorted by their x coordinate
+//! let builder: InputBuilder<PointSet, _> =
+//!     InputBuilder::new(Uniform::new(10..=1000).unwrap(), PointSetGenerator::default());
+//! let sorted_builder = builder.map(|mut points| {
+//!     points.sort_by(|a, b| a.x.total_cmp(&b.x));
+//!     points
+//! });
+//!
+//! let inputs = sorted_builder.build(10);
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::input::distribution::Distribution;
+use crate::input::{Input, InputBuilder};
+
+/// An input obtained by applying a transformation to an input of type `I`.
+///
+/// It dereferences to the inner input, so algorithms written for `I` can be used on it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(transparent)]
+pub struct Mapped<I>(pub I);
+
+/// The builder of [`Mapped`]: the builder of the inner input and the transformation to apply.
+pub struct MapBuilder<'a, I: Input> {
+    builder: I::Builder<'a>,
+    f: Box<dyn Fn(I) -> I + 'a>,
+}
+
+impl<I> Deref for Mapped<I> {
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<I> DerefMut for Mapped<I> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<I: Input> Debug for MapBuilder<'_, I>
+where
+    for<'a> I::Builder<'a>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Mapped {:?}", self.builder)
+    }
+}
+
+impl<I: Input> Input for Mapped<I> {
+    type Builder<'a> = MapBuilder<'a, I>;
+
+    fn get_size(&self) -> usize {
+        self.0.get_size()
+    }
+
+    fn get_size_bytes(&self) -> Option<usize> {
+        self.0.get_size_bytes()
+    }
+
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+        Mapped((builder.f)(I::generate_input(size, &builder.builder)))
+    }
+
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self {
+        Mapped((builder.f)(I::generate_input_with(
+            size,
+            &builder.builder,
+            rng,
+        )))
+    }
+}
+
+/// A pair of inputs generated with the same size.
+///
+/// The size of the pair is the size of the first input.
+impl<A: Input, B: Input> Input for (A, B) {
+    type Builder<'a> = (A::Builder<'a>, B::Builder<'a>);
+
+    fn get_size(&self) -> usize {
+        self.0.get_size()
+    }
+
+    fn get_size_bytes(&self) -> Option<usize> {
+        Some(self.0.get_size_bytes()? + self.1.get_size_bytes()?)
+    }
+
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+        (
+            A::generate_input(size, &builder.0),
+            B::generate_input(size, &builder.1),
+        )
+    }
+
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self {
+        (
+            A::generate_input_with(size, &builder.0, rng),
+            B::generate_input_with(size, &builder.1, rng),
+        )
+    }
+}
+
+impl<'a, I: Input, D: Distribution> InputBuilder<'a, I, D> {
+    /// Returns a new [`InputBuilder`] that applies `f` to every generated input.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The transformation to apply to the inputs.
+    pub fn map<F>(self, f: F) -> InputBuilder<'a, Mapped<I>, D>
+    where
+        F: Fn(I) -> I + 'a,
+    {
+        InputBuilder::new(
+            self.distribution,
+            MapBuilder {
+                builder: self.builder,
+                f: Box::new(f),
+            },
+        )
+    }
+
+    /// Returns a new [`InputBuilder`] that generates pairs of inputs of the same size: the first
+    /// one is generated by this builder, the second one by the builder of `J` given as argument.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The builder used to generate the second input of the pair.
+    pub fn zip<J: Input>(self, other: J::Builder<'a>) -> InputBuilder<'a, (I, J), D> {
+        InputBuilder::new(self.distribution, (self.builder, other))
+    }
+}
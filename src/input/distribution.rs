@@ -18,7 +18,7 @@
 //! use chrono_probe::input::distribution::*;
 //!
 //! // First, we create an instance of the Uniform distribution
-//! let uniform = Uniform::new(1..=100);
+//! let uniform = Uniform::new(1..=100).unwrap();
 //!
 //! // Then we generate a vector of 10 input sizes using the distribution
 //! let lengths = uniform.generate(10);
@@ -27,6 +27,38 @@
 //! println!("{:?}", lengths);
 //! ```
 //!
+//! ## Serialization
+//!
+//! The predefined distributions implement [`Serialize`] and [`Deserialize`], so that the
+//! configuration of an experiment can be saved together with its results and loaded back. The
+//! loaded parameters are checked like the ones given to the constructors.
+//!
+//! ```
+//! use chrono_probe::input::distribution::*;
+//!
+//! let uniform = Uniform::new(1..=100).unwrap();
+//! let json = serde_json::to_string(&uniform).unwrap();
+//! let loaded: Uniform = serde_json::from_str(&json).unwrap();
+//! assert_eq!(loaded.generate(10), uniform.generate(10));
+//!
+//! let json = r#"{"range": {"start": 1, "end": 100}, "alpha": -1.0, "gen_type": "Random"}"#;
+//! assert!(serde_json::from_str::<Pareto>(json).is_err());
+//! ```
+//!
+//! ## Introspection
+//!
+//! Every distribution describes itself with [`Distribution::info`], which returns its name, its
+//! parameters and its range:
+//!
+//! ```
+//! use chrono_probe::input::distribution::*;
+//!
+//! let info = Normal::new(1..=1000, 500.0, 100.0).unwrap().info();
+//! assert_eq!(info.name, "Normal");
+//! assert_eq!(info.params["stddev"], 100.0);
+//! assert_eq!(info.range, Some(1..=1000));
+//! ```
+//!
 //! ## Custom distribution
 //!
 //! In this example we will cover the steps needed to create a custom distribution.
@@ -131,10 +163,12 @@
 //! you can use the [`ProbabilityDistribution`] trait. If you want to implement a more complex
 //! distribution, you should implement the [`Distribution`] trait directly.
 
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::Debug;
 use std::ops::RangeInclusive;
 
-use rand::{Rng, thread_rng};
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
 
 // =====================
 // = THE MODULE ITSELF =
@@ -148,15 +182,139 @@ pub trait Distribution: Debug {
     /// Generates a vector of input sizes. The number of input sizes to generate is given as
     /// argument.
     fn generate(&self, n: usize) -> Vec<usize>;
+
+    /// Generates a vector of input sizes using the given random number generator.
+    ///
+    /// This method is used instead of [`Distribution::generate`] when a seed is set on the
+    /// [`InputBuilder`](crate::input::InputBuilder), so that the input sizes can be reproduced.
+    /// By default, it ignores the random number generator and calls [`Distribution::generate`]:
+    /// it should be overridden by the distributions using randomness.
+    fn generate_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize>
+    where
+        Self: Sized,
+    {
+        let _ = rng;
+        self.generate(n)
+    }
+
+    /// Returns the range of the input sizes generated by the distribution, if known.
+    ///
+    /// By default, it returns `None`. It must be overridden to allow the stratification of the
+    /// input sizes (see [`InputBuilder::with_buckets`](crate::input::InputBuilder::with_buckets)).
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        None
+    }
+
+    /// Returns a description of the distribution, that can be used in reports, plots and
+    /// serialized metadata.
+    ///
+    /// By default, the name is the name of the type, there are no parameters and the range is
+    /// the one returned by [`Distribution::range`].
+    fn info(&self) -> DistributionInfo {
+        DistributionInfo {
+            name: short_type_name::<Self>(),
+            params: BTreeMap::new(),
+            range: self.range(),
+        }
+    }
+}
+
+/// A description of a distribution: its name, its numeric parameters and its range.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributionInfo {
+    /// The name of the distribution
+    pub name: String,
+    /// The numeric parameters of the distribution, by name
+    pub params: BTreeMap<String, f64>,
+    /// The range of the input sizes generated by the distribution, if known
+    pub range: Option<RangeInclusive<usize>>,
+}
+
+/// Returns the name of the given type, without its path and its generic parameters.
+fn short_type_name<T: ?Sized>() -> String {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name).to_owned()
 }
 
 /// This enum defines the possible generation types.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GenerationType {
     /// Generates input in fixed intervals.
     FixedIntervals,
     /// Generates input in random intervals.
     Random,
+    /// Splits \[0,1] in as many strata as input sizes, and generates one input in a random
+    /// position of each stratum. The input sizes cover the whole distribution like with
+    /// [`GenerationType::FixedIntervals`], while still being random like with
+    /// [`GenerationType::Random`].
+    Stratified,
+    /// Generates input in fixed intervals, then moves each of them by a random amount of at most
+    /// the given fraction of the interval, in either direction. The input sizes cover the whole
+    /// distribution evenly, but they don't always hit the same sizes (e.g. powers of two).
+    ///
+    /// ```
+    /// use chrono_probe::input::distribution::*;
+    ///
+    /// let mut uniform = Uniform::new(0..=1000).unwrap();
+    /// uniform.set_gen_type(GenerationType::Jittered(Jitter::new(0.1).unwrap()));
+    /// let sizes = uniform.generate(3);
+    /// assert!((450..=550).contains(&sizes[1]));
+    /// ```
+    Jittered(Jitter),
+}
+
+/// The fraction of the interval by which [`GenerationType::Jittered`] moves the input sizes,
+/// between 0 and 1.
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// assert_eq!(Jitter::new(0.25).unwrap().fraction(), 0.25);
+/// assert!(Jitter::new(1.5).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Jitter(f64);
+
+impl Jitter {
+    /// Creates a new jitter.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - The largest movement of an input size, as a fraction of the interval.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::InvalidParameter`] if `fraction` is not between 0 and 1.
+    pub fn new(fraction: f64) -> Result<Self, DistributionError> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(DistributionError::InvalidParameter {
+                name: "fraction",
+                requirement: "between 0 and 1",
+            });
+        }
+        Ok(Jitter(fraction))
+    }
+
+    /// Returns the fraction of the interval.
+    pub fn fraction(&self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for Jitter {
+    type Error = DistributionError;
+
+    fn try_from(fraction: f64) -> Result<Self, Self::Error> {
+        Jitter::new(fraction)
+    }
+}
+
+impl From<Jitter> for f64 {
+    fn from(jitter: Jitter) -> Self {
+        jitter.0
+    }
 }
 
 // ==============================
@@ -181,44 +339,362 @@ pub trait ProbabilityDistribution {
 
     /// Returns the generation type of the distribution.
     ///
-    /// This is used to determine whether the input sizes should be generated in fixed intervals or
-    /// in random intervals. By default, it returns [`GenerationType::Random`] but it can be
-    /// overridden to return the desired generation type.
+    /// This is used to determine whether the input sizes should be generated in fixed intervals,
+    /// in random intervals or in random positions of fixed strata. By default, it returns
+    /// [`GenerationType::Random`] but it can be overridden to return the desired generation type.
     fn get_gen_type(&self) -> &GenerationType {
         &GenerationType::Random
     }
+
+    /// Returns the range of the values returned by [`ProbabilityDistribution::inverse_cdf`], if
+    /// known. By default, it returns `None`.
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        None
+    }
+
+    /// Returns the numeric parameters of the distribution, by name, used to describe it (see
+    /// [`Distribution::info`]). By default, it returns no parameters.
+    fn params(&self) -> BTreeMap<String, f64> {
+        BTreeMap::new()
+    }
+
+    /// Returns the name of the distribution, used to describe it (see [`Distribution::info`]).
+    /// By default, it returns the name of the type.
+    fn name(&self) -> String {
+        short_type_name::<Self>()
+    }
+}
+
+/// This trait defines a distribution of real values, e.g. of a continuous parameter of the
+/// inputs (like a tolerance or a density) rather than of their size.
+///
+/// It is the continuous counterpart of [`Distribution`]: every type implementing
+/// [`ProbabilityDistribution`] and [`Debug`] also implements this trait, so the same
+/// implementations can generate both input sizes and parameter values. Their values are real, but
+/// they lie in their range of sizes: to generate the values in a range with real bounds, wrap the
+/// distribution in [`Rescaled`]. The values can be swept with
+/// [`ParameterSweep::from_distribution`](crate::sweep::ParameterSweep::from_distribution).
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let values = Uniform::new(0..=1).unwrap().generate_values(5);
+/// assert_eq!(values, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+/// ```
+pub trait ContinuousDistribution: Debug {
+    /// Generates a vector of values. The number of values to generate is given as argument.
+    fn generate_values(&self, n: usize) -> Vec<f64>;
+
+    /// Generates a vector of values using the given random number generator.
+    ///
+    /// By default, it ignores the random number generator and calls
+    /// [`ContinuousDistribution::generate_values`].
+    fn generate_values_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<f64>
+    where
+        Self: Sized,
+    {
+        let _ = rng;
+        self.generate_values(n)
+    }
+}
+
+impl<T: ProbabilityDistribution + Debug> ContinuousDistribution for T {
+    fn generate_values(&self, n: usize) -> Vec<f64> {
+        self.generate_values_with(&mut thread_rng(), n)
+    }
+
+    fn generate_values_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<f64> {
+        assert!(n > 0, "The number of values must be greater than zero");
+        (0..n)
+            .map(|i| {
+                let u: f64 = match self.get_gen_type() {
+                    GenerationType::FixedIntervals => {
+                        if n != 1 {
+                            i as f64 / (n - 1) as f64
+                        } else {
+                            0.0
+                        }
+                    }
+                    GenerationType::Random => rng.gen::<f64>(),
+                    GenerationType::Stratified => (i as f64 + rng.gen::<f64>()) / n as f64,
+                    GenerationType::Jittered(jitter) => {
+                        if n != 1 {
+                            let fraction = jitter.fraction();
+                            let jitter = rng.gen_range(-fraction..=fraction);
+                            ((i as f64 + jitter) / (n - 1) as f64).clamp(0.0, 1.0)
+                        } else {
+                            0.0
+                        }
+                    }
+                };
+                self.inverse_cdf(u)
+            })
+            .collect()
+    }
 }
 
 impl<T: ProbabilityDistribution + Debug> Distribution for T {
     fn generate(&self, n: usize) -> Vec<usize> {
+        self.generate_with(&mut thread_rng(), n)
+    }
+
+    fn generate_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
         assert!(n > 0, "The number of input sizes must be greater than zero");
-        // Preallocating the vector of input sizes
-        let mut lengths = Vec::with_capacity(n);
-
-        for i in 0..n {
-            let u: f64 = match self.get_gen_type() {
-                GenerationType::FixedIntervals => {
-                    if n != 1 {
-                        i as f64 / (n - 1) as f64
-                    } else {
-                        0.0
-                    }
-                }
-                GenerationType::Random => thread_rng().gen::<f64>(),
-            };
+        self.generate_values_with(rng, n)
+            .into_iter()
+            .map(|x| x as usize)
+            .collect()
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        ProbabilityDistribution::range(self)
+    }
 
-            let x = self.inverse_cdf(u);
+    fn info(&self) -> DistributionInfo {
+        DistributionInfo {
+            name: self.name(),
+            params: self.params(),
+            range: Distribution::range(self),
+        }
+    }
+}
+
+/// Generates `n` input sizes using the given distribution, guaranteeing that the same number of
+/// sizes falls in each of `buckets` equally wide buckets partitioning the range of the
+/// distribution (if `n` is not a multiple of `buckets`, the first buckets get one more size).
+///
+/// The sizes are sampled from the distribution (with [`Distribution::generate_with`]) and
+/// discarded when their bucket is already full. If the distribution rarely generates sizes in
+/// some buckets, the missing sizes are chosen uniformly at random in those buckets.
+pub(crate) fn stratify<D: Distribution, R: Rng>(
+    distribution: &D,
+    n: usize,
+    buckets: usize,
+    rng: &mut R,
+) -> Vec<usize> {
+    let range = distribution
+        .range()
+        .expect("The distribution must report its range to generate the sizes in buckets");
+    let (min, max) = (*range.start(), *range.end());
+    let width = (max - min) as u128 + 1;
+    assert!(buckets > 0, "The number of buckets must be greater than 0");
+    assert!(
+        buckets <= n,
+        "The number of buckets must not be greater than the number of input sizes"
+    );
+    assert!(
+        buckets as u128 <= width,
+        "The number of buckets must not be greater than the number of possible input sizes"
+    );
+
+    // The first size of each bucket (and the first size after the range, at the end)
+    let bounds: Vec<usize> = (0..=buckets)
+        .map(|b| min + (b as u128 * width / buckets as u128) as usize)
+        .collect();
+    let quota = |b: usize| n / buckets + usize::from(b < n % buckets);
+    let mut stratified: Vec<Vec<usize>> =
+        (0..buckets).map(|b| Vec::with_capacity(quota(b))).collect();
+    let mut missing = n;
+
+    // Sample the distribution, giving up after a reasonable number of attempts
+    for _ in 0..100 {
+        for size in distribution.generate_with(rng, n) {
+            if !range.contains(&size) {
+                continue;
+            }
+            let b = (((size - min) as u128 + 1) * buckets as u128 - 1) / width;
+            let bucket = &mut stratified[b as usize];
+            if bucket.len() < quota(b as usize) {
+                bucket.push(size);
+                missing -= 1;
+            }
+        }
+        if missing == 0 {
+            break;
+        }
+    }
+
+    // Fill the buckets the distribution didn't reach
+    for (b, bucket) in stratified.iter_mut().enumerate() {
+        while bucket.len() < quota(b) {
+            bucket.push(rng.gen_range(bounds[b]..=bounds[b + 1] - 1));
+        }
+    }
+    stratified.into_iter().flatten().collect()
+}
+
+/// The error returned when a distribution is created with invalid parameters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DistributionError {
+    /// The range of the distribution is empty, e.g. `10..=1`
+    EmptyRange,
+    /// The range of the distribution starts at 0, but the distribution needs to take its
+    /// logarithm or to divide by its start
+    ZeroStart,
+    /// The range of the distribution contains a single size, but the distribution needs its
+    /// start and its end to differ
+    DegenerateRange,
+    /// The distribution would generate no sizes, e.g. its range contains no powers of its base
+    NoSizes,
+    /// A numeric parameter of the distribution is out of its valid values, e.g. a negative shape
+    InvalidParameter {
+        /// The name of the parameter
+        name: &'static str,
+        /// The condition the parameter must satisfy, e.g. "greater than 0"
+        requirement: &'static str,
+    },
+}
 
-            lengths.push(x as usize);
+impl std::fmt::Display for DistributionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DistributionError::EmptyRange => write!(f, "the range must not be empty"),
+            DistributionError::ZeroStart => write!(f, "the range must not contain 0"),
+            DistributionError::DegenerateRange => {
+                write!(f, "the range must contain more than one size")
+            }
+            DistributionError::NoSizes => write!(f, "the distribution must contain a size"),
+            DistributionError::InvalidParameter { name, requirement } => {
+                write!(f, "the parameter `{}` must be {}", name, requirement)
+            }
         }
-        lengths
+    }
+}
+
+impl std::error::Error for DistributionError {}
+
+/// The number of bins of the histogram of a [`ValidationReport`].
+const VALIDATION_BINS: usize = 10;
+
+/// The empirical statistics of a large sample of input sizes generated by a distribution,
+/// returned by [`validate`] and [`validate_against_cdf`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    /// The number of sizes in the sample
+    pub samples: usize,
+    /// The smallest size of the sample
+    pub min: usize,
+    /// The largest size of the sample
+    pub max: usize,
+    /// The mean size of the sample
+    pub mean: f64,
+    /// The number of sizes of the sample falling in each of at most 10 equally wide bins between
+    /// the smallest and the largest size (the last one ends at the largest size)
+    pub histogram: Vec<(RangeInclusive<usize>, usize)>,
+    /// The Kolmogorov-Smirnov statistic of the sample against the intended cumulative
+    /// distribution function, i.e. the largest distance between the two, if one was given
+    pub ks_statistic: Option<f64>,
+}
+
+/// Generates a sample of `n` input sizes with the given distribution and reports their empirical
+/// statistics, so that a custom distribution can be checked before using it in a long
+/// measurement.
+///
+/// # Arguments
+///
+/// * `distribution` - The distribution to validate.
+/// * `n` - The number of sizes of the sample, it should be large (e.g. 10000).
+///
+/// # Example
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let report = validate(&Uniform::new(0..=1000).unwrap(), 10_000);
+/// assert_eq!((report.min, report.max), (0, 1000));
+/// assert!((report.mean - 500.0).abs() < 1.0);
+/// // About 10% of the sizes in each bin
+/// assert_eq!(report.histogram[9].0, 900..=1000);
+/// assert!(report.histogram.iter().all(|(_, count)| (950..=1050).contains(count)));
+/// ```
+pub fn validate<D: Distribution>(distribution: &D, n: usize) -> ValidationReport {
+    let mut sample = distribution.generate(n);
+    sample.sort_unstable();
+    report(&sample)
+}
+
+/// Computes the statistics of a sorted sample of input sizes.
+fn report(sample: &[usize]) -> ValidationReport {
+    assert!(!sample.is_empty(), "The distribution generated no sizes");
+    let min = sample[0];
+    let max = sample[sample.len() - 1];
+    let mean = sample.iter().map(|&size| size as f64).sum::<f64>() / sample.len() as f64;
+
+    // Equally wide bins, the width is rounded up so that the remainder of the division doesn't
+    // end up in the last bin, which includes the largest size
+    let width = (max - min).div_ceil(VALIDATION_BINS).max(1);
+    let mut histogram: Vec<(RangeInclusive<usize>, usize)> = (0..VALIDATION_BINS)
+        .map(|b| min + b * width)
+        .take_while(|&start| start <= max)
+        .map(|start| (start..=start + width - 1, 0))
+        .collect();
+    let last = histogram.len() - 1;
+    histogram[last].0 = *histogram[last].0.start()..=max;
+    for &size in sample {
+        histogram[((size - min) / width).min(last)].1 += 1;
+    }
+
+    ValidationReport {
+        samples: sample.len(),
+        min,
+        max,
+        mean,
+        histogram,
+        ks_statistic: None,
+    }
+}
+
+/// Like [`validate`], but also computes the Kolmogorov-Smirnov statistic of the sample against
+/// the cumulative distribution function the distribution is meant to follow. This is useful to
+/// check a custom [`ProbabilityDistribution::inverse_cdf`]: a statistic much larger than
+/// 1/&radic;n means that the sizes don't follow the intended distribution.
+///
+/// The input sizes are integers, so the statistic is increased by at most the probability of an
+/// interval of length 1.
+///
+/// # Arguments
+///
+/// * `distribution` - The distribution to validate.
+/// * `n` - The number of sizes of the sample, it should be large (e.g. 10000).
+/// * `cdf` - The intended cumulative distribution function of the sizes.
+///
+/// # Example
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let mut uniform = Uniform::new(0..=1000).unwrap();
+/// uniform.set_gen_type(GenerationType::Random);
+/// let report = validate_against_cdf(&uniform, 10_000, |x| x / 1000.0);
+/// assert!(report.ks_statistic.unwrap() < 0.05);
+/// ```
+pub fn validate_against_cdf<D, F>(distribution: &D, n: usize, cdf: F) -> ValidationReport
+where
+    D: Distribution,
+    F: Fn(f64) -> f64,
+{
+    let mut sample = distribution.generate(n);
+    sample.sort_unstable();
+    let len = sample.len() as f64;
+    let ks_statistic = sample
+        .iter()
+        .enumerate()
+        .map(|(i, &size)| {
+            let expected = cdf(size as f64);
+            (expected - i as f64 / len).max((i + 1) as f64 / len - expected)
+        })
+        .fold(0.0, f64::max);
+    ValidationReport {
+        ks_statistic: Some(ks_statistic),
+        ..report(&sample)
     }
 }
 
 /// The struct representing an uniform distribution.
 ///
 /// Given a range, it generates a vector of uniform distributed input sizes.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "UniformFields")]
 pub struct Uniform {
     range: RangeInclusive<usize>,
     gen_type: GenerationType,
@@ -230,16 +706,22 @@ impl Uniform {
     /// # Arguments
     ///
     /// * `range` - The range of the distribution.
-    pub fn new(range: RangeInclusive<usize>) -> Self {
-        assert!(!range.is_empty(), "The range must not be empty.");
-        Uniform {
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if the range is empty.
+    pub fn new(range: RangeInclusive<usize>) -> Result<Self, DistributionError> {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        Ok(Uniform {
             range,
             gen_type: GenerationType::FixedIntervals,
-        }
+        })
     }
 
     /// Sets the generation type of the exponential distribution.
-    /// The generation type can be either fixed intervals or random.
+    /// The generation type can be either fixed intervals, random, stratified or jittered.
     ///
     /// # Arguments
     ///
@@ -265,12 +747,25 @@ impl ProbabilityDistribution for Uniform {
     fn get_gen_type(&self) -> &GenerationType {
         &self.gen_type
     }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        Some(self.range.clone())
+    }
 }
 
 /// The struct representing an exponential distribution.
 ///
 /// Given a range, it generates a vector of input sizes using an exponential distribution.
-#[derive(Clone)]
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// assert!(Exponential::new(10..=1000).is_ok());
+/// assert_eq!(Exponential::new(0..=1000).unwrap_err(), DistributionError::ZeroStart);
+/// assert_eq!(Exponential::new(10..=10).unwrap_err(), DistributionError::DegenerateRange);
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "ExponentialFields")]
 pub struct Exponential {
     range: RangeInclusive<usize>,
     lambda: f64,
@@ -284,16 +779,30 @@ impl Exponential {
     /// # Arguments
     ///
     /// * `range` - The range of the distribution.
-    pub fn new(range: RangeInclusive<usize>) -> Self {
-        assert!(!range.is_empty(), "The range must not be empty.");
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if the range is empty.
+    /// * [`DistributionError::ZeroStart`] if the range starts at 0.
+    /// * [`DistributionError::DegenerateRange`] if the range contains a single size.
+    pub fn new(range: RangeInclusive<usize>) -> Result<Self, DistributionError> {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        if *range.start() == 0 {
+            return Err(DistributionError::ZeroStart);
+        }
+        if range.start() == range.end() {
+            return Err(DistributionError::DegenerateRange);
+        }
         let lambda =
             ((range.end() / range.start()) as f64).ln() / ((range.end() - range.start()) as f64);
         let gen_type = GenerationType::FixedIntervals;
-        Exponential {
+        Ok(Exponential {
             range,
             lambda,
             gen_type,
-        }
+        })
     }
 
     /// Sets the &lambda; of the exponential distribution.
@@ -302,12 +811,12 @@ impl Exponential {
     ///
     /// * `lambda` - The new &lambda; of the exponential distribution.
     pub fn set_lambda(&mut self, lambda: f64) {
-        assert!(lambda > 0.0, "Lambda must be grater then zero");
+        assert!(lambda > 0.0, "Lambda must be greater than zero");
         self.lambda = lambda;
     }
 
     /// Sets the generation type of the exponential distribution.
-    /// The generation type can be either fixed intervals or random.
+    /// The generation type can be either fixed intervals, random, stratified or jittered.
     ///
     /// # Arguments
     ///
@@ -380,53 +889,1653 @@ impl ProbabilityDistribution for Exponential {
     fn get_gen_type(&self) -> &GenerationType {
         &self.gen_type
     }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        Some(self.range.clone())
+    }
+
+    fn params(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([("lambda".to_owned(), self.lambda)])
+    }
 }
 
-/// The struct representing a uniform distribution.
+/// The struct representing a log-uniform (or reciprocal) distribution.
+///
+/// Given a range, it generates a vector of input sizes whose logarithm is uniformly distributed:
+/// each order of magnitude of the range (e.g. 10..100 and 100..1000) gets the same number of
+/// input sizes. This is usually what is wanted when the range spans several orders of magnitude
+/// and the results are plotted in a log-log scale.
 ///
-/// Given a range, it generates a vector of input sizes using a uniform distribution.
-pub struct Reciprocal {
+/// Prefer it over [`Exponential`], whose sizes are concentrated at the small end of the range
+/// and whose shape depends on &lambda;, unless you need to tune how fast the density decreases.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "LogUniformFields")]
+pub struct LogUniform {
     range: RangeInclusive<usize>,
     gen_type: GenerationType,
 }
 
-impl Reciprocal {
-    /// Creates a new uniform distribution.
+/// The former name of [`LogUniform`], kept for backward compatibility.
+pub type Reciprocal = LogUniform;
+
+impl LogUniform {
+    /// Creates a new log-uniform distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the distribution, it must not contain 0.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if the range is empty.
+    /// * [`DistributionError::ZeroStart`] if the range starts at 0.
+    pub fn new(range: RangeInclusive<usize>) -> Result<Self, DistributionError> {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        if *range.start() == 0 {
+            return Err(DistributionError::ZeroStart);
+        }
+        Ok(LogUniform {
+            range,
+            gen_type: GenerationType::FixedIntervals,
+        })
+    }
+
+    /// Sets the generation type of the log-uniform distribution.
+    /// The generation type can be either fixed intervals, random, stratified or jittered.
+    ///
+    /// # Arguments
+    ///
+    /// * `gen_type` - The new generation type of the log-uniform distribution.
+    pub fn set_gen_type(&mut self, gen_type: GenerationType) {
+        self.gen_type = gen_type;
+    }
+}
+
+impl Debug for LogUniform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LogUniform, generation type: {:?}", self.gen_type)
+    }
+}
+
+impl ProbabilityDistribution for LogUniform {
+    fn inverse_cdf(&self, u: f64) -> f64 {
+        (*self.range.end() as f64 / *self.range.start() as f64).powf(u) * *self.range.start() as f64
+    }
+
+    fn get_gen_type(&self) -> &GenerationType {
+        &self.gen_type
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        Some(self.range.clone())
+    }
+}
+
+/// The struct representing a truncated normal distribution.
+///
+/// Given a range, a mean and a standard deviation, it generates a vector of input sizes using a
+/// normal distribution truncated to the range. It is useful when the sizes should cluster around
+/// a typical workload size.
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let normal = Normal::new(1..=1000, 500.0, 100.0).unwrap();
+/// let sizes = normal.generate(3);
+/// assert!((499..=500).contains(&sizes[1]));
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "NormalFields")]
+pub struct Normal {
+    range: RangeInclusive<usize>,
+    mean: f64,
+    stddev: f64,
+    gen_type: GenerationType,
+}
+
+impl Normal {
+    /// Creates a new truncated normal distribution.
     ///
     /// # Arguments
     ///
     /// * `range` - The range of the distribution.
-    pub fn new(range: RangeInclusive<usize>) -> Self {
-        assert!(!range.is_empty(), "The range must not be empty.");
-        Reciprocal {
+    /// * `mean` - The mean of the normal distribution before the truncation.
+    /// * `stddev` - The standard deviation of the normal distribution before the truncation.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if the range is empty.
+    /// * [`DistributionError::InvalidParameter`] if `stddev` is not greater than zero.
+    pub fn new(
+        range: RangeInclusive<usize>,
+        mean: f64,
+        stddev: f64,
+    ) -> Result<Self, DistributionError> {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        if stddev.is_nan() || stddev <= 0.0 {
+            return Err(DistributionError::InvalidParameter {
+                name: "stddev",
+                requirement: "greater than 0",
+            });
+        }
+        Ok(Normal {
             range,
+            mean,
+            stddev,
             gen_type: GenerationType::FixedIntervals,
+        })
+    }
+
+    /// Sets the generation type of the normal distribution.
+    /// The generation type can be either fixed intervals, random, stratified or jittered.
+    ///
+    /// # Arguments
+    ///
+    /// * `gen_type` - The new generation type of the normal distribution.
+    pub fn set_gen_type(&mut self, gen_type: GenerationType) {
+        self.gen_type = gen_type;
+    }
+}
+
+impl Debug for Normal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Normal μ={}, σ={}, generation type: {:?}",
+            self.mean, self.stddev, self.gen_type
+        )
+    }
+}
+
+impl ProbabilityDistribution for Normal {
+    fn inverse_cdf(&self, u: f64) -> f64 {
+        /*
+        The uniform value u ∈ [0, 1] is mapped to [Φ(a), Φ(b)], where Φ is the cumulative
+        distribution function of the standard normal distribution and a, b are the bounds of the
+        range standardized. The inverse of Φ is then applied, and the result is clamped to the
+        range to absorb the approximation errors.
+        */
+        let min = *self.range.start() as f64;
+        let max = *self.range.end() as f64;
+        let a = standard_normal_cdf((min - self.mean) / self.stddev);
+        let b = standard_normal_cdf((max - self.mean) / self.stddev);
+        let p = a + u * (b - a);
+        let x = if p <= 0.0 {
+            min
+        } else if p >= 1.0 {
+            max
+        } else {
+            self.mean + self.stddev * standard_normal_inverse_cdf(p)
+        };
+        x.clamp(min, max)
+    }
+
+    fn get_gen_type(&self) -> &GenerationType {
+        &self.gen_type
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        Some(self.range.clone())
+    }
+
+    fn params(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([
+            ("mean".to_owned(), self.mean),
+            ("stddev".to_owned(), self.stddev),
+        ])
+    }
+}
+
+/// The struct representing a Poisson distribution.
+///
+/// Given a range and a mean &lambda;, it generates a vector of input sizes equal to the start of
+/// the range plus a Poisson distributed offset with mean &lambda;, truncated to the range. It can
+/// be used to model sizes that count discrete events, like the number of requests in a time
+/// window.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "PoissonFields")]
+pub struct Poisson {
+    range: RangeInclusive<usize>,
+    lambda: f64,
+    gen_type: GenerationType,
+}
+
+impl Poisson {
+    /// Creates a new Poisson distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the distribution.
+    /// * `lambda` - The mean of the offset from the start of the range.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if the range is empty.
+    /// * [`DistributionError::InvalidParameter`] if `lambda` is not greater than zero.
+    pub fn new(range: RangeInclusive<usize>, lambda: f64) -> Result<Self, DistributionError> {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        if lambda.is_nan() || lambda <= 0.0 {
+            return Err(DistributionError::InvalidParameter {
+                name: "lambda",
+                requirement: "greater than 0",
+            });
         }
+        Ok(Poisson {
+            range,
+            lambda,
+            gen_type: GenerationType::FixedIntervals,
+        })
     }
 
-    /// Sets the generation type of the reciprocal distribution.
-    /// The generation type can be either fixed intervals or random.
+    /// Sets the generation type of the Poisson distribution.
+    /// The generation type can be either fixed intervals, random, stratified or jittered.
     ///
     /// # Arguments
     ///
-    /// * `gen_type` - The new generation type of the exponential distribution.
+    /// * `gen_type` - The new generation type of the Poisson distribution.
     pub fn set_gen_type(&mut self, gen_type: GenerationType) {
         self.gen_type = gen_type;
     }
 }
 
-impl Debug for Reciprocal {
+impl Debug for Poisson {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Reciprocal, generation type: {:?}", self.gen_type)
+        write!(
+            f,
+            "Poisson λ={}, generation type: {:?}",
+            self.lambda, self.gen_type
+        )
     }
 }
 
-impl ProbabilityDistribution for Reciprocal {
+impl ProbabilityDistribution for Poisson {
     fn inverse_cdf(&self, u: f64) -> f64 {
-        (*self.range.end() as f64 / *self.range.start() as f64).powf(u) * *self.range.start() as f64
+        let min = *self.range.start() as f64;
+        let max_offset = (self.range.end() - self.range.start()) as f64;
+        let lambda = self.lambda;
+
+        let offset = if lambda > 500.0 {
+            /*
+            For a big lambda e^-lambda underflows, but the Poisson distribution is well
+            approximated by a normal distribution with mean and variance lambda.
+            */
+            let u = u.clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+            (lambda + lambda.sqrt() * standard_normal_inverse_cdf(u)).round()
+        } else {
+            // Sum the probability mass function until the cumulative probability reaches u (or
+            // the remaining probability is negligible, as cdf may never reach 1 due to rounding)
+            let mut k = 0.0;
+            let mut pmf = (-lambda).exp();
+            let mut cdf = pmf;
+            while cdf < u && k < max_offset && (k < lambda || pmf > f64::EPSILON * f64::EPSILON) {
+                k += 1.0;
+                pmf *= lambda / k;
+                cdf += pmf;
+            }
+            k
+        };
+        min + offset.clamp(0.0, max_offset)
     }
 
     fn get_gen_type(&self) -> &GenerationType {
         &self.gen_type
     }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        Some(self.range.clone())
+    }
+
+    fn params(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([("lambda".to_owned(), self.lambda)])
+    }
+}
+
+/// The struct representing a Pareto distribution.
+///
+/// Given a range and a shape &alpha;, it generates a vector of input sizes using a Pareto
+/// distribution with scale equal to the start of the range, truncated to the range. It is a
+/// heavy-tailed distribution: most sizes are close to the start of the range, but a few huge
+/// ones dominate the total cost, and the smaller &alpha; is the heavier the tail gets.
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let pareto = Pareto::new(10..=100_000, 1.0).unwrap();
+/// let sizes = pareto.generate(100);
+/// // Half of the sizes are at most twice the start of the range
+/// assert!(sizes[50] <= 20);
+/// assert_eq!(sizes[99], 100_000);
+/// assert!(matches!(
+///     Pareto::new(10..=100_000, 0.0),
+///     Err(DistributionError::InvalidParameter { name: "alpha", .. })
+/// ));
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "ParetoFields")]
+pub struct Pareto {
+    range: RangeInclusive<usize>,
+    alpha: f64,
+    gen_type: GenerationType,
+}
+
+impl Pareto {
+    /// Creates a new Pareto distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the distribution, its start is the scale of the distribution.
+    /// * `alpha` - The shape of the distribution, it must be greater than zero.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if the range is empty.
+    /// * [`DistributionError::ZeroStart`] if the range starts at 0.
+    /// * [`DistributionError::InvalidParameter`] if `alpha` is not greater than zero.
+    pub fn new(range: RangeInclusive<usize>, alpha: f64) -> Result<Self, DistributionError> {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        if *range.start() == 0 {
+            return Err(DistributionError::ZeroStart);
+        }
+        if alpha.is_nan() || alpha <= 0.0 {
+            return Err(DistributionError::InvalidParameter {
+                name: "alpha",
+                requirement: "greater than 0",
+            });
+        }
+        Ok(Pareto {
+            range,
+            alpha,
+            gen_type: GenerationType::FixedIntervals,
+        })
+    }
+
+    /// Sets the generation type of the Pareto distribution.
+    /// The generation type can be either fixed intervals, random, stratified or jittered.
+    ///
+    /// # Arguments
+    ///
+    /// * `gen_type` - The new generation type of the Pareto distribution.
+    pub fn set_gen_type(&mut self, gen_type: GenerationType) {
+        self.gen_type = gen_type;
+    }
+}
+
+impl Debug for Pareto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Pareto α={}, generation type: {:?}",
+            self.alpha, self.gen_type
+        )
+    }
+}
+
+impl ProbabilityDistribution for Pareto {
+    fn inverse_cdf(&self, u: f64) -> f64 {
+        /*
+        The cumulative distribution function of the Pareto distribution with scale m is
+        F(x) = 1 - (m / x)^alpha. The uniform value u ∈ [0, 1] is mapped to p ∈ [0, F(max)], and
+        the inverse F^-1(p) = m / (1 - p)^(1 / alpha) is applied.
+        */
+        let min = *self.range.start() as f64;
+        let max = *self.range.end() as f64;
+        let p = u * (1.0 - (min / max).powf(self.alpha));
+        let x = min / (1.0 - p).powf(1.0 / self.alpha);
+        x.clamp(min, max)
+    }
+
+    fn get_gen_type(&self) -> &GenerationType {
+        &self.gen_type
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        Some(self.range.clone())
+    }
+
+    fn params(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([("alpha".to_owned(), self.alpha)])
+    }
+}
+
+/// The cumulative distribution function of the standard normal distribution.
+fn standard_normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / std::f64::consts::SQRT_2))
+}
+
+/// The error function, approximated with the formula 7.1.26 of Abramowitz and Stegun (maximum
+/// absolute error 1.5·10<sup>-7</sup>).
+fn erf(x: f64) -> f64 {
+    let sign = x.signum();
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let polynomial = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    sign * (1.0 - polynomial * (-x * x).exp())
+}
+
+/// The inverse of the cumulative distribution function of the standard normal distribution,
+/// approximated with the algorithm of Peter Acklam (relative error 1.15·10<sup>-9</sup>).
+/// The argument must be in (0, 1).
+fn standard_normal_inverse_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    if p < P_LOW {
+        // Lower tail
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        // Central region
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        // Upper tail
+        -standard_normal_inverse_cdf(1.0 - p)
+    }
+}
+
+/// The struct representing the geometric doubling schedule of input sizes.
+///
+/// It generates the sizes start, 2·start, 4·start, ..., up to max, the most common schedule for
+/// complexity experiments.
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let doubling = Doubling::new(100, 1000).unwrap();
+/// assert_eq!(doubling.generate(doubling.len()), vec![100, 200, 400, 800]);
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "DoublingFields")]
+pub struct Doubling {
+    start: usize,
+    max: usize,
+}
+
+impl Doubling {
+    /// Creates a new doubling schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first input size, it must be greater than 0.
+    /// * `max` - The maximum input size.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if `start` is greater than `max`.
+    /// * [`DistributionError::ZeroStart`] if `start` is 0.
+    pub fn new(start: usize, max: usize) -> Result<Self, DistributionError> {
+        if start > max {
+            return Err(DistributionError::EmptyRange);
+        }
+        if start == 0 {
+            return Err(DistributionError::ZeroStart);
+        }
+        Ok(Doubling { start, max })
+    }
+
+    /// Returns the number of input sizes of the schedule.
+    pub fn len(&self) -> usize {
+        (self.max / self.start).ilog2() as usize + 1
+    }
+
+    /// Returns true if the schedule contains no input sizes. This never happens, as the schedule
+    /// always contains the first input size.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl Debug for Doubling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Doubling from {} to {}", self.start, self.max)
+    }
+}
+
+impl Distribution for Doubling {
+    /// Generates the first `n` input sizes of the schedule. If `n` is greater than the number of
+    /// input sizes of the schedule (see [`Doubling::len`]), the whole schedule is generated.
+    fn generate(&self, n: usize) -> Vec<usize> {
+        assert!(n > 0, "The number of input sizes must be greater than zero");
+        (0..n.min(self.len())).map(|i| self.start << i).collect()
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        Some(self.start..=self.max)
+    }
+
+    fn info(&self) -> DistributionInfo {
+        DistributionInfo {
+            name: "Doubling".to_owned(),
+            params: BTreeMap::from([("start".to_owned(), self.start as f64)]),
+            range: self.range(),
+        }
+    }
+}
+
+/// The struct representing an arithmetic grid of input sizes.
+///
+/// Given a range and a step, it generates the sizes start, start + step, start + 2·step, ..., up
+/// to the end of the range. Unlike [`Uniform`] with [`GenerationType::FixedIntervals`], the
+/// spacing between the sizes doesn't depend on the number of sizes requested.
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let grid = Grid::new(100..=500, 150).unwrap();
+/// assert_eq!(grid.generate(grid.len()), vec![100, 250, 400]);
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "GridFields")]
+pub struct Grid {
+    range: RangeInclusive<usize>,
+    step: usize,
+}
+
+impl Grid {
+    /// Creates a new arithmetic grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the grid.
+    /// * `step` - The difference between two consecutive sizes, it must be greater than 0.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if the range is empty.
+    /// * [`DistributionError::InvalidParameter`] if `step` is 0.
+    pub fn new(range: RangeInclusive<usize>, step: usize) -> Result<Self, DistributionError> {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        if step == 0 {
+            return Err(DistributionError::InvalidParameter {
+                name: "step",
+                requirement: "greater than 0",
+            });
+        }
+        Ok(Grid { range, step })
+    }
+
+    /// Returns the number of sizes of the grid.
+    pub fn len(&self) -> usize {
+        (self.range.end() - self.range.start()) / self.step + 1
+    }
+
+    /// Returns true if the grid contains no sizes. This never happens, as the grid always
+    /// contains the start of the range.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl Debug for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Grid with step {}", self.step)
+    }
+}
+
+impl Distribution for Grid {
+    /// Generates the first `n` sizes of the grid. If `n` is greater than the number of sizes of
+    /// the grid (see [`Grid::len`]), the whole grid is generated.
+    fn generate(&self, n: usize) -> Vec<usize> {
+        assert!(n > 0, "The number of input sizes must be greater than zero");
+        self.range.clone().step_by(self.step).take(n).collect()
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        Some(self.range.clone())
+    }
+
+    fn info(&self) -> DistributionInfo {
+        DistributionInfo {
+            name: "Grid".to_owned(),
+            params: BTreeMap::from([("step".to_owned(), self.step as f64)]),
+            range: self.range(),
+        }
+    }
+}
+
+/// The struct representing the powers of a base within a range.
+///
+/// It generates exactly the powers of the base contained in the range, without randomness, e.g.
+/// the powers of two to measure cache effects.
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let powers = Geometric::new(2, 10..=100).unwrap();
+/// assert_eq!(powers.generate(powers.len()), vec![16, 32, 64]);
+/// assert_eq!(Geometric::new(10, 20..=90).unwrap_err(), DistributionError::NoSizes);
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "GeometricFields")]
+pub struct Geometric {
+    base: usize,
+    range: RangeInclusive<usize>,
+}
+
+impl Geometric {
+    /// Creates a new distribution of the powers of a base.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The base, it must be greater than 1.
+    /// * `range` - The range of the distribution, it must contain at least a power of the base.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::InvalidParameter`] if `base` is not greater than 1.
+    /// * [`DistributionError::EmptyRange`] if the range is empty.
+    /// * [`DistributionError::NoSizes`] if the range contains no powers of the base.
+    pub fn new(base: usize, range: RangeInclusive<usize>) -> Result<Self, DistributionError> {
+        if base <= 1 {
+            return Err(DistributionError::InvalidParameter {
+                name: "base",
+                requirement: "greater than 1",
+            });
+        }
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        let geometric = Geometric { base, range };
+        if geometric.is_empty() {
+            return Err(DistributionError::NoSizes);
+        }
+        Ok(geometric)
+    }
+
+    /// Returns the powers of the base contained in the range.
+    fn powers(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(Some(1_usize), |power| power.checked_mul(self.base))
+            .skip_while(|power| power < self.range.start())
+            .take_while(|power| power <= self.range.end())
+    }
+
+    /// Returns the number of powers of the base contained in the range.
+    pub fn len(&self) -> usize {
+        self.powers().count()
+    }
+
+    /// Returns true if the range contains no powers of the base. This never happens for a
+    /// distribution created with [`Geometric::new`].
+    pub fn is_empty(&self) -> bool {
+        self.powers().next().is_none()
+    }
+}
+
+impl Debug for Geometric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Powers of {}", self.base)
+    }
+}
+
+impl Distribution for Geometric {
+    /// Generates the first `n` powers of the base contained in the range. If `n` is greater than
+    /// the number of powers in the range (see [`Geometric::len`]), all of them are generated.
+    fn generate(&self, n: usize) -> Vec<usize> {
+        assert!(n > 0, "The number of input sizes must be greater than zero");
+        self.powers().take(n).collect()
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        Some(self.range.clone())
+    }
+
+    fn info(&self) -> DistributionInfo {
+        DistributionInfo {
+            name: "Geometric".to_owned(),
+            params: BTreeMap::from([("base".to_owned(), self.base as f64)]),
+            range: self.range(),
+        }
+    }
+}
+
+/// The struct representing explicitly chosen input sizes.
+///
+/// It generates exactly the given sizes, so that hand-picked experiment designs can be used with
+/// an [`InputBuilder`](crate::input::InputBuilder). If more sizes than the given ones are
+/// requested, the sizes are cycled, while if less are requested, they are truncated.
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let explicit = Explicit::new(vec![10, 100, 1000]).unwrap();
+/// assert_eq!(explicit.generate(2), vec![10, 100]);
+/// assert_eq!(explicit.generate(5), vec![10, 100, 1000, 10, 100]);
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "ExplicitFields")]
+pub struct Explicit {
+    sizes: Vec<usize>,
+}
+
+impl Explicit {
+    /// Creates a new distribution of explicitly chosen input sizes.
+    ///
+    /// # Arguments
+    ///
+    /// * `sizes` - The input sizes, they must not be empty.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::NoSizes`] if `sizes` is empty.
+    pub fn new(sizes: Vec<usize>) -> Result<Self, DistributionError> {
+        if sizes.is_empty() {
+            return Err(DistributionError::NoSizes);
+        }
+        Ok(Explicit { sizes })
+    }
+}
+
+impl Debug for Explicit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Explicit {:?}", self.sizes)
+    }
+}
+
+impl Distribution for Explicit {
+    fn generate(&self, n: usize) -> Vec<usize> {
+        assert!(n > 0, "The number of input sizes must be greater than zero");
+        self.sizes.iter().copied().cycle().take(n).collect()
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        let min = *self.sizes.iter().min()?;
+        let max = *self.sizes.iter().max()?;
+        Some(min..=max)
+    }
+}
+
+/// A wrapper around a distribution that generates only distinct input sizes.
+///
+/// Duplicated sizes waste measurement time and make the plots noisier, without giving more
+/// information. This wrapper discards the duplicated sizes generated by the inner distribution
+/// and generates new ones, up to a maximum number of attempts. If the inner distribution can't
+/// generate enough distinct sizes (e.g. its range is too small), fewer sizes than requested are
+/// returned.
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let mut uniform = Uniform::new(1..=20).unwrap();
+/// uniform.set_gen_type(GenerationType::Random);
+/// let unique = Unique::new(uniform);
+///
+/// let mut sizes = unique.generate(10);
+/// sizes.sort();
+/// sizes.dedup();
+/// assert_eq!(sizes.len(), 10);
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "UniqueFields<D>")]
+pub struct Unique<D: Distribution> {
+    distribution: D,
+    max_attempts: usize,
+}
+
+impl<D: Distribution> Unique<D> {
+    /// Creates a new wrapper generating distinct sizes with the given distribution, with at
+    /// most 100 attempts.
+    ///
+    /// # Arguments
+    ///
+    /// * `distribution` - The inner distribution.
+    pub fn new(distribution: D) -> Self {
+        Unique {
+            distribution,
+            max_attempts: 100,
+        }
+    }
+
+    /// Sets the maximum number of times the inner distribution is asked for new sizes.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_attempts` - The maximum number of attempts, it must be greater than zero.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::InvalidParameter`] if `max_attempts` is zero.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Result<Self, DistributionError> {
+        if max_attempts == 0 {
+            return Err(DistributionError::InvalidParameter {
+                name: "max_attempts",
+                requirement: "greater than 0",
+            });
+        }
+        self.max_attempts = max_attempts;
+        Ok(self)
+    }
+}
+
+impl<D: Distribution> Debug for Unique<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unique {:?}", self.distribution)
+    }
+}
+
+impl<D: Distribution> Distribution for Unique<D> {
+    fn generate(&self, n: usize) -> Vec<usize> {
+        self.generate_with(&mut thread_rng(), n)
+    }
+
+    fn generate_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        assert!(n > 0, "The number of input sizes must be greater than zero");
+        let mut seen = HashSet::with_capacity(n);
+        let mut sizes = Vec::with_capacity(n);
+        for _ in 0..self.max_attempts {
+            for size in self.distribution.generate_with(rng, n - sizes.len()) {
+                if seen.insert(size) {
+                    sizes.push(size);
+                }
+            }
+            if sizes.len() == n {
+                break;
+            }
+        }
+        sizes
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        self.distribution.range()
+    }
+
+    fn info(&self) -> DistributionInfo {
+        let mut info = self.distribution.info();
+        info.name = format!("Unique {}", info.name);
+        info.params
+            .insert("max_attempts".to_owned(), self.max_attempts as f64);
+        info
+    }
+}
+
+/// A wrapper around a distribution that clamps the generated input sizes to a range.
+///
+/// The sizes generated by the inner distribution that fall outside the range are replaced by the
+/// nearest bound of the range.
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let clamped = Clamped::new(Uniform::new(0..=100).unwrap(), 20..=80).unwrap();
+/// assert_eq!(clamped.generate(5), vec![20, 25, 50, 75, 80]);
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "ClampedFields<D>")]
+pub struct Clamped<D: Distribution> {
+    distribution: D,
+    range: RangeInclusive<usize>,
+}
+
+impl<D: Distribution> Clamped<D> {
+    /// Creates a new wrapper clamping the sizes generated by the given distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `distribution` - The inner distribution.
+    /// * `range` - The range the sizes are clamped to, it must not be empty.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if the range is empty.
+    pub fn new(distribution: D, range: RangeInclusive<usize>) -> Result<Self, DistributionError> {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        Ok(Clamped {
+            distribution,
+            range,
+        })
+    }
+
+    /// Clamps the given sizes to the range.
+    fn clamp(&self, sizes: Vec<usize>) -> Vec<usize> {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        sizes
+            .into_iter()
+            .map(|size| size.clamp(start, end))
+            .collect()
+    }
+}
+
+impl<D: Distribution> Debug for Clamped<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Clamped to {:?} {:?}", self.range, self.distribution)
+    }
+}
+
+impl<D: Distribution> Distribution for Clamped<D> {
+    fn generate(&self, n: usize) -> Vec<usize> {
+        self.clamp(self.distribution.generate(n))
+    }
+
+    fn generate_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        self.clamp(self.distribution.generate_with(rng, n))
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        match self.distribution.range() {
+            Some(inner) => {
+                Some((*inner.start()).clamp(start, end)..=(*inner.end()).clamp(start, end))
+            }
+            None => Some(self.range.clone()),
+        }
+    }
+
+    fn info(&self) -> DistributionInfo {
+        let mut info = self.distribution.info();
+        info.name = format!("Clamped {}", info.name);
+        info.range = self.range();
+        info
+    }
+}
+
+/// A wrapper around a distribution that multiplies the generated input sizes by a factor.
+///
+/// The scaled sizes are rounded to the nearest integer. Together with [`Shifted`], it can be used
+/// to move the shape of a distribution to a different range, e.g. a [`Normal`] defined on
+/// `0..=100` can be moved to `1000..=2000`:
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let normal = Normal::new(0..=100, 50.0, 10.0).unwrap();
+/// let moved = Shifted::new(Scaled::new(normal, 10.0).unwrap(), 1000);
+/// assert_eq!(moved.range(), Some(1000..=2000));
+/// assert!(moved.generate(10).iter().all(|size| (1000..=2000).contains(size)));
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "ScaledFields<D>")]
+pub struct Scaled<D: Distribution> {
+    distribution: D,
+    factor: f64,
+}
+
+impl<D: Distribution> Scaled<D> {
+    /// Creates a new wrapper scaling the sizes generated by the given distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `distribution` - The inner distribution.
+    /// * `factor` - The factor the sizes are multiplied by, it must not be negative.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::InvalidParameter`] if `factor` is negative or NaN.
+    pub fn new(distribution: D, factor: f64) -> Result<Self, DistributionError> {
+        if factor.is_nan() || factor < 0.0 {
+            return Err(DistributionError::InvalidParameter {
+                name: "factor",
+                requirement: "not negative",
+            });
+        }
+        Ok(Scaled {
+            distribution,
+            factor,
+        })
+    }
+
+    /// Scales the given size.
+    fn scale(&self, size: usize) -> usize {
+        (size as f64 * self.factor).round() as usize
+    }
+}
+
+impl<D: Distribution> Debug for Scaled<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Scaled by {} {:?}", self.factor, self.distribution)
+    }
+}
+
+impl<D: Distribution> Distribution for Scaled<D> {
+    fn generate(&self, n: usize) -> Vec<usize> {
+        let sizes = self.distribution.generate(n);
+        sizes.into_iter().map(|size| self.scale(size)).collect()
+    }
+
+    fn generate_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        let sizes = self.distribution.generate_with(rng, n);
+        sizes.into_iter().map(|size| self.scale(size)).collect()
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        let range = self.distribution.range()?;
+        Some(self.scale(*range.start())..=self.scale(*range.end()))
+    }
+
+    fn info(&self) -> DistributionInfo {
+        let mut info = self.distribution.info();
+        info.name = format!("Scaled {}", info.name);
+        info.params.insert("factor".to_owned(), self.factor);
+        info.range = self.range();
+        info
+    }
+}
+
+/// A wrapper around a distribution that adds an offset to the generated input sizes.
+///
+/// The offset can be negative, in which case the sizes that would become negative are set to 0.
+/// See [`Scaled`] for an example.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Shifted<D: Distribution> {
+    distribution: D,
+    offset: isize,
+}
+
+impl<D: Distribution> Shifted<D> {
+    /// Creates a new wrapper shifting the sizes generated by the given distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `distribution` - The inner distribution.
+    /// * `offset` - The offset added to the sizes.
+    pub fn new(distribution: D, offset: isize) -> Self {
+        Shifted {
+            distribution,
+            offset,
+        }
+    }
+
+    /// Shifts the given size.
+    fn shift(&self, size: usize) -> usize {
+        size.saturating_add_signed(self.offset)
+    }
+}
+
+impl<D: Distribution> Debug for Shifted<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Shifted by {} {:?}", self.offset, self.distribution)
+    }
+}
+
+impl<D: Distribution> Distribution for Shifted<D> {
+    fn generate(&self, n: usize) -> Vec<usize> {
+        let sizes = self.distribution.generate(n);
+        sizes.into_iter().map(|size| self.shift(size)).collect()
+    }
+
+    fn generate_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        let sizes = self.distribution.generate_with(rng, n);
+        sizes.into_iter().map(|size| self.shift(size)).collect()
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        let range = self.distribution.range()?;
+        Some(self.shift(*range.start())..=self.shift(*range.end()))
+    }
+
+    fn info(&self) -> DistributionInfo {
+        let mut info = self.distribution.info();
+        info.name = format!("Shifted {}", info.name);
+        info.params.insert("offset".to_owned(), self.offset as f64);
+        info.range = self.range();
+        info
+    }
+}
+
+/// A wrapper around a distribution that drops the input sizes smaller than a minimum.
+///
+/// It is meant to skip the sizes too small to be measured reliably, see
+/// [`min_measurable_size`](crate::measurements::min_measurable_size). Fewer sizes than requested
+/// are returned if the inner distribution generates some sizes below the minimum.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MinSize<D: Distribution> {
+    distribution: D,
+    min_size: usize,
+}
+
+impl<D: Distribution> MinSize<D> {
+    /// Creates a new wrapper dropping the sizes generated by the given distribution smaller than
+    /// `min_size`.
+    ///
+    /// # Arguments
+    ///
+    /// * `distribution` - The inner distribution.
+    /// * `min_size` - The smallest size kept.
+    pub fn new(distribution: D, min_size: usize) -> Self {
+        MinSize {
+            distribution,
+            min_size,
+        }
+    }
+}
+
+impl<D: Distribution> Debug for MinSize<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "At least {} {:?}", self.min_size, self.distribution)
+    }
+}
+
+impl<D: Distribution> Distribution for MinSize<D> {
+    fn generate(&self, n: usize) -> Vec<usize> {
+        let mut sizes = self.distribution.generate(n);
+        sizes.retain(|&size| size >= self.min_size);
+        sizes
+    }
+
+    fn generate_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        let mut sizes = self.distribution.generate_with(rng, n);
+        sizes.retain(|&size| size >= self.min_size);
+        sizes
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        let range = self.distribution.range()?;
+        Some((*range.start()).max(self.min_size)..=*range.end())
+    }
+
+    fn info(&self) -> DistributionInfo {
+        let mut info = self.distribution.info();
+        info.name = format!("MinSize {}", info.name);
+        info.params
+            .insert("min_size".to_owned(), self.min_size as f64);
+        info.range = self.range();
+        info
+    }
+}
+
+/// A wrapper around a distribution that rescales its values linearly from its range of sizes to
+/// a range of real values.
+///
+/// The distributions generate their [`ContinuousDistribution`] values in their range of sizes,
+/// which has integer bounds. This wrapper moves the shape of the distribution to any range, e.g.
+/// to sweep a density between 0 and 0.5:
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let density = Rescaled::new(Uniform::new(0..=100).unwrap(), 0.0..=0.5).unwrap();
+/// assert_eq!(density.generate_values(3), vec![0.0, 0.25, 0.5]);
+///
+/// let normal = Normal::new(0..=100, 50.0, 10.0).unwrap();
+/// let tolerance = Rescaled::new(normal, 1e-3..=1e-2).unwrap();
+/// assert!(tolerance
+///     .generate_values(10)
+///     .iter()
+///     .all(|value| (1e-3..=1e-2).contains(value)));
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "RescaledFields<D>")]
+pub struct Rescaled<D: ProbabilityDistribution + Debug> {
+    distribution: D,
+    range: RangeInclusive<f64>,
+}
+
+impl<D: ProbabilityDistribution + Debug> Rescaled<D> {
+    /// Creates a new wrapper rescaling the values generated by the given distribution to the
+    /// given range.
+    ///
+    /// # Arguments
+    ///
+    /// * `distribution` - The inner distribution, its range must be known.
+    /// * `range` - The range the values are rescaled to, it must be finite and not empty.
+    ///
+    /// # Errors
+    ///
+    /// * [`DistributionError::EmptyRange`] if `range` is empty.
+    /// * [`DistributionError::InvalidParameter`] if `range` is not finite, or if the range of the
+    ///   inner distribution is unknown.
+    /// * [`DistributionError::DegenerateRange`] if the range of the inner distribution contains a
+    ///   single size.
+    pub fn new(distribution: D, range: RangeInclusive<f64>) -> Result<Self, DistributionError> {
+        if range.is_empty() {
+            return Err(DistributionError::EmptyRange);
+        }
+        if !range.start().is_finite() || !range.end().is_finite() {
+            return Err(DistributionError::InvalidParameter {
+                name: "range",
+                requirement: "finite",
+            });
+        }
+        let inner = ProbabilityDistribution::range(&distribution).ok_or(
+            DistributionError::InvalidParameter {
+                name: "distribution",
+                requirement: "defined on a known range",
+            },
+        )?;
+        if inner.start() == inner.end() {
+            return Err(DistributionError::DegenerateRange);
+        }
+        Ok(Rescaled {
+            distribution,
+            range,
+        })
+    }
+
+    /// Rescales the given values of the inner distribution.
+    fn rescale(&self, values: Vec<f64>) -> Vec<f64> {
+        let inner = ProbabilityDistribution::range(&self.distribution)
+            .expect("The range of the inner distribution is checked by the constructor");
+        let (from, width) = (
+            *inner.start() as f64,
+            (*inner.end() - *inner.start()) as f64,
+        );
+        let (start, end) = (*self.range.start(), *self.range.end());
+        values
+            .into_iter()
+            .map(|value| (start + (value - from) / width * (end - start)).clamp(start, end))
+            .collect()
+    }
+}
+
+impl<D: ProbabilityDistribution + Debug> Debug for Rescaled<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Rescaled to {:?} {:?}", self.range, self.distribution)
+    }
+}
+
+impl<D: ProbabilityDistribution + Debug> ContinuousDistribution for Rescaled<D> {
+    fn generate_values(&self, n: usize) -> Vec<f64> {
+        self.rescale(self.distribution.generate_values(n))
+    }
+
+    fn generate_values_with<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<f64> {
+        self.rescale(self.distribution.generate_values_with(rng, n))
+    }
+}
+
+/// A distribution defined by a closure generating the input sizes.
+///
+/// It allows defining a one-off distribution inline, without a new type implementing [`Debug`]
+/// and [`Distribution`].
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// let squares = FnDistribution::new("Squares", |n| (1..=n).map(|i| i * i).collect());
+/// assert_eq!(squares.generate(4), vec![1, 4, 9, 16]);
+/// assert_eq!(squares.info().name, "Squares");
+/// ```
+pub struct FnDistribution<F: Fn(usize) -> Vec<usize>> {
+    name: String,
+    f: F,
+}
+
+impl<F: Fn(usize) -> Vec<usize>> FnDistribution<F> {
+    /// Creates a new distribution from a closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the distribution, used to describe it.
+    /// * `f` - The closure generating `n` input sizes, given `n`.
+    pub fn new(name: &str, f: F) -> Self {
+        FnDistribution {
+            name: name.to_owned(),
+            f,
+        }
+    }
+}
+
+impl<F: Fn(usize) -> Vec<usize>> Debug for FnDistribution<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<F: Fn(usize) -> Vec<usize>> Distribution for FnDistribution<F> {
+    fn generate(&self, n: usize) -> Vec<usize> {
+        assert!(n > 0, "The number of input sizes must be greater than zero");
+        (self.f)(n)
+    }
+
+    fn info(&self) -> DistributionInfo {
+        DistributionInfo {
+            name: self.name.clone(),
+            params: BTreeMap::new(),
+            range: None,
+        }
+    }
+}
+
+/// A probability distribution defined by a closure computing its inverse cumulative
+/// distribution function (see [`ProbabilityDistribution::inverse_cdf`]).
+///
+/// It allows defining a one-off distribution inline, without a new type implementing [`Debug`]
+/// and [`ProbabilityDistribution`].
+///
+/// ```
+/// use chrono_probe::input::distribution::*;
+///
+/// // Sizes between 0 and 1000, concentrated at the small end of the range
+/// let quadratic = FnProbability::new("Quadratic", |u| 1000.0 * u * u).with_range(0..=1000);
+/// assert_eq!(quadratic.generate(3), vec![0, 250, 1000]);
+/// assert_eq!(quadratic.info().name, "Quadratic");
+/// ```
+pub struct FnProbability<F: Fn(f64) -> f64> {
+    name: String,
+    f: F,
+    range: Option<RangeInclusive<usize>>,
+    gen_type: GenerationType,
+}
+
+impl<F: Fn(f64) -> f64> FnProbability<F> {
+    /// Creates a new probability distribution from a closure, with fixed intervals generation
+    /// type.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the distribution, used to describe it.
+    /// * `f` - The inverse cumulative distribution function, mapping a value in \[0,1] to a size.
+    pub fn new(name: &str, f: F) -> Self {
+        FnProbability {
+            name: name.to_owned(),
+            f,
+            range: None,
+            gen_type: GenerationType::FixedIntervals,
+        }
+    }
+
+    /// Sets the range of the values returned by the closure, needed to stratify the input sizes
+    /// (see [`InputBuilder::with_buckets`](crate::input::InputBuilder::with_buckets)).
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the values returned by the closure.
+    pub fn with_range(mut self, range: RangeInclusive<usize>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Sets the generation type of the distribution.
+    /// The generation type can be either fixed intervals, random, stratified or jittered.
+    ///
+    /// # Arguments
+    ///
+    /// * `gen_type` - The new generation type of the distribution.
+    pub fn set_gen_type(&mut self, gen_type: GenerationType) {
+        self.gen_type = gen_type;
+    }
+}
+
+impl<F: Fn(f64) -> f64> Debug for FnProbability<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, generation type: {:?}", self.name, self.gen_type)
+    }
+}
+
+impl<F: Fn(f64) -> f64> ProbabilityDistribution for FnProbability<F> {
+    fn inverse_cdf(&self, u: f64) -> f64 {
+        (self.f)(u)
+    }
+
+    fn get_gen_type(&self) -> &GenerationType {
+        &self.gen_type
+    }
+
+    fn range(&self) -> Option<RangeInclusive<usize>> {
+        self.range.clone()
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+// ===================
+// = DESERIALIZATION =
+// ===================
+
+// The distributions are deserialized through the fields below and then created with their
+// constructors, so that a deserialized distribution is as valid as one created in the code.
+
+#[derive(Deserialize)]
+struct UniformFields {
+    range: RangeInclusive<usize>,
+    gen_type: GenerationType,
+}
+
+impl TryFrom<UniformFields> for Uniform {
+    type Error = DistributionError;
+
+    fn try_from(fields: UniformFields) -> Result<Self, Self::Error> {
+        let mut uniform = Uniform::new(fields.range)?;
+        uniform.set_gen_type(fields.gen_type);
+        Ok(uniform)
+    }
+}
+
+#[derive(Deserialize)]
+struct ExponentialFields {
+    range: RangeInclusive<usize>,
+    lambda: f64,
+    gen_type: GenerationType,
+}
+
+impl TryFrom<ExponentialFields> for Exponential {
+    type Error = DistributionError;
+
+    fn try_from(fields: ExponentialFields) -> Result<Self, Self::Error> {
+        let mut exponential = Exponential::new(fields.range)?;
+        if fields.lambda.is_nan() || fields.lambda <= 0.0 {
+            return Err(DistributionError::InvalidParameter {
+                name: "lambda",
+                requirement: "greater than 0",
+            });
+        }
+        exponential.set_lambda(fields.lambda);
+        exponential.set_gen_type(fields.gen_type);
+        Ok(exponential)
+    }
+}
+
+#[derive(Deserialize)]
+struct LogUniformFields {
+    range: RangeInclusive<usize>,
+    gen_type: GenerationType,
+}
+
+impl TryFrom<LogUniformFields> for LogUniform {
+    type Error = DistributionError;
+
+    fn try_from(fields: LogUniformFields) -> Result<Self, Self::Error> {
+        let mut log_uniform = LogUniform::new(fields.range)?;
+        log_uniform.set_gen_type(fields.gen_type);
+        Ok(log_uniform)
+    }
+}
+
+#[derive(Deserialize)]
+struct NormalFields {
+    range: RangeInclusive<usize>,
+    mean: f64,
+    stddev: f64,
+    gen_type: GenerationType,
+}
+
+impl TryFrom<NormalFields> for Normal {
+    type Error = DistributionError;
+
+    fn try_from(fields: NormalFields) -> Result<Self, Self::Error> {
+        let mut normal = Normal::new(fields.range, fields.mean, fields.stddev)?;
+        normal.set_gen_type(fields.gen_type);
+        Ok(normal)
+    }
+}
+
+#[derive(Deserialize)]
+struct PoissonFields {
+    range: RangeInclusive<usize>,
+    lambda: f64,
+    gen_type: GenerationType,
+}
+
+impl TryFrom<PoissonFields> for Poisson {
+    type Error = DistributionError;
+
+    fn try_from(fields: PoissonFields) -> Result<Self, Self::Error> {
+        let mut poisson = Poisson::new(fields.range, fields.lambda)?;
+        poisson.set_gen_type(fields.gen_type);
+        Ok(poisson)
+    }
+}
+
+#[derive(Deserialize)]
+struct ParetoFields {
+    range: RangeInclusive<usize>,
+    alpha: f64,
+    gen_type: GenerationType,
+}
+
+impl TryFrom<ParetoFields> for Pareto {
+    type Error = DistributionError;
+
+    fn try_from(fields: ParetoFields) -> Result<Self, Self::Error> {
+        let mut pareto = Pareto::new(fields.range, fields.alpha)?;
+        pareto.set_gen_type(fields.gen_type);
+        Ok(pareto)
+    }
+}
+
+#[derive(Deserialize)]
+struct DoublingFields {
+    start: usize,
+    max: usize,
+}
+
+impl TryFrom<DoublingFields> for Doubling {
+    type Error = DistributionError;
+
+    fn try_from(fields: DoublingFields) -> Result<Self, Self::Error> {
+        Doubling::new(fields.start, fields.max)
+    }
+}
+
+#[derive(Deserialize)]
+struct GridFields {
+    range: RangeInclusive<usize>,
+    step: usize,
+}
+
+impl TryFrom<GridFields> for Grid {
+    type Error = DistributionError;
+
+    fn try_from(fields: GridFields) -> Result<Self, Self::Error> {
+        Grid::new(fields.range, fields.step)
+    }
+}
+
+#[derive(Deserialize)]
+struct GeometricFields {
+    base: usize,
+    range: RangeInclusive<usize>,
+}
+
+impl TryFrom<GeometricFields> for Geometric {
+    type Error = DistributionError;
+
+    fn try_from(fields: GeometricFields) -> Result<Self, Self::Error> {
+        Geometric::new(fields.base, fields.range)
+    }
+}
+
+#[derive(Deserialize)]
+struct ExplicitFields {
+    sizes: Vec<usize>,
+}
+
+impl TryFrom<ExplicitFields> for Explicit {
+    type Error = DistributionError;
+
+    fn try_from(fields: ExplicitFields) -> Result<Self, Self::Error> {
+        Explicit::new(fields.sizes)
+    }
+}
+
+#[derive(Deserialize)]
+struct UniqueFields<D> {
+    distribution: D,
+    max_attempts: usize,
+}
+
+impl<D: Distribution> TryFrom<UniqueFields<D>> for Unique<D> {
+    type Error = DistributionError;
+
+    fn try_from(fields: UniqueFields<D>) -> Result<Self, Self::Error> {
+        Unique::new(fields.distribution).with_max_attempts(fields.max_attempts)
+    }
+}
+
+#[derive(Deserialize)]
+struct ClampedFields<D> {
+    distribution: D,
+    range: RangeInclusive<usize>,
+}
+
+impl<D: Distribution> TryFrom<ClampedFields<D>> for Clamped<D> {
+    type Error = DistributionError;
+
+    fn try_from(fields: ClampedFields<D>) -> Result<Self, Self::Error> {
+        Clamped::new(fields.distribution, fields.range)
+    }
+}
+
+#[derive(Deserialize)]
+struct ScaledFields<D> {
+    distribution: D,
+    factor: f64,
+}
+
+impl<D: Distribution> TryFrom<ScaledFields<D>> for Scaled<D> {
+    type Error = DistributionError;
+
+    fn try_from(fields: ScaledFields<D>) -> Result<Self, Self::Error> {
+        Scaled::new(fields.distribution, fields.factor)
+    }
+}
+
+#[derive(Deserialize)]
+struct RescaledFields<D> {
+    distribution: D,
+    range: RangeInclusive<f64>,
+}
+
+impl<D: ProbabilityDistribution + Debug> TryFrom<RescaledFields<D>> for Rescaled<D> {
+    type Error = DistributionError;
+
+    fn try_from(fields: RescaledFields<D>) -> Result<Self, Self::Error> {
+        Rescaled::new(fields.distribution, fields.range)
+    }
 }
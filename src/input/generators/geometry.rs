@@ -0,0 +1,184 @@
+//! Generators for sets of points in the plane.
+//!
+//! The [`PointSet`] input type can be used to measure the time complexity of computational
+//! geometry algorithms, like convex hull or closest pair algorithms. The size of a [`PointSet`]
+//! is the number of points it contains.
+//!
+//! The way the points are placed is chosen with the [`PointSetGenerator`] builder:
+//!
+//! * [`PointSetGenerator::UniformSquare`]: points uniformly distributed in a square.
+//! * [`PointSetGenerator::Clustered`]: points grouped around a few random centers.
+//! * [`PointSetGenerator::Circle`]: points on a circle, i.e. all points are in convex position.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::Input;
+//! use chrono_probe::input::generators::geometry::{PointSet, PointSetGenerator};
+//!
+//! let points = PointSet::generate_input(100, &PointSetGenerator::Circle { radius: 1.0 });
+//! assert_eq!(points.get_size(), 100);
+//! ```
+
+use std::f64::consts::PI;
+use std::ops::{Deref, DerefMut};
+
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::input::scalable::ScalableInput;
+use crate::input::Input;
+
+/// A point in the plane.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Point2D {
+    /// The x coordinate of the point
+    pub x: f64,
+    /// The y coordinate of the point
+    pub y: f64,
+}
+
+/// A set of points in the plane.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PointSet(pub Vec<Point2D>);
+
+/// The builder of [`PointSet`], it selects how the points are placed in the plane.
+#[derive(Serialize, Clone, Debug)]
+pub enum PointSetGenerator {
+    /// Points uniformly distributed in the square \[0, side) × \[0, side).
+    UniformSquare {
+        /// The length of the side of the square
+        side: f64,
+    },
+    /// Points normally distributed around `clusters` centers, chosen uniformly at random in the
+    /// unit square.
+    Clustered {
+        /// The number of clusters
+        clusters: usize,
+        /// The standard deviation of the points around the center of their cluster
+        spread: f64,
+    },
+    /// Points uniformly distributed on a circle centered in the origin.
+    ///
+    /// All the points are in convex position, which is the worst case for output-sensitive
+    /// convex hull algorithms.
+    Circle {
+        /// The radius of the circle
+        radius: f64,
+    },
+}
+
+impl Default for PointSetGenerator {
+    fn default() -> Self {
+        PointSetGenerator::UniformSquare { side: 1.0 }
+    }
+}
+
+impl Deref for PointSet {
+    type Target = Vec<Point2D>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for PointSet {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Input for PointSet {
+    type Builder<'a> = PointSetGenerator;
+
+    fn get_size(&self) -> usize {
+        self.len()
+    }
+
+    fn get_size_bytes(&self) -> Option<usize> {
+        Some(self.len() * std::mem::size_of::<Point2D>())
+    }
+
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+        generate(&mut thread_rng(), size, builder)
+    }
+
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self {
+        generate(rng, size, builder)
+    }
+}
+
+/// The logical instance of a [`PointSet`] is the seed used to generate its points: rendering
+/// the same instance at a bigger size adds new points to the ones of the smaller sizes.
+impl ScalableInput for PointSet {
+    type Instance = u64;
+
+    fn new_instance(_builder: &Self::Builder<'_>) -> Self::Instance {
+        thread_rng().gen()
+    }
+
+    fn new_instance_with<R: Rng>(_builder: &Self::Builder<'_>, rng: &mut R) -> Self::Instance {
+        rng.gen()
+    }
+
+    fn render(instance: &Self::Instance, size: usize, builder: &Self::Builder<'_>) -> Self {
+        generate(&mut StdRng::seed_from_u64(*instance), size, builder)
+    }
+}
+
+/// Generates a [`PointSet`] of the given size using the given random number generator.
+fn generate<R: Rng>(rng: &mut R, size: usize, builder: &PointSetGenerator) -> PointSet {
+    let points = match builder {
+        PointSetGenerator::UniformSquare { side } => uniform_square(rng, size, *side),
+        PointSetGenerator::Clustered { clusters, spread } => {
+            clustered(rng, size, *clusters, *spread)
+        }
+        PointSetGenerator::Circle { radius } => circle(rng, size, *radius),
+    };
+    PointSet(points)
+}
+
+/// Generates `n` points uniformly distributed in a square of the given side.
+fn uniform_square<R: Rng>(rng: &mut R, n: usize, side: f64) -> Vec<Point2D> {
+    (0..n)
+        .map(|_| Point2D {
+            x: rng.gen::<f64>() * side,
+            y: rng.gen::<f64>() * side,
+        })
+        .collect()
+}
+
+/// Generates `n` points normally distributed around `clusters` random centers.
+fn clustered<R: Rng>(rng: &mut R, n: usize, clusters: usize, spread: f64) -> Vec<Point2D> {
+    assert!(
+        clusters > 0,
+        "The number of clusters must be greater than 0"
+    );
+    let centers = uniform_square(rng, clusters, 1.0);
+    (0..n)
+        .map(|_| {
+            let center = centers[rng.gen_range(0..clusters)];
+            // Box-Muller transform: two independent standard normal samples
+            let r = (-2.0 * (1.0 - rng.gen::<f64>()).ln()).sqrt();
+            let theta = 2.0 * PI * rng.gen::<f64>();
+            Point2D {
+                x: center.x + spread * r * theta.cos(),
+                y: center.y + spread * r * theta.sin(),
+            }
+        })
+        .collect()
+}
+
+/// Generates `n` points uniformly distributed on a circle of the given radius.
+fn circle<R: Rng>(rng: &mut R, n: usize, radius: f64) -> Vec<Point2D> {
+    (0..n)
+        .map(|_| {
+            let theta = 2.0 * PI * rng.gen::<f64>();
+            Point2D {
+                x: radius * theta.cos(),
+                y: radius * theta.sin(),
+            }
+        })
+        .collect()
+}
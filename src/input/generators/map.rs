@@ -0,0 +1,281 @@
+//! Generators for key/value workloads on maps.
+//!
+//! The [`MapWorkload`] input type can be used to measure the time complexity of the operations of
+//! map data structures, like [`HashMap`] and [`BTreeMap`]. A workload is made of a map, filled
+//! when the input is generated (so that filling it is not measured), and a sequence of operations
+//! to be executed on it. The size of a [`MapWorkload`] is the number of operations.
+//!
+//! The operations are chosen with the [`MapWorkloadGenerator`] builder:
+//!
+//! * [`MapWorkloadGenerator::Insertions`]: n random insertions in an empty map.
+//! * [`MapWorkloadGenerator::Lookups`]: n lookups in a map with n entries, with a configurable
+//!   hit rate.
+//! * [`MapWorkloadGenerator::Mixed`]: n insertions, lookups and removals, in random order.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::map::{MapWorkload, MapWorkloadGenerator};
+//! use chrono_probe::input::InputBuilder;
+//! use chrono_probe::measurements::measure_mut;
+//!
+//! let builder: InputBuilder<MapWorkload<BTreeMap<u64, u64>>, _> = InputBuilder::new(
+//!     Uniform::new(10..=1000).unwrap(),
+//!     MapWorkloadGenerator::Mixed { insert: 0.5, remove: 0.2, hit_rate: 0.9 },
+//! );
+//! let inputs = builder.build(10);
+//!
+//! fn run(workload: &mut MapWorkload<BTreeMap<u64, u64>>) {
+//!     workload.run();
+//! }
+//!
+//! let results = measure_mut(&inputs, &[(run, "btree map")], 0.01);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::input::Input;
+
+/// A map from `u64` keys to `u64` values, on which the operations of a [`MapWorkload`] can be
+/// executed.
+///
+/// It is implemented for [`HashMap`] and [`BTreeMap`], and can be implemented for other map
+/// data structures to compare them.
+pub trait KeyValueMap: Default + Clone {
+    /// Inserts the given key/value pair, returning the previous value of the key, if any.
+    fn insert(&mut self, key: u64, value: u64) -> Option<u64>;
+    /// Returns the value of the given key, if any.
+    fn get(&self, key: &u64) -> Option<&u64>;
+    /// Removes the given key, returning its value, if any.
+    fn remove(&mut self, key: &u64) -> Option<u64>;
+    /// Returns the number of entries of the map.
+    fn len(&self) -> usize;
+    /// Returns true if the map contains no entries.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl KeyValueMap for HashMap<u64, u64> {
+    fn insert(&mut self, key: u64, value: u64) -> Option<u64> {
+        HashMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &u64) -> Option<&u64> {
+        HashMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &u64) -> Option<u64> {
+        HashMap::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+}
+
+impl KeyValueMap for BTreeMap<u64, u64> {
+    fn insert(&mut self, key: u64, value: u64) -> Option<u64> {
+        BTreeMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &u64) -> Option<&u64> {
+        BTreeMap::get(self, key)
+    }
+
+    fn remove(&mut self, key: &u64) -> Option<u64> {
+        BTreeMap::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+}
+
+/// An operation on a map.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapOp {
+    /// Inserts the key/value pair.
+    Insert(u64, u64),
+    /// Looks up the key.
+    Get(u64),
+    /// Removes the key.
+    Remove(u64),
+}
+
+/// A map together with a sequence of operations to be executed on it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MapWorkload<M: KeyValueMap = HashMap<u64, u64>> {
+    /// The map on which the operations are executed, filled when the workload is generated
+    pub map: M,
+    /// The operations to be executed
+    pub ops: Vec<MapOp>,
+}
+
+/// The builder of [`MapWorkload`], it selects the kind of operations of the workload.
+///
+/// The keys present in the map are always even and the keys of the lookups that miss are always
+/// odd, so the hit rates are exact.
+#[derive(Serialize, Clone, Copy, Debug, Default)]
+pub enum MapWorkloadGenerator {
+    /// n insertions of random keys in an empty map.
+    #[default]
+    Insertions,
+    /// n lookups in a map with n entries.
+    Lookups {
+        /// The probability that a looked up key is present in the map, between 0 and 1
+        hit_rate: f64,
+    },
+    /// n operations in an initially empty map: insertions with probability `insert`, removals
+    /// with probability `remove` and lookups otherwise.
+    Mixed {
+        /// The probability of an insertion, between 0 and 1
+        insert: f64,
+        /// The probability of a removal, between 0 and 1
+        remove: f64,
+        /// The probability that a looked up or removed key is present in the map, between 0 and 1
+        hit_rate: f64,
+    },
+}
+
+impl<M: KeyValueMap> MapWorkload<M> {
+    /// Executes the operations of the workload on its map, returning the number of lookups and
+    /// removals that found their key.
+    pub fn run(&mut self) -> usize {
+        let mut hits = 0;
+        for op in &self.ops {
+            let hit = match op {
+                MapOp::Insert(key, value) => {
+                    self.map.insert(*key, *value);
+                    false
+                }
+                MapOp::Get(key) => self.map.get(key).is_some(),
+                MapOp::Remove(key) => self.map.remove(key).is_some(),
+            };
+            hits += usize::from(hit);
+        }
+        hits
+    }
+
+    /// Executes the lookups of the workload on its map, without modifying it, returning the
+    /// number of lookups that found their key. The other operations are ignored.
+    pub fn run_lookups(&self) -> usize {
+        self.ops
+            .iter()
+            .filter(|op| matches!(op, MapOp::Get(key) if self.map.get(key).is_some()))
+            .count()
+    }
+}
+
+impl<M: KeyValueMap> Input for MapWorkload<M> {
+    type Builder<'a> = MapWorkloadGenerator;
+
+    fn get_size(&self) -> usize {
+        self.ops.len()
+    }
+
+    fn get_size_bytes(&self) -> Option<usize> {
+        Some(
+            self.ops.len() * std::mem::size_of::<MapOp>()
+                + self.map.len() * 2 * std::mem::size_of::<u64>(),
+        )
+    }
+
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+        generate(&mut thread_rng(), size, builder)
+    }
+
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self {
+        generate(rng, size, builder)
+    }
+}
+
+/// Generates a [`MapWorkload`] with the given number of operations using the given random number
+/// generator.
+fn generate<M: KeyValueMap, R: Rng>(
+    rng: &mut R,
+    size: usize,
+    builder: &MapWorkloadGenerator,
+) -> MapWorkload<M> {
+    let mut map = M::default();
+    // The keys present in the map, to choose the keys that hit
+    let mut keys = Vec::new();
+    let ops = match *builder {
+        MapWorkloadGenerator::Insertions => (0..size)
+            .map(|_| MapOp::Insert(present_key(rng), rng.gen()))
+            .collect(),
+        MapWorkloadGenerator::Lookups { hit_rate } => {
+            check_probability(hit_rate, "hit rate");
+            for _ in 0..size {
+                let key = present_key(rng);
+                map.insert(key, rng.gen());
+                keys.push(key);
+            }
+            (0..size)
+                .map(|_| MapOp::Get(lookup_key(rng, &keys, hit_rate)))
+                .collect()
+        }
+        MapWorkloadGenerator::Mixed {
+            insert,
+            remove,
+            hit_rate,
+        } => {
+            check_probability(insert, "insertion probability");
+            check_probability(remove, "removal probability");
+            check_probability(hit_rate, "hit rate");
+            assert!(
+                insert + remove <= 1.0,
+                "The probabilities of insertions and removals must sum to at most 1"
+            );
+            (0..size)
+                .map(|_| {
+                    let p: f64 = rng.gen();
+                    if p < insert {
+                        let key = present_key(rng);
+                        keys.push(key);
+                        MapOp::Insert(key, rng.gen())
+                    } else if p < insert + remove {
+                        let hit = !keys.is_empty() && rng.gen_bool(hit_rate);
+                        if hit {
+                            MapOp::Remove(keys.swap_remove(rng.gen_range(0..keys.len())))
+                        } else {
+                            MapOp::Remove(present_key(rng) | 1)
+                        }
+                    } else {
+                        MapOp::Get(lookup_key(rng, &keys, hit_rate))
+                    }
+                })
+                .collect()
+        }
+    };
+    MapWorkload { map, ops }
+}
+
+/// Returns a random even key, i.e. a key that can be present in the map.
+fn present_key<R: Rng>(rng: &mut R) -> u64 {
+    rng.gen::<u64>() << 1
+}
+
+/// Returns a key present in `keys` with probability `hit_rate`, an odd key (i.e. a key never
+/// present in the map) otherwise.
+fn lookup_key<R: Rng>(rng: &mut R, keys: &[u64], hit_rate: f64) -> u64 {
+    if !keys.is_empty() && rng.gen_bool(hit_rate) {
+        keys[rng.gen_range(0..keys.len())]
+    } else {
+        present_key(rng) | 1
+    }
+}
+
+/// Checks that the given probability is between 0 and 1.
+fn check_probability(p: f64, name: &str) {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "The {name} must be between 0 and 1"
+    );
+}
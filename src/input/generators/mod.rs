@@ -0,0 +1,34 @@
+//! Ready-made input types for common families of algorithms.
+//!
+//! Each submodule provides one or more types implementing the [`Input`](crate::input::Input)
+//! trait, together with a builder that selects how the inputs are generated. They can be used
+//! directly with an [`InputBuilder`](crate::input::InputBuilder), without having to write a
+//! custom generator:
+//!
+//! ```
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::geometry::{PointSet, PointSetGenerator};
+//! use chrono_probe::input::InputBuilder;
+//!
+//! let builder: InputBuilder<PointSet, _> =
+//!     InputBuilder::new(Uniform::new(10..=1000).unwrap(), PointSetGenerator::Circle { radius: 1.0 });
+//! let inputs = builder.build(10);
+//! ```
+//!
+//! Available generators:
+//!
+//! * [`geometry`]: sets of points in the plane, for computational-geometry algorithms.
+//! * [`map`]: key/value workloads, for the operations of map data structures.
+//! * [`permutation`]: permutations with a controlled disorder, for adaptive sorting algorithms.
+//! * [`sat`]: random k-SAT formulas, for SAT solvers and backtracking algorithms.
+//! * [`string`]: random strings over an alphabet, for string algorithms.
+//! * [`text`]: windows of a natural-text corpus, for string algorithms.
+//! * [`vec`]: vectors of integers, for sorting and searching algorithms.
+
+pub mod geometry;
+pub mod map;
+pub mod permutation;
+pub mod sat;
+pub mod string;
+pub mod text;
+pub mod vec;
@@ -0,0 +1,230 @@
+//! Generators for permutations with a controlled amount of disorder.
+//!
+//! Adaptive sorting algorithms (like timsort or natural merge sort) run faster on inputs that are
+//! already partially sorted. The [`Permutation`] input type allows measuring how they scale as a
+//! function of the disorder of the input, and not only of its size. The size of a [`Permutation`]
+//! is the number of elements it contains.
+//!
+//! The disorder is chosen with the [`Presortedness`] builder:
+//!
+//! * [`Presortedness::Inversions`]: the number of inversions, i.e. of pairs of elements in the
+//!   wrong order, as a fraction of the maximum possible.
+//! * [`Presortedness::Runs`]: the number of ascending runs the permutation is made of.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::Input;
+//! use chrono_probe::input::generators::permutation::{Permutation, Presortedness};
+//!
+//! // 10% of the 4950 possible inversions
+//! let permutation = Permutation::generate_input(100, &Presortedness::Inversions { fraction: 0.1 });
+//! assert_eq!(permutation.inversions(), 495);
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::input::Input;
+
+/// A permutation of the numbers from 0 to n - 1.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Permutation(pub Vec<u32>);
+
+/// The builder of [`Permutation`], it selects the disorder of the permutation.
+#[derive(Serialize, Clone, Copy, Debug)]
+pub enum Presortedness {
+    /// A random permutation with the given number of inversions, expressed as a fraction of the
+    /// maximum number of inversions n(n - 1)/2: 0 gives the sorted permutation, 1 the reversed
+    /// one.
+    Inversions {
+        /// The fraction of inversions, between 0 and 1
+        fraction: f64,
+    },
+    /// The concatenation of the given number of ascending runs of random elements. Adjacent runs
+    /// may happen to join, so the permutation has at most `runs` ascending runs.
+    Runs {
+        /// The number of runs, it must be greater than 0
+        runs: usize,
+    },
+}
+
+impl Permutation {
+    /// Returns the number of inversions of the permutation, i.e. the number of pairs of elements
+    /// in the wrong order.
+    pub fn inversions(&self) -> usize {
+        // Count the greater elements on the left of each element with a Fenwick tree
+        let mut tree = Fenwick::new(self.len());
+        let mut inversions = 0;
+        for (i, &value) in self.iter().enumerate() {
+            inversions += i - tree.prefix_sum(value as usize);
+            tree.add(value as usize, 1);
+        }
+        inversions
+    }
+}
+
+impl Deref for Permutation {
+    type Target = Vec<u32>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Permutation {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Input for Permutation {
+    type Builder<'a> = Presortedness;
+
+    fn get_size(&self) -> usize {
+        self.len()
+    }
+
+    fn get_size_bytes(&self) -> Option<usize> {
+        Some(self.len() * std::mem::size_of::<u32>())
+    }
+
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+        generate(&mut thread_rng(), size, builder)
+    }
+
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self {
+        generate(rng, size, builder)
+    }
+}
+
+/// Generates a [`Permutation`] of the given size using the given random number generator.
+fn generate<R: Rng>(rng: &mut R, size: usize, builder: &Presortedness) -> Permutation {
+    let permutation = match *builder {
+        Presortedness::Inversions { fraction } => {
+            assert!(
+                (0.0..=1.0).contains(&fraction),
+                "The fraction of inversions must be between 0 and 1"
+            );
+            let max_inversions = size * size.saturating_sub(1) / 2;
+            with_inversions(
+                rng,
+                size,
+                (fraction * max_inversions as f64).round() as usize,
+            )
+        }
+        Presortedness::Runs { runs } => {
+            assert!(runs > 0, "The number of runs must be greater than 0");
+            with_runs(rng, size, runs)
+        }
+    };
+    Permutation(permutation)
+}
+
+/// Generates a random permutation of the given size with exactly `inversions` inversions.
+fn with_inversions<R: Rng>(rng: &mut R, size: usize, inversions: usize) -> Vec<u32> {
+    // The Lehmer code of the permutation: code[i] is the number of smaller elements on the right
+    // of the i-th element, at most size - 1 - i. The sum of the code is the number of inversions.
+    let mut code = vec![0; size];
+    // Distribute the inversions visiting the positions in random order, so that they are not
+    // concentrated at the beginning of the permutation
+    let mut positions: Vec<usize> = (0..size).collect();
+    positions.shuffle(rng);
+    let mut capacity_left: usize = (0..size).sum();
+    let mut remaining = inversions;
+    for i in positions {
+        let capacity = size - 1 - i;
+        capacity_left -= capacity;
+        let low = remaining.saturating_sub(capacity_left);
+        let high = capacity.min(remaining);
+        code[i] = rng.gen_range(low..=high);
+        remaining -= code[i];
+    }
+
+    // Decode the Lehmer code: the i-th element is the code[i]-th smallest unused element
+    let mut unused = Fenwick::new(size);
+    for value in 0..size {
+        unused.add(value, 1);
+    }
+    code.into_iter()
+        .map(|rank| {
+            let value = unused.find(rank);
+            unused.add(value, -1);
+            value as u32
+        })
+        .collect()
+}
+
+/// Generates a random permutation of the given size made of `runs` ascending runs.
+fn with_runs<R: Rng>(rng: &mut R, size: usize, runs: usize) -> Vec<u32> {
+    let mut permutation: Vec<u32> = (0..size as u32).collect();
+    permutation.shuffle(rng);
+    let runs = runs.min(size.max(1));
+    // Choose the boundaries of the runs and sort each of them
+    let mut cuts: Vec<usize> = rand::seq::index::sample(rng, size.max(1), runs - 1)
+        .into_iter()
+        .map(|cut| cut + 1)
+        .filter(|&cut| cut < size)
+        .collect();
+    cuts.push(0);
+    cuts.push(size);
+    cuts.sort_unstable();
+    cuts.dedup();
+    for bounds in cuts.windows(2) {
+        permutation[bounds[0]..bounds[1]].sort_unstable();
+    }
+    permutation
+}
+
+/// A Fenwick tree over the counts of the values from 0 to n - 1.
+struct Fenwick {
+    tree: Vec<i64>,
+}
+
+impl Fenwick {
+    fn new(n: usize) -> Self {
+        Fenwick {
+            tree: vec![0; n + 1],
+        }
+    }
+
+    /// Adds `delta` to the count of `value`.
+    fn add(&mut self, value: usize, delta: i64) {
+        let mut i = value + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Returns the sum of the counts of the values smaller than `value`.
+    fn prefix_sum(&self, value: usize) -> usize {
+        let mut sum = 0;
+        let mut i = value;
+        while i > 0 {
+            sum += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum as usize
+    }
+
+    /// Returns the smallest value such that the sum of the counts of the values up to it is
+    /// greater than `rank`.
+    fn find(&self, rank: usize) -> usize {
+        let mut position = 0;
+        let mut remaining = rank as i64;
+        let mut step = (self.tree.len() - 1).next_power_of_two();
+        while step > 0 {
+            let next = position + step;
+            if next < self.tree.len() && self.tree[next] <= remaining {
+                position = next;
+                remaining -= self.tree[next];
+            }
+            step /= 2;
+        }
+        position
+    }
+}
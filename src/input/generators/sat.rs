@@ -0,0 +1,155 @@
+//! Generators for boolean formulas in conjunctive normal form.
+//!
+//! The [`CnfFormula`] input type can be used to measure the time complexity of SAT solvers,
+//! heuristics and backtracking algorithms. The size of a [`CnfFormula`] is its number of
+//! variables.
+//!
+//! The formulas are random k-SAT formulas, generated with the [`KSatGenerator`] builder: each
+//! clause contains k distinct variables chosen uniformly at random, each negated with probability
+//! 1/2. The number of clauses is the number of variables multiplied by the clause/variable ratio,
+//! which controls the hardness of the formulas: for random 3-SAT, the hardest formulas have a ratio
+//! around 4.26, the satisfiability threshold.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::Input;
+//! use chrono_probe::input::generators::sat::{CnfFormula, KSatGenerator};
+//!
+//! let formula = CnfFormula::generate_input(20, &KSatGenerator { k: 3, ratio: 4.26 });
+//! assert_eq!(formula.get_size(), 20);
+//! assert_eq!(formula.clauses.len(), 85);
+//! ```
+
+use std::fmt::Write;
+
+use rand::seq::index::sample;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::input::Input;
+
+/// A boolean formula in conjunctive normal form.
+///
+/// The literals use the DIMACS convention: the variables are numbered from 1, a positive literal
+/// `v` is the variable `v` and a negative literal `-v` is its negation.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CnfFormula {
+    /// The number of variables of the formula
+    pub variables: usize,
+    /// The clauses of the formula, each clause is the disjunction of its literals
+    pub clauses: Vec<Vec<i64>>,
+}
+
+/// The builder of [`CnfFormula`], it generates random k-SAT formulas.
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct KSatGenerator {
+    /// The number of literals of each clause. If the formula has fewer variables than `k`, each
+    /// clause contains all the variables.
+    pub k: usize,
+    /// The ratio between the number of clauses and the number of variables
+    pub ratio: f64,
+}
+
+impl Default for KSatGenerator {
+    fn default() -> Self {
+        KSatGenerator { k: 3, ratio: 4.26 }
+    }
+}
+
+impl CnfFormula {
+    /// Returns true if the given assignment satisfies the formula.
+    ///
+    /// # Arguments
+    ///
+    /// * `assignment` - The value of each variable, the value of the variable `v` is at index
+    ///   `v - 1`.
+    pub fn is_satisfied_by(&self, assignment: &[bool]) -> bool {
+        assert_eq!(
+            assignment.len(),
+            self.variables,
+            "The assignment must give a value to every variable"
+        );
+        self.clauses.iter().all(|clause| {
+            clause.iter().any(|&literal| {
+                let value = assignment[literal.unsigned_abs() as usize - 1];
+                value == (literal > 0)
+            })
+        })
+    }
+
+    /// Returns the formula in the DIMACS CNF format, used by most SAT solvers.
+    pub fn to_dimacs(&self) -> String {
+        let mut dimacs = format!("p cnf {} {}\n", self.variables, self.clauses.len());
+        for clause in &self.clauses {
+            for literal in clause {
+                write!(dimacs, "{literal} ").unwrap();
+            }
+            dimacs.push_str("0\n");
+        }
+        dimacs
+    }
+}
+
+impl Input for CnfFormula {
+    type Builder<'a> = KSatGenerator;
+
+    fn get_size(&self) -> usize {
+        self.variables
+    }
+
+    fn get_size_bytes(&self) -> Option<usize> {
+        Some(
+            self.clauses
+                .iter()
+                .map(|clause| clause.len() * std::mem::size_of::<i64>())
+                .sum(),
+        )
+    }
+
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+        generate(&mut thread_rng(), size, builder)
+    }
+
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self {
+        generate(rng, size, builder)
+    }
+}
+
+/// Generates a random k-SAT formula with the given number of variables using the given random
+/// number generator.
+fn generate<R: Rng>(rng: &mut R, size: usize, builder: &KSatGenerator) -> CnfFormula {
+    assert!(
+        builder.k > 0,
+        "The number of literals per clause must be greater than 0"
+    );
+    assert!(
+        builder.ratio >= 0.0,
+        "The clause/variable ratio must not be negative"
+    );
+    let k = builder.k.min(size);
+    let n_clauses = (builder.ratio * size as f64).round() as usize;
+    let clauses = if k == 0 {
+        Vec::new()
+    } else {
+        (0..n_clauses)
+            .map(|_| {
+                sample(rng, size, k)
+                    .into_iter()
+                    .map(|variable| {
+                        let literal = variable as i64 + 1;
+                        if rng.gen() {
+                            literal
+                        } else {
+                            -literal
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+    CnfFormula {
+        variables: size,
+        clauses,
+    }
+}
@@ -0,0 +1,78 @@
+//! Generators for random strings.
+//!
+//! The [`AsciiString`] input type can be used to measure the time complexity of string
+//! algorithms on random text. The size of an [`AsciiString`] is its number of characters.
+//!
+//! The characters are chosen uniformly at random from an alphabet, a reference to which is the
+//! builder of the inputs. Small alphabets give many repetitions, which is the worst case of some
+//! pattern matching algorithms.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::Input;
+//! use chrono_probe::input::generators::string::AsciiString;
+//!
+//! let string = AsciiString::generate_input(100, &"ab");
+//! assert!(string.chars().all(|c| c == 'a' || c == 'b'));
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::input::Input;
+
+/// A random string of ASCII characters.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AsciiString(pub String);
+
+impl Deref for AsciiString {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for AsciiString {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Input for AsciiString {
+    type Builder<'a> = &'a str;
+
+    fn get_size(&self) -> usize {
+        self.len()
+    }
+
+    fn get_size_bytes(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+        generate(&mut thread_rng(), size, builder)
+    }
+
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self {
+        generate(rng, size, builder)
+    }
+}
+
+/// Generates an [`AsciiString`] of the given size using the given random number generator.
+fn generate<R: Rng>(rng: &mut R, size: usize, alphabet: &str) -> AsciiString {
+    assert!(!alphabet.is_empty(), "The alphabet must not be empty");
+    assert!(
+        alphabet.is_ascii(),
+        "The alphabet must contain only ASCII characters"
+    );
+    let alphabet = alphabet.as_bytes();
+    AsciiString(
+        (0..size)
+            .map(|_| alphabet[rng.gen_range(0..alphabet.len())] as char)
+            .collect(),
+    )
+}
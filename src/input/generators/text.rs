@@ -0,0 +1,162 @@
+//! Generators for natural text.
+//!
+//! String algorithms often behave very differently on real text than on random characters
+//! (think of the repetitions exploited by pattern matching or compression algorithms). The
+//! [`TextSample`] input type samples windows of a user-provided text [`Corpus`], so that those
+//! algorithms can be measured on realistic inputs.
+//!
+//! The corpus is loaded once and borrowed by the builder of the inputs: for each requested size n,
+//! a window of n characters starting at a random position of the corpus is taken. If n is larger
+//! than the corpus, the corpus is repeated cyclically.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::text::{Corpus, TextSample};
+//! use chrono_probe::input::InputBuilder;
+//!
+//! let corpus = Corpus::new("the quick brown fox jumps over the lazy dog");
+//! let builder: InputBuilder<TextSample, _> = InputBuilder::new(Uniform::new(5..=20).unwrap(), &corpus);
+//! let inputs = builder.build(10);
+//! ```
+
+use std::fs;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::input::scalable::ScalableInput;
+use crate::input::Input;
+
+/// A text corpus, a reference to it is used as the builder of [`TextSample`].
+#[derive(Clone, Debug)]
+pub struct Corpus {
+    text: String,
+    // The byte offset of every character of the text, None if the text is ASCII
+    offsets: Option<Vec<usize>>,
+}
+
+/// A window of text sampled from a [`Corpus`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TextSample(pub String);
+
+impl Corpus {
+    /// Creates a new corpus from the given text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text of the corpus, it must not be empty.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        assert!(!text.is_empty(), "The corpus must not be empty.");
+        let offsets = if text.is_ascii() {
+            None
+        } else {
+            Some(text.char_indices().map(|(i, _)| i).collect())
+        };
+        Corpus { text, offsets }
+    }
+
+    /// Loads a corpus from a UTF-8 text file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to load.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Corpus::new(fs::read_to_string(path)?))
+    }
+
+    /// Returns the number of characters of the corpus.
+    pub fn len(&self) -> usize {
+        match &self.offsets {
+            None => self.text.len(),
+            Some(offsets) => offsets.len(),
+        }
+    }
+
+    /// Returns true if the corpus contains no characters. This never happens for a corpus
+    /// created with [`Corpus::new`].
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Returns the byte offset of the character with the given index.
+    fn offset(&self, index: usize) -> usize {
+        match &self.offsets {
+            None => index,
+            Some(offsets) => offsets.get(index).copied().unwrap_or(self.text.len()),
+        }
+    }
+
+    /// Returns the window of `n` characters starting at the character with index `start`,
+    /// wrapping around the end of the corpus if needed.
+    pub fn window(&self, start: usize, n: usize) -> String {
+        let len = self.len();
+        let mut window = String::with_capacity(n);
+        let mut start = start % len;
+        let mut remaining = n;
+        while remaining > 0 {
+            let end = len.min(start + remaining);
+            window.push_str(&self.text[self.offset(start)..self.offset(end)]);
+            remaining -= end - start;
+            start = 0;
+        }
+        window
+    }
+}
+
+impl Deref for TextSample {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for TextSample {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Input for TextSample {
+    type Builder<'a> = &'a Corpus;
+
+    fn get_size(&self) -> usize {
+        self.chars().count()
+    }
+
+    fn get_size_bytes(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+        Self::render(&Self::new_instance(builder), size, builder)
+    }
+
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self {
+        Self::render(&Self::new_instance_with(builder, rng), size, builder)
+    }
+}
+
+/// The logical instance of a [`TextSample`] is the position of the corpus where the window
+/// starts: rendering the same instance at different sizes gives windows with the same prefix.
+impl ScalableInput for TextSample {
+    type Instance = usize;
+
+    fn new_instance(builder: &Self::Builder<'_>) -> Self::Instance {
+        Self::new_instance_with(builder, &mut thread_rng())
+    }
+
+    fn new_instance_with<R: Rng>(builder: &Self::Builder<'_>, rng: &mut R) -> Self::Instance {
+        rng.gen_range(0..builder.len())
+    }
+
+    fn render(instance: &Self::Instance, size: usize, builder: &Self::Builder<'_>) -> Self {
+        TextSample(builder.window(*instance, size))
+    }
+}
@@ -0,0 +1,85 @@
+//! Generators for vectors of integers.
+//!
+//! The [`U32Vec`] input type can be used to measure the time complexity of the algorithms working
+//! on sequences of numbers, like sorting and searching algorithms. The size of a [`U32Vec`] is the
+//! number of elements it contains.
+//!
+//! The order of the elements is chosen with the [`VecOrder`] builder.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::Input;
+//! use chrono_probe::input::generators::vec::{U32Vec, VecOrder};
+//!
+//! let vec = U32Vec::generate_input(100, &VecOrder::Sorted);
+//! assert!(vec.windows(2).all(|w| w[0] <= w[1]));
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::input::Input;
+
+/// A vector of random `u32`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct U32Vec(pub Vec<u32>);
+
+/// The builder of [`U32Vec`], it selects the order of the elements.
+#[derive(Serialize, Clone, Copy, Debug, Default)]
+pub enum VecOrder {
+    /// Elements in random order.
+    #[default]
+    Random,
+    /// Elements sorted in nondecreasing order.
+    Sorted,
+    /// Elements sorted in nonincreasing order.
+    Reversed,
+}
+
+impl Deref for U32Vec {
+    type Target = Vec<u32>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for U32Vec {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Input for U32Vec {
+    type Builder<'a> = VecOrder;
+
+    fn get_size(&self) -> usize {
+        self.len()
+    }
+
+    fn get_size_bytes(&self) -> Option<usize> {
+        Some(self.len() * std::mem::size_of::<u32>())
+    }
+
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+        generate(&mut thread_rng(), size, builder)
+    }
+
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self {
+        generate(rng, size, builder)
+    }
+}
+
+/// Generates a [`U32Vec`] of the given size using the given random number generator.
+fn generate<R: Rng>(rng: &mut R, size: usize, builder: &VecOrder) -> U32Vec {
+    let mut vec: Vec<u32> = (0..size).map(|_| rng.gen()).collect();
+    match builder {
+        VecOrder::Random => {}
+        VecOrder::Sorted => vec.sort_unstable(),
+        VecOrder::Reversed => vec.sort_unstable_by(|a, b| b.cmp(a)),
+    }
+    U32Vec(vec)
+}
@@ -0,0 +1,130 @@
+// Macros helping the implementation of the Input trait.
+
+/// Implements the [`Input`](crate::input::Input) trait for a type, without writing the
+/// boilerplate of the `impl` block.
+///
+/// The macro supports three kinds of input types: types without a builder, types whose
+/// builder is a closure and types with a custom builder (e.g. an enum selecting the generator).
+/// In every case, the size of the input is given with a non-capturing closure taking a reference
+/// to the input.
+///
+/// # Examples
+///
+/// ## Types without a builder
+///
+/// The builder is `()` and the inputs are generated with a non-capturing closure taking the size:
+///
+/// ```
+/// use chrono_probe::impl_input;
+/// use chrono_probe::input::distribution::Uniform;
+/// use chrono_probe::input::InputBuilder;
+///
+/// #[derive(Clone)]
+/// pub struct InputVec(Vec<u32>);
+///
+/// impl_input!(
+///     InputVec,
+///     size = |input| input.0.len(),
+///     generate = |size| InputVec((0..size as u32).rev().collect())
+/// );
+///
+/// let builder: InputBuilder<InputVec, _> = InputBuilder::new(Uniform::new(10..=100).unwrap(), ());
+/// let inputs = builder.build(10);
+/// ```
+///
+/// ## Types whose builder is a closure
+///
+/// The builder is a boxed closure taking the size and returning the input, so that different
+/// generators can be chosen when creating the [`InputBuilder`](crate::input::InputBuilder):
+///
+/// ```
+/// use chrono_probe::impl_input;
+/// use chrono_probe::input::distribution::Uniform;
+/// use chrono_probe::input::InputBuilder;
+///
+/// #[derive(Clone)]
+/// pub struct InputVec(Vec<u32>);
+///
+/// impl_input!(InputVec, builder = fn, size = |input| input.0.len());
+///
+/// let max = 1000;
+/// let generator: Box<dyn Fn(usize) -> InputVec> = Box::new(|size| InputVec(vec![max; size]));
+/// let builder: InputBuilder<InputVec, _> = InputBuilder::new(Uniform::new(10..=100).unwrap(), generator);
+/// let inputs = builder.build(10);
+/// ```
+///
+/// ## Types with a custom builder
+///
+/// The inputs are generated with a non-capturing closure taking the size and a reference to the
+/// builder:
+///
+/// ```
+/// use chrono_probe::impl_input;
+/// use chrono_probe::input::distribution::Uniform;
+/// use chrono_probe::input::InputBuilder;
+///
+/// #[derive(Clone)]
+/// pub struct InputVec(Vec<u32>);
+///
+/// pub enum Generator {
+///     Sorted,
+///     Reversed,
+/// }
+///
+/// impl_input!(
+///     InputVec,
+///     builder = Generator,
+///     size = |input| input.0.len(),
+///     generate = |size, builder| match builder {
+///         Generator::Sorted => InputVec((0..size as u32).collect()),
+///         Generator::Reversed => InputVec((0..size as u32).rev().collect()),
+///     }
+/// );
+///
+/// let builder: InputBuilder<InputVec, _> =
+///     InputBuilder::new(Uniform::new(10..=100).unwrap(), Generator::Reversed);
+/// let inputs = builder.build(10);
+/// ```
+#[macro_export]
+macro_rules! impl_input {
+    ($input:ty, builder = fn, size = $size:expr $(,)?) => {
+        impl $crate::input::Input for $input {
+            type Builder<'a> = ::std::boxed::Box<dyn Fn(usize) -> $input + 'a>;
+
+            fn get_size(&self) -> usize {
+                let size: fn(&$input) -> usize = $size;
+                size(self)
+            }
+
+            fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+                builder(size)
+            }
+        }
+    };
+    ($input:ty, builder = $builder:ty, size = $size:expr, generate = $generate:expr $(,)?) => {
+        impl $crate::input::Input for $input {
+            type Builder<'a> = $builder;
+
+            fn get_size(&self) -> usize {
+                let size: fn(&$input) -> usize = $size;
+                size(self)
+            }
+
+            fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
+                let generate: fn(usize, &$builder) -> $input = $generate;
+                generate(size, builder)
+            }
+        }
+    };
+    ($input:ty, size = $size:expr, generate = $generate:expr $(,)?) => {
+        $crate::impl_input!(
+            $input,
+            builder = (),
+            size = $size,
+            generate = |size, _| {
+                let generate: fn(usize) -> $input = $generate;
+                generate(size)
+            }
+        );
+    };
+}
@@ -11,9 +11,20 @@
 //! have:
 //!
 //! * Figured out which distribution suits your needs (read the [distribution] documentation
-//! for more infos).
+//!   for more infos).
 //! * Created your input type (read the example below).
 //!
+//! If you don't want to write your own input type, the [`generators`] submodule provides
+//! ready-made ones for common families of algorithms, and the [`presets`] submodule provides
+//! ready-made builders for the most common experiments. Existing generators can also be composed
+//! with the combinators of the [`adapters`] submodule.
+//!
+//! For simple input types, the [`impl_input!`](crate::impl_input) macro can be used to implement
+//! the [`Input`] trait without writing the boilerplate of the `impl` block.
+//!
+//! Input types whose instances can be rendered at any size can implement the
+//! [`scalable::ScalableInput`] trait, so that the same logical inputs are measured at every size.
+//!
 //! # Example
 //!
 //! ## Basic usage
@@ -38,7 +49,7 @@
 //! # }
 //!
 //! impl Input for PrimeTestInput {
-//!     type Builder = ();
+//!     type Builder<'a> = ();
 //!
 //!     // Return the size of the input.
 //!     fn get_size(&self) -> usize {
@@ -47,7 +58,7 @@
 //!     }
 //!
 //!     // Generate a random input of the given size.
-//!     fn generate_input(size: usize, builder: &Self::Builder) -> Self {
+//!     fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
 //!         let mut rng = rand::thread_rng();
 //!         PrimeTestInput {
 //!             // We consider the size as the number of bits.
@@ -97,7 +108,7 @@
 //! # fn generate_order_vector(size: usize, min: u32, max: u32) -> PrimeTestInput { todo!() }
 //!
 //! impl Input for PrimeTestInput {
-//!     type Builder = Generator;
+//!     type Builder<'a> = Generator;
 //!
 //!     // Return the size of the input.
 //!     fn get_size(&self) -> usize {
@@ -106,7 +117,7 @@
 //!     }
 //!
 //!     // Generate a random input of the given size.
-//!     fn generate_input(size: usize, builder: &Self::Builder) -> Self {
+//!     fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self {
 //!         match builder {
 //!             Generator::Fast => generate_order_vector_fast(size, u32::MIN, u32::MAX),
 //!             Generator::Uniform => generate_order_vector(size, u32::MIN, u32::MAX),
@@ -124,52 +135,305 @@
 //! If you need to use a primitive type as an input you need to create a new wrapper type, for
 //! more information refer to the [rust guide](https://doc.rust-lang.org/rust-by-example/generics/new_types.html)
 
+use std::collections::BTreeMap;
 use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::time::{Duration, Instant};
 
-use serde::Serialize;
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "bincode")]
+use crate::measurements::{ExportError, ImportError};
+use crate::progress::{DefaultProgress, Progress, ProgressHandler, Stage};
 
 use self::distribution::Distribution;
 
+mod macros;
+
+pub mod adapters;
 pub mod distribution;
+pub mod generators;
+pub mod presets;
+pub mod scalable;
 
 /// Trait that must be implemented by algorithms' input types.
 pub trait Input {
     /// The type of the builder. A builder can be used to select the type of generation for the inputs.
-    type Builder;
+    ///
+    /// The builder can borrow data with the lifetime `'a`, so that big data needed by the
+    /// generation (e.g. a loaded text corpus or a lookup table) doesn't need to be owned by the
+    /// builder.
+    type Builder<'a>;
     /// Returns the size of the input.
     fn get_size(&self) -> usize;
+    /// Returns the size of the input in bytes, if known.
+    ///
+    /// By default, it returns `None`. It can be overridden to let the library know how much
+    /// memory the inputs take. When known, the size in bytes is kept in the measurements together
+    /// with the size returned by [`Input::get_size`], and can be used as the x-axis of the plots
+    /// (see [`XAxis`](crate::plot::XAxis)).
+    fn get_size_bytes(&self) -> Option<usize> {
+        None
+    }
     /// Generates an input of the given size, using the given builder.
-    fn generate_input(size: usize, builder: &Self::Builder) -> Self;
+    fn generate_input(size: usize, builder: &Self::Builder<'_>) -> Self;
+    /// Generates an input of the given size, using the given builder and the given random number
+    /// generator.
+    ///
+    /// This method is used instead of [`Input::generate_input`] when a seed is set on the
+    /// [`InputBuilder`], so that the generated inputs can be reproduced. By default, it ignores
+    /// the random number generator and calls [`Input::generate_input`]: it should be overridden
+    /// by the input types that want to support reproducible generation.
+    fn generate_input_with<R: Rng>(size: usize, builder: &Self::Builder<'_>, rng: &mut R) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = rng;
+        Self::generate_input(size, builder)
+    }
 }
 
 /// Struct that holds the inputs.
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub struct InputSet<I: Input> {
     /// The inputs.
     /// The inputs are grouped by size.
     pub inputs: Vec<Vec<I>>,
+    /// The seed used to generate the inputs, if any.
+    pub seed: Option<u64>,
+    /// The time spent generating the inputs, if they were generated by an [`InputBuilder`].
+    pub generation_time: Option<GenerationTime>,
+}
+
+/// The time spent generating the inputs of an [`InputSet`].
+///
+/// Only the time spent in the generation of the inputs (e.g. in [`Input::generate_input`]) is
+/// taken into account. It can be compared with the time spent measuring the algorithms, to know
+/// whether the generation of the inputs is dominating the duration of an experiment.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct GenerationTime {
+    /// The total time spent generating the inputs
+    #[serde(with = "crate::measurements::nanos")]
+    pub total: Duration,
+    /// The time spent generating each group of inputs with the same size, as (size, time) pairs
+    #[serde(with = "crate::measurements::nanos::sized")]
+    pub per_size: Vec<(usize, Duration)>,
+}
+
+impl GenerationTime {
+    /// Adds the time spent generating a group of inputs with the given size.
+    pub(crate) fn push(&mut self, size: usize, time: Duration) {
+        self.total += time;
+        self.per_size.push((size, time));
+    }
+
+    /// Merges the generation times of two different sets of inputs.
+    pub(crate) fn merge(mut self, other: GenerationTime) -> GenerationTime {
+        self.total += other.total;
+        self.per_size.extend(other.per_size);
+        self
+    }
 }
 
 /// Struct used for building an [`InputSet`].
 #[derive(Serialize)]
-pub struct InputBuilder<I: Input, D: Distribution> {
+pub struct InputBuilder<'a, I: Input, D: Distribution> {
     // The distribution that will be used to generate the input lengths.
     pub(crate) distribution: D,
     // The builder that will be used to generate the inputs.
-    pub(crate) builder: I::Builder,
+    pub(crate) builder: I::Builder<'a>,
+    // The user-provided estimate of the bytes taken by an input of a given size.
+    #[serde(skip)]
+    pub(crate) bytes_estimate: Option<Box<dyn Fn(usize) -> usize>>,
+    // The maximum number of bytes the generated inputs are allowed to take.
+    #[serde(skip)]
+    pub(crate) memory_limit: Option<usize>,
+    // The seed used to generate the inputs.
+    pub(crate) seed: Option<u64>,
+    // The number of buckets the range of the input sizes is partitioned into.
+    pub(crate) buckets: Option<usize>,
 }
 
-impl<I: Input, D: Distribution> InputBuilder<I, D> {
+impl<'a, I: Input, D: Distribution> InputBuilder<'a, I, D> {
     /// Creates a new [`InputBuilder`].
     ///
     /// # Arguments
     ///
     /// * `distribution` - The distribution that will be used to generate the input lengths.
     /// * `builder` - The builder that will be used to generate the inputs.
-    pub fn new(distribution: D, builder: I::Builder) -> InputBuilder<I, D> {
+    pub fn new(distribution: D, builder: I::Builder<'a>) -> InputBuilder<'a, I, D> {
         InputBuilder {
             distribution,
             builder,
+            bytes_estimate: None,
+            memory_limit: None,
+            seed: None,
+            buckets: None,
+        }
+    }
+
+    /// Sets the seed used to generate the inputs.
+    ///
+    /// When a seed is set, the input sizes are generated with [`Distribution::generate_with`]
+    /// and the inputs with [`Input::generate_input_with`], using a random number generator
+    /// initialized with the seed, so that the same inputs are generated every time. The seed is
+    /// recorded in the generated [`InputSet`] and in the
+    /// [`Measurements`](crate::measurements::Measurements) obtained from it, so that the
+    /// serialized results can be reproduced.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed.
+    pub fn with_seed(mut self, seed: u64) -> InputBuilder<'a, I, D> {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Returns the random number generator initialized with the seed, if any.
+    pub(crate) fn seeded_rng(&self) -> Option<StdRng> {
+        self.seed.map(StdRng::seed_from_u64)
+    }
+
+    /// Partitions the range of the input sizes into `k` equally wide buckets, and guarantees
+    /// that the same number of input sizes is generated in each bucket, whatever the
+    /// distribution.
+    ///
+    /// Without buckets, a distribution like [`Exponential`](distribution::Exponential) with
+    /// random intervals can leave the large end of the range almost uncovered. With buckets, the
+    /// sizes are still sampled from the distribution inside each bucket, as far as possible. The
+    /// distribution must report its range with [`Distribution::range`].
+    ///
+    /// ```
+    /// # use chrono_probe::input::distribution::{Exponential, GenerationType};
+    /// # use chrono_probe::input::generators::geometry::{PointSet, PointSetGenerator};
+    /// # use chrono_probe::input::InputBuilder;
+    /// let mut distribution = Exponential::new(10..=1000).unwrap();
+    /// distribution.set_gen_type(GenerationType::Random);
+    /// let builder: InputBuilder<PointSet, _> =
+    ///     InputBuilder::new(distribution, PointSetGenerator::default()).with_buckets(4);
+    ///
+    /// // 5 input sizes in each of [10, 256], [257, 504], [505, 752] and [753, 1000]
+    /// let inputs = builder.build(20);
+    /// let large = inputs.inputs.iter().filter(|group| group[0].len() >= 753).count();
+    /// assert_eq!(large, 5);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The number of buckets, it must not be greater than the number of input sizes.
+    pub fn with_buckets(mut self, k: usize) -> InputBuilder<'a, I, D> {
+        assert!(k > 0, "The number of buckets must be greater than 0");
+        self.buckets = Some(k);
+        self
+    }
+
+    /// Generates `n` input sizes using the distribution, in buckets if requested. The given
+    /// random number generator is used if any, so that the sizes can be reproduced.
+    pub(crate) fn generate_sizes(&self, n: usize, rng: Option<&mut StdRng>) -> Vec<usize> {
+        match (self.buckets, rng) {
+            (Some(buckets), Some(rng)) => {
+                distribution::stratify(&self.distribution, n, buckets, rng)
+            }
+            (Some(buckets), None) => {
+                distribution::stratify(&self.distribution, n, buckets, &mut thread_rng())
+            }
+            (None, Some(rng)) => self.distribution.generate_with(rng, n),
+            (None, None) => self.distribution.generate(n),
+        }
+    }
+
+    /// Sets the function used to estimate the bytes taken by an input of a given size.
+    ///
+    /// If no function is set, the estimate is obtained by generating a small input and
+    /// extrapolating linearly the size in bytes it reports with [`Input::get_size_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `estimate` - A function returning the bytes taken by an input of the given size.
+    pub fn with_bytes_estimate<F>(mut self, estimate: F) -> InputBuilder<'a, I, D>
+    where
+        F: Fn(usize) -> usize + 'static,
+    {
+        self.bytes_estimate = Some(Box::new(estimate));
+        self
+    }
+
+    /// Sets the maximum number of bytes the generated inputs are allowed to take.
+    ///
+    /// When a limit is set, the memory needed is estimated before generating the inputs (see
+    /// [`InputBuilder::estimate_memory`]), and a warning is printed on the standard error if the
+    /// limit is exceeded, before trying to allocate the inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The maximum number of bytes.
+    pub fn with_memory_limit(mut self, bytes: usize) -> InputBuilder<'a, I, D> {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Estimates the number of bytes that the inputs generated with the given arguments would
+    /// take, without generating them.
+    ///
+    /// When a seed is set, the estimate is based on the same sizes that the build generates,
+    /// otherwise on a new draw of the sizes from the distribution, which may differ from the one
+    /// of the build.
+    ///
+    /// Returns `None` if no function was set with [`InputBuilder::with_bytes_estimate`] and the
+    /// input type doesn't report its size in bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of inputs to be generated (excluding repetitions).
+    /// * `repetitions` - The number of repetitions for each input size.
+    pub fn estimate_memory(&self, n: usize, repetitions: usize) -> Option<usize> {
+        let sizes = self.generate_sizes(n, self.seeded_rng().as_mut());
+        self.estimate_memory_of_sizes(&sizes, &|_| repetitions)
+    }
+
+    /// Estimates the number of bytes taken by the inputs of the given sizes, each one repeated
+    /// the number of times given by `repetitions`.
+    fn estimate_memory_of_sizes(
+        &self,
+        sizes: &[usize],
+        repetitions: &dyn Fn(usize) -> usize,
+    ) -> Option<usize> {
+        let bytes: f64 = match &self.bytes_estimate {
+            Some(estimate) => sizes
+                .iter()
+                .map(|size| estimate(*size).saturating_mul(repetitions(*size)) as f64)
+                .sum(),
+            None => {
+                // Generate a small input and extrapolate the bytes it takes
+                let probe_size = sizes.iter().min()?.clamp(&1, &1024);
+                let probe_bytes = I::generate_input(*probe_size, &self.builder).get_size_bytes()?;
+                let bytes_per_element = probe_bytes as f64 / *probe_size as f64;
+                sizes
+                    .iter()
+                    .map(|size| size.saturating_mul(repetitions(*size)) as f64 * bytes_per_element)
+                    .sum()
+            }
+        };
+        Some(bytes as usize)
+    }
+
+    /// Prints a warning if a memory limit is set and the inputs of the given sizes would exceed
+    /// it.
+    pub(crate) fn check_memory_limit(&self, sizes: &[usize], repetitions: &dyn Fn(usize) -> usize) {
+        if let Some(limit) = self.memory_limit {
+            if let Some(estimate) = self.estimate_memory_of_sizes(sizes, repetitions) {
+                if estimate > limit {
+                    eprintln!(
+                        "Warning: the inputs would take about {} bytes, exceeding the memory \
+                         limit of {} bytes",
+                        estimate, limit
+                    );
+                }
+            }
         }
     }
 
@@ -190,57 +454,304 @@ impl<I: Input, D: Distribution> InputBuilder<I, D> {
     /// * `n` - The number of inputs to be generated (excluding repetitions: the actual amount of inputs generated is n*repetitions).
     /// * `repetitions` - The number of repetitions for each input size.
     pub fn build_with_repetitions(&self, n: usize, repetitions: usize) -> InputSet<I> {
+        self.build_with_progress(n, repetitions, &mut DefaultProgress::default())
+    }
+
+    /// Generates the inputs with a number of repetitions depending on the size of the inputs.
+    ///
+    /// Small inputs usually need many repetitions to obtain an accurate result, while big inputs
+    /// need few of them (and take a lot of memory). For example, the following builds at least
+    /// 3 inputs for each size, and more of them for the small sizes:
+    ///
+    /// ```
+    /// # use chrono_probe::input::distribution::Uniform;
+    /// # use chrono_probe::input::generators::geometry::{PointSet, PointSetGenerator};
+    /// # use chrono_probe::input::InputBuilder;
+    /// # let builder: InputBuilder<PointSet, _> =
+    /// #     InputBuilder::new(Uniform::new(10..=1000).unwrap(), PointSetGenerator::default());
+    /// let inputs = builder.build_with_repetitions_fn(20, |size| 3.max(1000 / size));
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of input sizes to be generated.
+    /// * `repetitions` - A function returning the number of repetitions for the given input size.
+    pub fn build_with_repetitions_fn<F>(&self, n: usize, repetitions: F) -> InputSet<I>
+    where
+        F: Fn(usize) -> usize,
+    {
+        self.build_groups(n, &repetitions, &mut DefaultProgress::default())
+    }
+
+    /// Generates the inputs with repetitions, reporting the progress of the generation to the
+    /// given [`ProgressHandler`] after each input size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of inputs to be generated (excluding repetitions: the actual amount of inputs generated is n*repetitions).
+    /// * `repetitions` - The number of repetitions for each input size.
+    /// * `progress` - The handler receiving the progress reports.
+    pub fn build_with_progress<P: ProgressHandler>(
+        &self,
+        n: usize,
+        repetitions: usize,
+        progress: &mut P,
+    ) -> InputSet<I> {
+        self.build_groups(n, &|_| repetitions, progress)
+    }
+
+    /// Generates `n` groups of inputs with the same size, the number of inputs of each group
+    /// being given by the `repetitions` function.
+    fn build_groups<P: ProgressHandler>(
+        &self,
+        n: usize,
+        repetitions: &dyn Fn(usize) -> usize,
+        progress: &mut P,
+    ) -> InputSet<I> {
         // TODO: remove these assertions: usually asserts are not used in libraries
         // A better way to handle this would be to return a Result instead of panicking
         assert!(
             n > 0,
             "The number of inputs to be generated must be greater than 0"
         );
-        assert!(
-            repetitions > 0,
-            "The number of repetitions must be greater than 0"
-        );
 
+        // Generate the input lengths using the given distribution
+        let mut rng = self.seeded_rng();
+        let length_distribution = self.generate_sizes(n, rng.as_mut());
+
+        self.build_sizes(&length_distribution, repetitions, rng.as_mut(), progress)
+    }
+
+    /// Generates a group of inputs for each of the given sizes, the number of inputs of each group
+    /// being given by the `repetitions` function. The given random number generator is used if
+    /// any, so that the inputs can be reproduced.
+    pub(crate) fn build_sizes<P: ProgressHandler>(
+        &self,
+        length_distribution: &[usize],
+        repetitions: &dyn Fn(usize) -> usize,
+        mut rng: Option<&mut StdRng>,
+        progress: &mut P,
+    ) -> InputSet<I> {
         // Initialize the inputs vec with the correct capacity
-        let mut inputs = Vec::with_capacity(n);
+        let mut inputs = Vec::with_capacity(length_distribution.len());
 
-        // Generate the input lengths using the given distribution
-        let length_distribution = self.distribution.generate(n);
+        // Check that the inputs fit in the memory limit before allocating them
+        self.check_memory_limit(length_distribution, repetitions);
 
-        // Printing in the console for debug purposes
-        #[cfg(feature = "debug")]
-        println!("Generating inputs...\n");
+        let start = Instant::now();
+        let total = length_distribution
+            .iter()
+            .map(|size| repetitions(*size))
+            .sum();
+        let mut completed = 0;
+        // The bytes allocated so far, None as soon as an input doesn't report its size in bytes
+        let mut bytes = Some(0);
+        let mut generation_time = GenerationTime::default();
 
         // Iterate over the input lengths
-        for (_j, input_size) in length_distribution.iter().enumerate() {
+        for input_size in length_distribution.iter() {
+            let repetitions = repetitions(*input_size);
+            assert!(
+                repetitions > 0,
+                "The number of repetitions must be greater than 0"
+            );
+
             // Initialize the vec holding the inputs with the same size
             let mut inputs_with_same_size = Vec::with_capacity(repetitions);
 
             // Iterate over the repetitions
+            let mut group_time = Duration::ZERO;
             for _ in 0..repetitions {
                 // Generate the inputs of the given size and push them to the vec
-                inputs_with_same_size.push(I::generate_input(*input_size, &self.builder));
+                let start_generation = Instant::now();
+                let input = match &mut rng {
+                    Some(rng) => I::generate_input_with(*input_size, &self.builder, *rng),
+                    None => I::generate_input(*input_size, &self.builder),
+                };
+                group_time += start_generation.elapsed();
+                bytes = bytes.zip(input.get_size_bytes()).map(|(a, b)| a + b);
+                inputs_with_same_size.push(input);
             }
+            generation_time.push(*input_size, group_time);
 
             // Push the vec holding the inputs with the same size to the inputs vec
             inputs.push(inputs_with_same_size);
+            completed += repetitions;
 
-            // Printing in the console the progress for debug purposes
-            #[cfg(feature = "debug")]
+            // Report the progress
+            progress.on_progress(&Progress {
+                stage: Stage::InputGeneration,
+                label: None,
+                completed,
+                total,
+                bytes,
+                elapsed: start.elapsed(),
+                point: None,
+            });
+        }
+
+        // Return the input set
+        InputSet {
+            inputs,
+            seed: self.seed,
+            generation_time: Some(generation_time),
+        }
+    }
+}
+
+impl<I: Input> InputSet<I> {
+    /// Creates a new input set from inputs obtained in any way (e.g. loaded from files), grouping
+    /// them by size.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The inputs.
+    pub fn from_inputs(inputs: Vec<I>) -> InputSet<I> {
+        InputSet {
+            inputs: vec![inputs],
+            seed: None,
+            generation_time: None,
+        }
+        .regroup_by_size()
+    }
+
+    /// Concatenates two input sets, e.g. generated and loaded inputs, so that they can be
+    /// measured in one run.
+    ///
+    /// The inputs with the same size are grouped together (see [`InputSet::regroup_by_size`]).
+    /// The seed is kept only if it is the same for both input sets, while the generation times
+    /// are added together if known for both.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The input set to concatenate to this one.
+    pub fn concat(mut self, other: InputSet<I>) -> InputSet<I> {
+        self.inputs.extend(other.inputs);
+        if self.seed != other.seed {
+            self.seed = None;
+        }
+        self.generation_time = match (self.generation_time, other.generation_time) {
+            (Some(time), Some(other_time)) => Some(time.merge(other_time)),
+            _ => None,
+        };
+        self.regroup_by_size()
+    }
+
+    /// Regroups the inputs so that every group contains all and only the inputs with the same
+    /// size, with the groups sorted by size. Empty groups are removed.
+    ///
+    /// The generation times of the groups with the same size, if known, are added together.
+    pub fn regroup_by_size(self) -> InputSet<I> {
+        let mut groups: BTreeMap<usize, Vec<I>> = BTreeMap::new();
+        for input in self.inputs.into_iter().flatten() {
+            groups.entry(input.get_size()).or_default().push(input);
+        }
+        let generation_time = self.generation_time.map(|time| {
+            let mut per_size: BTreeMap<usize, Duration> = BTreeMap::new();
+            for (size, time) in time.per_size {
+                *per_size.entry(size).or_default() += time;
+            }
+            GenerationTime {
+                total: time.total,
+                per_size: per_size.into_iter().collect(),
+            }
+        });
+        InputSet {
+            inputs: groups.into_values().collect(),
+            seed: self.seed,
+            generation_time,
+        }
+    }
+
+    /// Splits the input set in `k` disjoint input sets, each one containing whole groups of
+    /// inputs with the same size.
+    ///
+    /// The groups are assigned to the shards in a round-robin fashion, so that every shard covers
+    /// the whole range of sizes and the shards take a similar time to be measured. The shards can
+    /// be measured on different machines or processes, and the results recombined with
+    /// [`Measurements::merge`](crate::measurements::Measurements::merge).
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The number of shards.
+    pub fn shard(self, k: usize) -> Vec<InputSet<I>> {
+        assert!(k > 0, "The number of shards must be greater than 0");
+        let mut shards: Vec<InputSet<I>> = (0..k)
+            .map(|_| InputSet {
+                inputs: Vec::new(),
+                seed: self.seed,
+                generation_time: self.generation_time.as_ref().map(|_| Default::default()),
+            })
+            .collect();
+        for (i, group) in self.inputs.into_iter().enumerate() {
+            let shard = &mut shards[i % k];
+            shard.inputs.push(group);
+            if let (Some(shard_time), Some(time)) =
+                (&mut shard.generation_time, &self.generation_time)
             {
-                if _j % (n / 20) == 0 {
-                    println!("{}%", _j * 100 / n);
+                if let Some(&(size, time)) = time.per_size.get(i) {
+                    shard_time.push(size, time);
                 }
             }
         }
+        shards
+    }
 
-        // Return the input set
-        InputSet { inputs }
+    /// Returns a new input set containing only the groups of inputs with the same size in the
+    /// given range.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of the indices of the groups to keep.
+    pub fn slice(&self, range: Range<usize>) -> InputSet<I>
+    where
+        I: Clone,
+    {
+        InputSet {
+            inputs: self.inputs[range.clone()].to_vec(),
+            seed: self.seed,
+            generation_time: self.generation_time.as_ref().map(|time| {
+                let mut slice_time = GenerationTime::default();
+                for (size, time) in time.per_size.iter().take(range.end).skip(range.start) {
+                    slice_time.push(*size, *time);
+                }
+                slice_time
+            }),
+        }
     }
 }
 
 impl<I: Input + Serialize> InputSet<I> {
-    /// Serializes the input set in a json file.
+    /// Writes the input set as compact JSON to the given writer, e.g. a socket, a buffer in
+    /// memory or the standard output.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer, or a mutable reference to it.
+    ///
+    /// # Errors
+    ///
+    /// * If the writer fails, or the input set can't be encoded, with the kind
+    ///   [`io::ErrorKind::InvalidData`].
+    pub fn to_writer<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Writes the input set as indented JSON to the given writer, like [`InputSet::to_writer`].
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer, or a mutable reference to it.
+    ///
+    /// # Errors
+    ///
+    /// * If the writer fails, or the input set can't be encoded, with the kind
+    ///   [`io::ErrorKind::InvalidData`].
+    pub fn to_writer_pretty<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::from)
+    }
+
+    /// Serializes the input set in a json file, see [`InputSet::to_writer`].
     /// The file will be created if it doesn't exist, otherwise it will be overwritten.
     ///
     /// # Arguments
@@ -253,8 +764,58 @@ impl<I: Input + Serialize> InputSet<I> {
     /// * Panics if the input set cannot be serialized.
     ///
     pub fn serialize_json(&self, filename: &str) {
-        let mut file = File::create(filename).unwrap();
-        serde_json::to_writer(&mut file, &self).unwrap();
-        // TODO: handle errors instead of panicking maybe returning a Result
+        let mut file = BufWriter::new(File::create(filename).unwrap());
+        self.to_writer(&mut file).unwrap();
+        file.flush().unwrap();
+    }
+
+    /// Serializes the input set to a binary file, much smaller and faster to write and to read
+    /// than the JSON of [`InputSet::serialize_json`], so that big inputs can be generated once
+    /// and reused. The file will be created if it doesn't exist, otherwise it will be
+    /// overwritten.
+    ///
+    /// The file is encoded with [bincode](https://docs.rs/bincode), which doesn't describe the
+    /// structure of the data: it can only be read by [`InputSet::deserialize_bin`], with the
+    /// same input type.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written.
+    /// * [`ExportError::Format`] - If the inputs can't be encoded, e.g. if their serialization
+    ///   has fields skipped when empty or is otherwise not supported by bincode.
+    #[cfg(feature = "bincode")]
+    pub fn serialize_bin<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), ExportError> {
+        crate::measurements::write_bin(self, path.as_ref())
+    }
+}
+
+impl<I: Input + DeserializeOwned> InputSet<I> {
+    /// Loads an input set from a binary file written by [`InputSet::serialize_bin`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ImportError::Io`] - If the file can't be read.
+    /// * [`ImportError::Format`] - If the file doesn't contain inputs of this type, or it is
+    ///   truncated.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chrono_probe::input::generators::vec::U32Vec;
+    /// use chrono_probe::input::InputSet;
+    ///
+    /// let inputs: InputSet<U32Vec> = InputSet::deserialize_bin("inputs/vectors.bin").unwrap();
+    /// ```
+    #[cfg(feature = "bincode")]
+    pub fn deserialize_bin<P: AsRef<std::path::Path>>(path: P) -> Result<InputSet<I>, ImportError> {
+        crate::measurements::read_bin(path.as_ref())
     }
 }
@@ -0,0 +1,63 @@
+//! Ready-made [`InputBuilder`]s for the most common experiments.
+//!
+//! Each function of this module returns an [`InputBuilder`] generating input sizes uniformly in
+//! the given range, using one of the input types of the [`generators`](crate::input::generators)
+//! module. They fail with a [`DistributionError`] if the range is empty. The returned builders
+//! can be further configured as usual (e.g. with [`InputBuilder::with_seed`]).
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::presets;
+//! use chrono_probe::measurements::measure_mut;
+//!
+//! let inputs = presets::random_u32_vecs(10..=1000).unwrap().build(10);
+//!
+//! fn sort(vec: &mut chrono_probe::input::generators::vec::U32Vec) {
+//!     vec.sort();
+//! }
+//!
+//! let results = measure_mut(&inputs, &[(sort, "sort")], 0.01);
+//! ```
+
+use std::ops::RangeInclusive;
+
+use crate::input::distribution::{DistributionError, Uniform};
+use crate::input::generators::string::AsciiString;
+use crate::input::generators::vec::{U32Vec, VecOrder};
+use crate::input::InputBuilder;
+
+/// Returns an [`InputBuilder`] generating vectors of random `u32`.
+///
+/// # Arguments
+///
+/// * `range` - The range of the sizes of the vectors.
+pub fn random_u32_vecs(
+    range: RangeInclusive<usize>,
+) -> Result<InputBuilder<'static, U32Vec, Uniform>, DistributionError> {
+    Ok(InputBuilder::new(Uniform::new(range)?, VecOrder::Random))
+}
+
+/// Returns an [`InputBuilder`] generating sorted vectors of random `u32`.
+///
+/// # Arguments
+///
+/// * `range` - The range of the sizes of the vectors.
+pub fn sorted_vecs(
+    range: RangeInclusive<usize>,
+) -> Result<InputBuilder<'static, U32Vec, Uniform>, DistributionError> {
+    Ok(InputBuilder::new(Uniform::new(range)?, VecOrder::Sorted))
+}
+
+/// Returns an [`InputBuilder`] generating random strings over the given alphabet.
+///
+/// # Arguments
+///
+/// * `range` - The range of the lengths of the strings.
+/// * `alphabet` - The ASCII characters the strings are made of.
+pub fn random_ascii_strings(
+    range: RangeInclusive<usize>,
+    alphabet: &str,
+) -> Result<InputBuilder<'_, AsciiString, Uniform>, DistributionError> {
+    Ok(InputBuilder::new(Uniform::new(range)?, alphabet))
+}
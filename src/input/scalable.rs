@@ -0,0 +1,104 @@
+//! Inputs that can be rendered at any size.
+//!
+//! When inputs are generated independently for every size, part of the variation of the
+//! measured times comes from the inputs themselves rather than from their size. This is
+//! particularly visible when few repetitions are used.
+//!
+//! The [`ScalableInput`] trait solves this problem: a logical instance (e.g. a seed, or a
+//! position in a text corpus) is created once, and then rendered at every size. The inputs
+//! built with [`InputBuilder::build_scalable`] are therefore the same logical inputs stretched
+//! to different sizes.
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::geometry::{PointSet, PointSetGenerator};
+//! use chrono_probe::input::InputBuilder;
+//!
+//! let builder: InputBuilder<PointSet, _> =
+//!     InputBuilder::new(Uniform::new(10..=1000).unwrap(), PointSetGenerator::default());
+//!
+//! // 3 logical point sets, each rendered at 20 different sizes
+//! let inputs = builder.build_scalable(20, 3);
+//! ```
+
+use std::time::Instant;
+
+use rand::Rng;
+
+use crate::input::distribution::Distribution;
+use crate::input::{GenerationTime, Input, InputBuilder, InputSet};
+
+/// Trait that can be implemented by input types whose instances can be rendered at any size.
+pub trait ScalableInput: Input {
+    /// The type of the logical instance, independent of the size.
+    type Instance;
+    /// Creates a new logical instance, using the given builder.
+    fn new_instance(builder: &Self::Builder<'_>) -> Self::Instance;
+    /// Creates a new logical instance, using the given builder and random number generator.
+    ///
+    /// This method is used when a seed is set on the [`InputBuilder`]. By default, it ignores the
+    /// random number generator and calls [`ScalableInput::new_instance`].
+    fn new_instance_with<R: Rng>(builder: &Self::Builder<'_>, rng: &mut R) -> Self::Instance {
+        let _ = rng;
+        Self::new_instance(builder)
+    }
+    /// Renders the given logical instance at the given size, using the given builder.
+    fn render(instance: &Self::Instance, size: usize, builder: &Self::Builder<'_>) -> Self;
+}
+
+impl<I: ScalableInput, D: Distribution> InputBuilder<'_, I, D> {
+    /// Generates the inputs rendering the same logical instances at every size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of input sizes to be generated.
+    /// * `instances` - The number of logical instances, i.e. the number of inputs for each size.
+    pub fn build_scalable(&self, n: usize, instances: usize) -> InputSet<I> {
+        assert!(
+            n > 0,
+            "The number of inputs to be generated must be greater than 0"
+        );
+        assert!(
+            instances > 0,
+            "The number of instances must be greater than 0"
+        );
+
+        // Generate the input lengths using the given distribution
+        let mut rng = self.seeded_rng();
+        let sizes = self.generate_sizes(n, rng.as_mut());
+
+        // Check that the inputs fit in the memory limit before allocating them
+        self.check_memory_limit(&sizes, &|_| instances);
+
+        // Create the logical instances once
+        let logical_instances: Vec<I::Instance> = (0..instances)
+            .map(|_| match &mut rng {
+                Some(rng) => I::new_instance_with(&self.builder, rng),
+                None => I::new_instance(&self.builder),
+            })
+            .collect();
+
+        // Render every instance at every size
+        let mut generation_time = GenerationTime::default();
+        let inputs = sizes
+            .into_iter()
+            .map(|size| {
+                let start = Instant::now();
+                let group = logical_instances
+                    .iter()
+                    .map(|instance| I::render(instance, size, &self.builder))
+                    .collect();
+                generation_time.push(size, start.elapsed());
+                group
+            })
+            .collect();
+
+        InputSet {
+            inputs,
+            seed: self.seed,
+            generation_time: Some(generation_time),
+        }
+    }
+}
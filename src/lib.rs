@@ -69,7 +69,7 @@
 //! ```ignore
 //! impl Input for InputVec {
 //!    // We don't need a Builder.
-//!     type Builder = ();
+//!     type Builder<'a> = ();
 //!
 //!     // Return the size of the input, in this case the size is the length of the vector.
 //!     fn get_size(&self) -> usize {
@@ -77,7 +77,7 @@
 //!     }
 //!
 //!     // Generate a vector of the given size and fill it with random numbers.
-//!     fn generate_input(size: usize, _builder: &Self::Builder) -> Self {
+//!     fn generate_input(size: usize, _builder: &Self::Builder<'_>) -> Self {
 //!         let mut rng = thread_rng();
 //!         let mut v = Vec::with_capacity(size);
 //!         for _ in 0..size {
@@ -142,15 +142,27 @@
 //!     .with_title("Sorting algorithms")
 //!     .with_caption("The time plot of sorting algorithms");
 //!
-//! time_plot(file_name, results, &config);
+//! time_plot(file_name, results, &config).unwrap();
 //! ```
 //!
 //! The entire code and other examples can be found in the [examples](https://github.com/ADS-laboratory/chrono-probe/tree/lib/examples) folder.
 
 #![warn(clippy::all)]
 #![warn(clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
 #![warn(missing_docs)]
 
+pub mod adaptive;
+pub mod analysis;
+pub mod environment;
 pub mod input;
 pub mod measurements;
+pub mod memory;
 pub mod plot;
+pub mod progress;
+pub mod report;
+pub mod repro;
+pub mod runs;
+pub mod sink;
+pub mod sweep;
+mod util;
@@ -7,6 +7,11 @@
 //! * `measure`
 //! * `measure_mut`
 //!
+//! and their variants [`measure_with_progress`] and [`measure_mut_with_progress`], which report the
+//! progress of the measurements to a [`ProgressHandler`](crate::progress::ProgressHandler).
+//! If the inputs can't be cloned, [`measure_mut_with_reset`] restores them with a user supplied
+//! function instead.
+//!
 //! Those functions take as input:
 //! * A reference to an [`InputSet`](crate::input::InputSet), which contains the inputs to test the algorithm on.
 //! * A relative error threshold.
@@ -32,28 +37,146 @@
 //! each algorithm on each input. Useful methods are provided like [`Measurements::serialize_json`]
 //! to save the measurements to a file or [`Measurements::log_log_scale`] to scale the measurements
 //! to a log-log scale.
+//! The machine and the build on which the measurements were taken are recorded too, see
+//! [`Environment`], and the configuration of the inputs can be attached with
+//! [`Measurements::with_input_builder`].
+//!
+//! Measurements taken by other tools can be loaded too, to analyze and plot them with the rest of
+//! the crate: [`Measurements::from_criterion_dir`] imports the results of the benchmarks of
+//! [criterion](https://docs.rs/criterion).
+//!
+//! If the `parquet` feature is enabled, the points and the samples of the measurements can also
+//! be exported to Parquet files with `Measurements::serialize_parquet` and
+//! `Measurements::serialize_samples_parquet`, while if the `bincode` feature is enabled they can
+//! be saved to and loaded from compact binary files with `Measurements::serialize_bin` and
+//! `Measurements::deserialize_bin`, much smaller and faster than JSON.
+//! The `gzip` and `zstd` features compress the JSON files instead, with
+//! `Measurements::serialize_json_gz` and `Measurements::serialize_json_zst`, while
+//! [`Measurements::from_json`] detects the compression of the files it loads.
+//! A summary of the measurements can be exposed to monitoring tools with
+//! [`Measurements::to_openmetrics`], and the measurements can be read by the tools of
+//! [Google Benchmark](https://github.com/google/benchmark) after writing them in its JSON format
+//! with [`Measurements::serialize_google_benchmark`]. For the analysis in Python, they can be
+//! exported as NumPy arrays with [`Measurements::serialize_npz`].
+//! In a Jupyter notebook running the [evcxr](https://github.com/evcxr/evcxr) kernel, the `evcxr`
+//! feature shows the measurements of a cell as a plot and a table, with
+//! `Measurements::evcxr_display`.
+//!
+//! The quickest way to look at the results is [`Measurements::print_table`], printing a table
+//! comparing the times of the algorithms on some of the sizes, configured by a [`TableConfig`].
+//!
+//! ## JSON format
+//!
+//! In JSON, the [`Measurements`] are an object with the fields of the struct, where all the
+//! durations (the times, their standard deviations and samples, the timestamps, the resolution
+//! of the clock and the generation times) are integer numbers of nanoseconds. The fields of the
+//! [`Point`]s which are unknown are `null`, e.g.:
+//!
+//! ```json
+//! {
+//!   "measurements": [
+//!     {
+//!       "algorithm_name": "merge sort",
+//!       "measurement": [
+//!         {
+//!           "size": 1000,
+//!           "mean_time": 2500,
+//!           "bytes": 4000,
+//!           "stddev": 100,
+//!           "samples": [2400, 2600],
+//!           "timestamp": 51000,
+//!           "allocations": null,
+//!           "peak_memory": null
+//!         }
+//!       ]
+//!     }
+//!   ],
+//!   "relative_error": 0.001,
+//!   "resolution": 20,
+//!   "seed": 42,
+//!   "generation_time": { "total": 80000, "per_size": [[1000, 80000]] },
+//!   "environment": null,
+//!   "input_builder": null
+//! }
+//! ```
+//!
+//! The files written by the previous versions, where the durations are objects like
+//! `{"secs": 0, "nanos": 2500}`, can still be loaded. The binary format of the `bincode` feature
+//! keeps the default encoding of the durations instead.
 //!
 //! Examples of the use of these two function can be found in the [examples](https://github.com/ADS-laboratory/chrono-probe/tree/lib/examples) folder.
 
-use std::fs::File;
+mod compression;
+mod google_benchmark;
+pub(crate) mod nanos;
+mod numpy;
+mod openmetrics;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod table;
+
+pub use table::TableConfig;
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
-use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::input::{Input, InputSet};
+use crate::environment::Environment;
+use crate::input::{GenerationTime, Input, InputSet};
+use crate::memory::{self, MemoryUsage};
+use crate::progress::{DefaultProgress, Progress, ProgressHandler, RunInfo, Stage};
 
 /// A point containing the size of the input and the time it took to process it
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Point {
     /// The size of the input
     pub size: usize,
-    /// The time it took to process the input
+    /// The time it took to process the input (the mean time, if multiple inputs with the same
+    /// size were measured)
+    ///
+    /// **Breaking change:** up to version 0.1.0 this was the total time of all the inputs with
+    /// the same size. The total is now `time * samples.len()`. The mean is serialized as
+    /// `mean_time`, so that the files containing the totals in `time` are rejected when loaded
+    /// instead of being misread, since the number of inputs of their sizes is unknown.
+    #[serde(rename = "mean_time", with = "nanos")]
     pub time: Duration,
+    /// The size of the input in bytes (the mean size, if multiple inputs with the same size were
+    /// measured), if reported by [`Input::get_size_bytes`]
+    pub bytes: Option<usize>,
+    /// The standard deviation of the times it took to process the inputs with this size, if more
+    /// than one was measured
+    #[serde(default, with = "nanos::option")]
+    pub stddev: Option<Duration>,
+    /// The times it took to process each input with this size
+    #[serde(with = "nanos::vec")]
+    pub samples: Vec<Duration>,
+    /// When the measurement of the inputs with this size started, relative to the start of the
+    /// run, if known. Plotting the residuals of a fit against it shows whether the machine got
+    /// slower during the run, e.g. because of thermal throttling or of other processes.
+    #[serde(default, with = "nanos::option")]
+    pub timestamp: Option<Duration>,
+    /// The number of allocations made by a run of the algorithm (the mean number, if multiple
+    /// inputs with the same size were measured), if counted by the
+    /// [`CountingAllocator`](memory::CountingAllocator)
+    #[serde(default)]
+    pub allocations: Option<u64>,
+    /// The peak of the memory allocated by a run of the algorithm, in bytes (the mean peak, if
+    /// multiple inputs with the same size were measured), if counted by the
+    /// [`CountingAllocator`](memory::CountingAllocator)
+    #[serde(default)]
+    pub peak_memory: Option<usize>,
 }
 
 /// A measurement of an algorithm.
 /// Contains all the times it took the algorithm to process a set of inputs
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Measurement {
     /// The name of the algorithm
     pub algorithm_name: String,
@@ -62,14 +185,303 @@ pub struct Measurement {
 }
 
 /// A set of measurements for some algorithms.
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Measurements {
     /// Vector of measurements
     pub measurements: Vec<Measurement>,
     /// The relative error of the measurements
     pub relative_error: f32,
     /// The resolution of the clock
+    #[serde(with = "nanos")]
     pub resolution: Duration,
+    /// The seed used to generate the inputs, if any
+    pub seed: Option<u64>,
+    /// The time spent generating the inputs, if known
+    pub generation_time: Option<GenerationTime>,
+    /// The machine and the build on which the measurements were taken, if known
+    pub environment: Option<Environment>,
+    /// The configuration of the generation of the inputs, if attached with
+    /// [`Measurements::with_input_builder`]
+    #[serde(default, with = "json_value")]
+    pub input_builder: Option<Value>,
+}
+
+/// Serializes an optional JSON value as it is in the human readable formats, and as its JSON
+/// text in the binary ones, which can't describe its structure.
+mod json_value {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Option<Value>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            value.serialize(serializer)
+        } else {
+            value.as_ref().map(Value::to_string).serialize(serializer)
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Value>, D::Error> {
+        if deserializer.is_human_readable() {
+            Option::<Value>::deserialize(deserializer)
+        } else {
+            Option::<String>::deserialize(deserializer)?
+                .map(|text| serde_json::from_str(&text).map_err(D::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+/// An error that occurred while loading measurements or inputs from files, for example the
+/// measurements taken by other tools.
+#[derive(Debug)]
+pub enum ImportError {
+    /// A file or a directory could not be read
+    Io {
+        /// The path of the file or of the directory
+        path: PathBuf,
+        /// The error of the operation
+        source: io::Error,
+    },
+    /// A file could not be parsed
+    Format {
+        /// The path of the file
+        path: PathBuf,
+        /// The description of the error
+        reason: String,
+    },
+    /// No measurement could be imported from a directory
+    Empty {
+        /// The path of the directory
+        path: PathBuf,
+    },
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Io { path, source } => {
+                write!(f, "Unable to read '{}': {}", path.display(), source)
+            }
+            ImportError::Format { path, reason } => {
+                write!(f, "Unable to parse '{}': {}", path.display(), reason)
+            }
+            ImportError::Empty { path } => {
+                write!(f, "No measurement found in '{}'", path.display())
+            }
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::Io { source, .. } => Some(source),
+            ImportError::Format { .. } | ImportError::Empty { .. } => None,
+        }
+    }
+}
+
+/// An error that occurred while exporting measurements or inputs to a file.
+#[derive(Debug)]
+pub enum ExportError {
+    /// A file or a directory could not be created or written
+    Io {
+        /// The path of the file or of the directory
+        path: PathBuf,
+        /// The error of the operation
+        source: io::Error,
+    },
+    /// The measurements could not be encoded in the format of a file
+    Format {
+        /// The path of the file
+        path: PathBuf,
+        /// The description of the error
+        reason: String,
+    },
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Io { path, source } => {
+                write!(f, "Unable to write '{}': {}", path.display(), source)
+            }
+            ExportError::Format { path, reason } => {
+                write!(f, "Unable to encode '{}': {}", path.display(), reason)
+            }
+        }
+    }
+}
+
+impl Error for ExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExportError::Io { source, .. } => Some(source),
+            ExportError::Format { .. } => None,
+        }
+    }
+}
+
+/// An error that occurred while rendering measurements in the OpenMetrics text format, see
+/// [`Measurements::to_openmetrics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenMetricsError {
+    /// The name of a label doesn't match `[a-zA-Z_][a-zA-Z0-9_]*`
+    InvalidLabel {
+        /// The name of the label
+        name: String,
+    },
+    /// The name of a label is `algorithm` or `size`, which are set for each sample
+    ReservedLabel {
+        /// The name of the label
+        name: String,
+    },
+}
+
+impl Display for OpenMetricsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpenMetricsError::InvalidLabel { name } => write!(
+                f,
+                "Invalid label name '{}': it must match [a-zA-Z_][a-zA-Z0-9_]*",
+                name
+            ),
+            OpenMetricsError::ReservedLabel { name } => {
+                write!(f, "The label name '{}' is reserved", name)
+            }
+        }
+    }
+}
+
+impl Error for OpenMetricsError {}
+
+/// A line of a JSON Lines file written by a [`JsonLinesSink`](crate::sink::JsonLinesSink).
+#[derive(Deserialize)]
+struct PointLine {
+    algorithm: String,
+    #[serde(flatten)]
+    point: Point,
+}
+
+/// Writes the given value to a binary file, encoded with bincode.
+#[cfg(feature = "bincode")]
+pub(crate) fn write_bin<T: Serialize>(value: &T, path: &Path) -> Result<(), ExportError> {
+    let file = File::create(path).map_err(|source| ExportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut writer = io::BufWriter::new(file);
+    bincode::serialize_into(&mut writer, value).map_err(|error| match *error {
+        bincode::ErrorKind::Io(source) => ExportError::Io {
+            path: path.to_path_buf(),
+            source,
+        },
+        error => ExportError::Format {
+            path: path.to_path_buf(),
+            reason: error.to_string(),
+        },
+    })?;
+    io::Write::flush(&mut writer).map_err(|source| ExportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Reads a value from a binary file written by [`write_bin`].
+#[cfg(feature = "bincode")]
+pub(crate) fn read_bin<T: DeserializeOwned>(path: &Path) -> Result<T, ImportError> {
+    let file = File::open(path).map_err(|source| ImportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    bincode::deserialize_from(io::BufReader::new(file)).map_err(|error| match *error {
+        // A truncated file, or a file of another type, ends before the value is read
+        bincode::ErrorKind::Io(source) if source.kind() == io::ErrorKind::UnexpectedEof => {
+            ImportError::Format {
+                path: path.to_path_buf(),
+                reason: "unexpected end of the file".to_string(),
+            }
+        }
+        bincode::ErrorKind::Io(source) => ImportError::Io {
+            path: path.to_path_buf(),
+            source,
+        },
+        error => ImportError::Format {
+            path: path.to_path_buf(),
+            reason: error.to_string(),
+        },
+    })
+}
+
+/// The identifier of a benchmark, in the `benchmark.json` file written by criterion.
+#[derive(Deserialize)]
+struct CriterionBenchmark {
+    group_id: String,
+    function_id: Option<String>,
+    value_str: Option<String>,
+}
+
+/// The statistics of a benchmark, in the `estimates.json` file written by criterion.
+#[derive(Deserialize)]
+struct CriterionEstimates {
+    mean: CriterionEstimate,
+    std_dev: CriterionEstimate,
+}
+
+/// A statistic estimated by criterion, in nanoseconds.
+#[derive(Deserialize)]
+struct CriterionEstimate {
+    point_estimate: f64,
+    standard_error: f64,
+}
+
+/// The samples of a benchmark, in the `sample.json` file written by criterion: each sample is
+/// the total time, in nanoseconds, of the given number of iterations.
+#[derive(Deserialize)]
+struct CriterionSample {
+    iters: Vec<f64>,
+    times: Vec<f64>,
+}
+
+/// Reads and parses the given JSON file.
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, ImportError> {
+    let contents = compression::read(path)?;
+    serde_json::from_slice(&contents).map_err(|error| ImportError::Format {
+        path: path.to_path_buf(),
+        reason: error.to_string(),
+    })
+}
+
+/// Collects the `new` directories containing the latest results of criterion under the given
+/// directory, in the order of their paths.
+fn criterion_results(dir: &Path, results: &mut Vec<PathBuf>) -> Result<(), ImportError> {
+    let io_error = |source| ImportError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_dir() {
+            entries.push(path);
+        }
+    }
+    entries.sort();
+    for path in entries {
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some("new") if path.join("benchmark.json").is_file() => results.push(path),
+            // The previous results, the changes between them and the HTML reports
+            Some("base" | "change" | "report") => {}
+            _ => criterion_results(&path, results)?,
+        }
+    }
+    Ok(())
 }
 
 /// Estimates the resolution of the clock
@@ -93,6 +505,66 @@ fn get_average_resolution() -> Duration {
     sum / 100
 }
 
+/// Estimates the resolution of the clock used to measure the algorithms.
+pub fn clock_resolution() -> Duration {
+    get_average_resolution()
+}
+
+/// Returns the smallest input size worth measuring, i.e. the smallest size on which a single run
+/// of the algorithm is long enough to be measured with the given relative error. On smaller sizes
+/// the algorithm must be run many times in a loop, and the measurements are dominated by the
+/// resolution of the clock and by the overhead of the loop.
+///
+/// The size is extrapolated from a pilot timing of the algorithm on a small input, assuming that
+/// the time grows linearly with the size, so the estimate is exact only for the linear
+/// algorithms. When the result is larger than the pilot size, it is too large for the algorithms
+/// growing faster than linearly and too small for the ones growing slower, and the other way
+/// around when it is smaller: a pilot size close to the result keeps the error small. The result
+/// can be used to drop the sizes below it with [`MinSize`](crate::input::distribution::MinSize).
+///
+/// # Arguments
+///
+/// * `resolution` - The resolution of the clock, see [`clock_resolution`]
+/// * `pilot_size` - The size of the pilot input
+/// * `pilot_time` - The time a single run of the algorithm took on the pilot input
+/// * `relative_error` - The required relative error of the measurements
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use chrono_probe::input::distribution::{Distribution, MinSize, Uniform};
+/// use chrono_probe::measurements::min_measurable_size;
+///
+/// // A run on an input of size 1000 takes 10µs, and the clock has a resolution of 100ns
+/// let min_size = min_measurable_size(
+///     Duration::from_nanos(100),
+///     1000,
+///     Duration::from_micros(10),
+///     0.01,
+/// );
+/// assert_eq!(min_size, 1010);
+///
+/// let distribution = MinSize::new(Uniform::new(0..=10_000).unwrap(), min_size);
+/// assert_eq!(distribution.generate(11).len(), 9);
+/// ```
+pub fn min_measurable_size(
+    resolution: Duration,
+    pilot_size: usize,
+    pilot_time: Duration,
+    relative_error: f32,
+) -> usize {
+    assert!(pilot_size > 0, "The pilot size must be greater than 0");
+    assert!(
+        pilot_time > Duration::ZERO,
+        "The pilot time must be greater than 0"
+    );
+    let min_time_measurable = resolution * ((1.0 / relative_error) + 1.0) as u32;
+    let size = pilot_size as f64 * min_time_measurable.as_secs_f64() / pilot_time.as_secs_f64();
+    (size.ceil() as usize).max(1)
+}
+
 /// Estimates the time it takes to run a function given a single input
 ///
 /// # Arguments
@@ -101,7 +573,7 @@ fn get_average_resolution() -> Duration {
 /// * `string` - The string to pass to the function
 /// * `relative_error` - The required relative error of the measurement
 /// * `resolution` - The resolution of the clock
-fn get_time<I, O, Alg>(f: Alg, input: &I, relative_error: f32, resolution: Duration) -> Duration
+fn get_time<I, O, Alg>(f: Alg, input: &I, relative_error: f32, resolution: Duration) -> Sample
 where
     I: Input,
     Alg: Fn(&I) -> O,
@@ -125,7 +597,7 @@ where
             break;
         }
     }
-    end / n
+    (end / n, memory::track(|| (f)(input)))
 }
 
 /// Estimates the time it takes to run a function given a single mutable input
@@ -136,7 +608,7 @@ where
 /// * `string` - The string to pass to the function
 /// * `relative_error` - The required relative error of the measurement
 /// * `resolution` - The resolution of the clock
-fn get_time_mut<I, O, Alg>(f: Alg, input: &I, relative_error: f32, resolution: Duration) -> Duration
+fn get_time_mut<I, O, Alg>(f: Alg, input: &I, relative_error: f32, resolution: Duration) -> Sample
 where
     I: Input + Clone,
     Alg: Fn(&mut I) -> O,
@@ -167,143 +639,238 @@ where
             break;
         }
     }
-    end / n
+    // Clone the input for the run counting the memory only if it's counted
+    let usage = memory::is_installed()
+        .then(|| {
+            let input_cloned = &mut input.clone();
+            memory::track(|| (f)(input_cloned))
+        })
+        .flatten();
+    (end / n, usage)
 }
 
-/// Estimates the time it takes to run a function given a vector of inputs of the same length.
-/// Return a Point with the length of the strings and the total time it took to run the function on all the strings.
+/// Estimates the time it takes to run a function given a single mutable input, restoring the
+/// input with the `reset` function before each run instead of cloning it.
 ///
 /// # Arguments
 ///
 /// * `f` - The function to measure
-/// * `strings` - The vector of strings to pass to the function
+/// * `reset` - The function restoring the input before each run
+/// * `input` - The input to pass to the function
 /// * `relative_error` - The required relative error of the measurement
 /// * `resolution` - The resolution of the clock
-fn get_time_same_length<I, O, Alg>(
-    f: &Alg,
-    inputs: &Vec<I>,
+fn get_time_reset<I, O, Alg, R>(
+    f: Alg,
+    reset: R,
+    input: &mut I,
     relative_error: f32,
     resolution: Duration,
-) -> Point
+) -> Sample
 where
     I: Input,
-    Alg: Fn(&I) -> O,
+    Alg: Fn(&mut I) -> O,
+    R: Fn(&mut I),
 {
-    let mut total_time = Duration::ZERO;
-    let size = inputs[0].get_size();
-    for input in inputs {
-        let time = get_time(f, input, relative_error, resolution);
-        total_time += time;
-    }
-    Point {
-        size,
-        time: total_time,
+    let mut n = 0;
+    // The minimum time measurable
+    let min_time_measurable = resolution * ((1.0 / relative_error) + 1.0) as u32;
+    let mut end: Duration;
+    let mut start = Instant::now();
+    loop {
+        // Measure the time it takes to reset the input
+        let start_input_reset = Instant::now();
+        (reset)(input);
+        n += 1;
+        let end_input_reset = start_input_reset.elapsed();
+
+        // Run the function
+        (f)(input);
+
+        // Remove the time it takes to reset the input
+        start += end_input_reset;
+
+        // Measure the time it takes to run the function
+        end = start.elapsed();
+
+        // Exit the loop if the time it takes to run the function is greater than the minimum time measurable
+        if end > min_time_measurable {
+            break;
+        }
     }
+    // Reset the input for the run counting the memory only if it's counted
+    let usage = memory::is_installed()
+        .then(|| {
+            (reset)(input);
+            memory::track(|| (f)(input))
+        })
+        .flatten();
+    (end / n, usage)
 }
 
-/// Estimates the time it takes to run a function given a mutable vector of inputs of the same length.
-/// Return a Point with the length of the strings and the total time it took to run the function on all the strings.
-///
-/// # Arguments
-///
-/// * `f` - The function to measure
-/// * `strings` - The vector of strings to pass to the function
-/// * `relative_error` - The required relative error of the measurement
-/// * `resolution` - The resolution of the clock
-fn get_time_same_length_mut<I, O, Alg>(
-    f: &Alg,
-    inputs: &Vec<I>,
-    relative_error: f32,
-    resolution: Duration,
-) -> Point
-where
-    I: Input + Clone,
-    Alg: Fn(&mut I) -> O,
-{
-    let mut total_time = Duration::ZERO;
-    let size = inputs[0].get_size();
-    for input in inputs {
-        let time = get_time_mut(f, input, relative_error, resolution);
-        total_time += time;
-    }
-    Point {
-        size,
-        time: total_time,
+/// The time it takes to run a function on an input and, if counted, the memory used by a run.
+type Sample = (Duration, Option<MemoryUsage>);
+
+/// A group of inputs with the same size.
+struct Group {
+    /// The size of the inputs
+    size: usize,
+    /// The mean size in bytes of the inputs, if known for all of them
+    bytes: Option<usize>,
+    /// The number of inputs
+    count: usize,
+}
+
+/// Returns the size, the size in bytes and the number of inputs of each group of inputs with the
+/// same size.
+fn get_groups<I: Input>(inputs: &InputSet<I>) -> Vec<Group> {
+    inputs
+        .inputs
+        .iter()
+        .map(|group| Group {
+            size: group[0].get_size(),
+            bytes: group
+                .iter()
+                .map(Input::get_size_bytes)
+                .sum::<Option<usize>>()
+                .map(|bytes| bytes / group.len()),
+            count: group.len(),
+        })
+        .collect()
+}
+
+/// Returns the sample standard deviation of the given times with the given mean, if there are at
+/// least two of them.
+fn standard_deviation(samples: &[Duration], mean: Duration) -> Option<Duration> {
+    if samples.len() < 2 {
+        return None;
     }
+    let mean = mean.as_secs_f64();
+    let variance = samples
+        .iter()
+        .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+        .sum::<f64>()
+        / (samples.len() - 1) as f64;
+    Some(Duration::from_secs_f64(variance.sqrt()))
 }
 
-/// Estimates the times it takes to run a function given a vector of inputs
+/// Estimates the times it takes to run a function given the groups of inputs with the same size.
+/// For each group, a Point with the size of the inputs and the mean time it took to run the
+/// function on them is returned. Using the mean, groups with a different number of inputs can be
+/// compared.
 ///
 /// # Arguments
 ///
-/// * `f` - The function to measure
-/// * `strings` - The vector of strings to pass to the function
-/// * `relative_error` - The required relative error of the measurement
-/// * `resolution` - The resolution of the clock
-fn get_times<I, O, Alg>(
-    f: &Alg,
+/// * `f_name` - The name of the function to measure
+/// * `groups` - The size, the size in bytes and the number of inputs of each group
+/// * `time` - Estimates the time it takes to run the function on the j-th input of the i-th group,
+///   and the memory used by a run
+/// * `progress` - The handler receiving the progress reports
+/// * `run_start` - The start of the run, to which the timestamps of the points are relative
+fn get_times<T, P>(
     f_name: &str,
-    inputs: &InputSet<I>,
-    relative_error: f32,
-    resolution: Duration,
+    groups: &[Group],
+    mut time: T,
+    progress: &mut P,
+    run_start: Instant,
 ) -> Measurement
 where
-    I: Input,
-    Alg: Fn(&I) -> O,
+    T: FnMut(usize, usize) -> Sample,
+    P: ProgressHandler,
 {
-    let n = inputs.inputs.len();
+    let n = groups.len();
     let mut times = Vec::with_capacity(n);
-    for (_i, input) in inputs.inputs.iter().enumerate() {
-        let time = get_time_same_length(f, input, relative_error, resolution);
-        times.push(time);
-        #[cfg(feature = "debug")]
-        {
-            if _i % (n / 20) == 0 {
-                println!("{}%", _i * 100 / n);
-            }
-        }
+    let start = Instant::now();
+    for (i, group) in groups.iter().enumerate() {
+        let timestamp = run_start.elapsed();
+        let (samples, usages): (Vec<Duration>, Vec<Option<MemoryUsage>>) =
+            (0..group.count).map(|j| time(i, j)).unzip();
+        let mean = samples.iter().sum::<Duration>() / group.count as u32;
+        let usages: Option<Vec<MemoryUsage>> = usages.into_iter().collect();
+        times.push(Point {
+            size: group.size,
+            time: mean,
+            bytes: group.bytes,
+            stddev: standard_deviation(&samples, mean),
+            samples,
+            timestamp: Some(timestamp),
+            allocations: usages.as_ref().map(|usages| {
+                usages.iter().map(|usage| usage.allocations).sum::<u64>() / group.count as u64
+            }),
+            peak_memory: usages.as_ref().map(|usages| {
+                usages.iter().map(|usage| usage.peak_bytes).sum::<usize>() / group.count
+            }),
+        });
+        progress.on_progress(&Progress {
+            stage: Stage::Measurement,
+            label: Some(f_name),
+            completed: times.len(),
+            total: n,
+            bytes: None,
+            elapsed: start.elapsed(),
+            point: times.last(),
+        });
     }
     Measurement {
-        algorithm_name: f_name.to_owned(), //get_algorithm_name(f),
+        algorithm_name: f_name.to_owned(),
         measurement: times,
     }
 }
 
-/// Estimates the times it takes to run a function given a mutable vector of inputs
-///
-/// # Arguments
-///
-/// * `f` - The function to measure
-/// * `strings` - The vector of strings to pass to the function
-/// * `relative_error` - The required relative error of the measurement
-/// * `resolution` - The resolution of the clock
-fn get_times_mut<I, O, Alg>(
-    f: &Alg,
-    f_name: &str,
+/// Returns the [`Measurements`] of a new run on the given inputs, still without any measurement.
+fn new_run<I: Input>(
     inputs: &InputSet<I>,
     relative_error: f32,
     resolution: Duration,
-) -> Measurement
+) -> Measurements {
+    Measurements {
+        measurements: Vec::new(),
+        relative_error,
+        resolution,
+        seed: inputs.seed,
+        generation_time: inputs.generation_time.clone(),
+        environment: Some(Environment::capture()),
+        input_builder: None,
+    }
+}
+
+/// Measures the given algorithms on the groups of inputs, adding their measurements to the given
+/// run and telling the handler when the run starts and ends.
+///
+/// # Arguments
+///
+/// * `run` - The measurements of the run, see [`new_run`]
+/// * `algorithms` - The algorithms to measure, with their names
+/// * `groups` - The groups of inputs with the same size
+/// * `progress` - The handler receiving the progress reports
+/// * `time` - The function measuring an algorithm on the j-th input of the i-th group
+fn measure_run<A, T, P>(
+    mut run: Measurements,
+    algorithms: &[(A, &str)],
+    groups: &[Group],
+    progress: &mut P,
+    mut time: T,
+) -> Measurements
 where
-    I: Input + Clone,
-    Alg: Fn(&mut I) -> O,
+    T: FnMut(&A, usize, usize) -> Sample,
+    P: ProgressHandler,
 {
-    let n = inputs.inputs.len();
-    let mut times = Vec::with_capacity(n);
-    for (_i, input) in inputs.inputs.iter().enumerate() {
-        let time = get_time_same_length_mut(f, input, relative_error, resolution);
-        times.push(time);
-        #[cfg(feature = "debug")]
-        {
-            if _i % (n / 20) == 0 {
-                println!("{}%", _i * 100 / n);
-            }
-        }
-    }
-    Measurement {
-        algorithm_name: f_name.to_owned(), //get_algorithm_name_mut(f),
-        measurement: times,
+    let names: Vec<&str> = algorithms.iter().map(|(_, name)| *name).collect();
+    let sizes: Vec<usize> = groups.iter().map(|group| group.size).collect();
+    progress.on_run_start(&RunInfo {
+        algorithms: &names,
+        sizes: &sizes,
+        relative_error: run.relative_error,
+        resolution: run.resolution,
+        seed: run.seed,
+        environment: run.environment.get_or_insert_with(Environment::capture),
+    });
+    let start = Instant::now();
+    for (algorithm, name) in algorithms {
+        let measurement = get_times(name, groups, |i, j| time(algorithm, i, j), progress, start);
+        run.measurements.push(measurement);
     }
+    progress.on_run_end(&run);
+    run
 }
 
 /// Measures the time it takes to run different functions given an [`InputSet`].
@@ -322,32 +889,43 @@ pub fn measure<I, O, Alg>(
 where
     I: Input,
     Alg: Fn(&I) -> O,
+{
+    measure_with_progress(
+        inputs,
+        algorithms,
+        relative_error,
+        &mut DefaultProgress::default(),
+    )
+}
+
+/// Measures the time it takes to run different functions given a [`InputSet`], reporting
+/// the progress of the measurement of each algorithm to the given [`ProgressHandler`].
+///
+/// # Arguments
+///
+/// * `strings` - The [`InputSet`] to pass to the functions
+/// * `algorithms` - The vector of functions to measure
+/// * `relative_error` - The required relative error of the measurements
+/// * `progress` - The handler receiving the progress reports
+///
+pub fn measure_with_progress<I, O, Alg, P>(
+    inputs: &InputSet<I>,
+    algorithms: &[(Alg, &str)],
+    relative_error: f32,
+    progress: &mut P,
+) -> Measurements
+where
+    I: Input,
+    Alg: Fn(&I) -> O,
+    P: ProgressHandler,
 {
     assert!(relative_error > 0.0, "Relative error must be positive");
     let resolution = get_average_resolution();
-    let mut results = Vec::with_capacity(algorithms.len());
-    for (_i, algorithm) in algorithms.iter().enumerate() {
-        #[cfg(feature = "debug")]
-        println!(
-            "\n\nProcessing {} ({}/{})...\n",
-            algorithm.1, // Algorithm name
-            _i + 1,
-            algorithms.len()
-        );
-        let measurement = get_times(
-            &algorithm.0,
-            algorithm.1,
-            inputs,
-            relative_error,
-            resolution,
-        );
-        results.push(measurement);
-    }
-    Measurements {
-        measurements: results,
-        relative_error,
-        resolution,
-    }
+    let run = new_run(inputs, relative_error, resolution);
+    let groups = get_groups(inputs);
+    measure_run(run, algorithms, &groups, progress, |algorithm, i, j| {
+        get_time(algorithm, &inputs.inputs[i][j], relative_error, resolution)
+    })
 }
 
 /// Measures the time it takes to run different functions given a mutable [`InputSet`].
@@ -366,36 +944,131 @@ pub fn measure_mut<I, O, Alg>(
 where
     I: Input + Clone,
     Alg: Fn(&mut I) -> O,
+{
+    measure_mut_with_progress(
+        inputs,
+        algorithms,
+        relative_error,
+        &mut DefaultProgress::default(),
+    )
+}
+
+/// Measures the time it takes to run different functions given a mutable [`InputSet`], reporting
+/// the progress of the measurement of each algorithm to the given [`ProgressHandler`].
+///
+/// # Arguments
+///
+/// * `strings` - The [`InputSet`] to pass to the functions
+/// * `algorithms` - The vector of functions to measure
+/// * `relative_error` - The required relative error of the measurements
+/// * `progress` - The handler receiving the progress reports
+///
+pub fn measure_mut_with_progress<I, O, Alg, P>(
+    inputs: &InputSet<I>,
+    algorithms: &[(Alg, &str)],
+    relative_error: f32,
+    progress: &mut P,
+) -> Measurements
+where
+    I: Input + Clone,
+    Alg: Fn(&mut I) -> O,
+    P: ProgressHandler,
 {
     assert!(relative_error > 0.0, "Relative error must be positive");
     let resolution = get_average_resolution();
-    let mut results = Vec::with_capacity(algorithms.len());
-    for (_i, algorithm) in algorithms.iter().enumerate() {
-        #[cfg(feature = "debug")]
-        println!(
-            "\n\nProcessing {} ({}/{})...\n",
-            algorithm.1, // Algorithm name
-            _i + 1,
-            algorithms.len()
-        );
-        let measurement = get_times_mut(
-            &algorithm.0,
-            algorithm.1,
-            inputs,
-            relative_error,
-            resolution,
-        );
-        results.push(measurement);
-    }
-    Measurements {
-        measurements: results,
-        relative_error,
-        resolution,
+    let run = new_run(inputs, relative_error, resolution);
+    let groups = get_groups(inputs);
+    measure_run(run, algorithms, &groups, progress, |algorithm, i, j| {
+        get_time_mut(algorithm, &inputs.inputs[i][j], relative_error, resolution)
+    })
+}
+
+/// Measures the time it takes to run different functions given a mutable [`InputSet`], without
+/// cloning the inputs.
+///
+/// Unlike [`measure_mut`], this function doesn't require the inputs to implement [`Clone`]: before
+/// each run of an algorithm, the input is restored with the `reset` function, which is executed
+/// outside the timed region. This allows measuring algorithms on inputs containing resources that
+/// can't be cloned, like file handles or memory mapped regions.
+///
+/// # Arguments
+///
+/// * `inputs` - The [`InputSet`] to pass to the functions
+/// * `algorithms` - The vector of functions to measure
+/// * `reset` - The function restoring an input before each run
+/// * `relative_error` - The required relative error of the measurements
+///
+pub fn measure_mut_with_reset<I, O, Alg, R>(
+    inputs: &mut InputSet<I>,
+    algorithms: &[(Alg, &str)],
+    reset: R,
+    relative_error: f32,
+) -> Measurements
+where
+    I: Input,
+    Alg: Fn(&mut I) -> O,
+    R: Fn(&mut I),
+{
+    assert!(relative_error > 0.0, "Relative error must be positive");
+    let resolution = get_average_resolution();
+    let run = new_run(inputs, relative_error, resolution);
+    let groups = get_groups(inputs);
+    let mut progress = DefaultProgress::default();
+    measure_run(
+        run,
+        algorithms,
+        &groups,
+        &mut progress,
+        |algorithm, i, j| {
+            get_time_reset(
+                algorithm,
+                &reset,
+                &mut inputs.inputs[i][j],
+                relative_error,
+                resolution,
+            )
+        },
+    )
+}
+
+impl Point {
+    /// Creates a new point with the given size and (mean) time, without samples and without any
+    /// other information. The other fields can be set with the struct update syntax, e.g.
+    /// `Point { bytes: Some(4000), ..Point::new(1000, time) }`.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size of the input
+    /// * `time` - The time it took to process the input
+    pub fn new(size: usize, time: Duration) -> Point {
+        Point {
+            size,
+            time,
+            bytes: None,
+            stddev: None,
+            samples: Vec::new(),
+            timestamp: None,
+            allocations: None,
+            peak_memory: None,
+        }
     }
 }
 
 // Some useful functions for Measurement
 impl Measurement {
+    /// Creates a new measurement of the algorithm with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `algorithm_name` - The name of the algorithm
+    /// * `measurement` - The points of the measurement
+    pub fn new(algorithm_name: &str, measurement: Vec<Point>) -> Measurement {
+        Measurement {
+            algorithm_name: algorithm_name.to_owned(),
+            measurement,
+        }
+    }
+
     /// Get the maximum time it took to run the function
     pub fn max_time(&self) -> Duration {
         self.measurement
@@ -432,6 +1105,28 @@ impl Measurement {
             .size
     }
 
+    /// Get the maximum size in bytes of the inputs passed to the function, if known for all of
+    /// them
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.measurement
+            .iter()
+            .map(|point| point.bytes)
+            .collect::<Option<Vec<usize>>>()?
+            .into_iter()
+            .max()
+    }
+
+    /// Get the minimum size in bytes of the inputs passed to the function, if known for all of
+    /// them
+    pub fn min_bytes(&self) -> Option<usize> {
+        self.measurement
+            .iter()
+            .map(|point| point.bytes)
+            .collect::<Option<Vec<usize>>>()?
+            .into_iter()
+            .min()
+    }
+
     /// Get the linear regression of the [`Measurement`]
     pub fn linear_regression(&self) -> (f32, f32) {
         let mut sum_x = 0.0;
@@ -453,8 +1148,10 @@ impl Measurement {
         (slope, intercept)
     }
 
-    /// Returns a new [`Measurement`] where the size and time of every [`Point`] is
-    /// the logarithm in base 2 of the original ones.
+    /// Returns a new [`Measurement`] where the size (in elements and in bytes) and time of every
+    /// [`Point`] is the logarithm in base 2 of the original ones. The standard deviations and the
+    /// samples are dropped, while the timestamps, the allocations and the peaks of the memory are
+    /// kept.
     pub fn log_log_scale(&self) -> Self {
         let mut new_measurement = Measurement {
             algorithm_name: self.algorithm_name.clone(),
@@ -464,6 +1161,12 @@ impl Measurement {
             new_measurement.measurement.push(Point {
                 size: (point.size as f32).log2() as usize,
                 time: Duration::from_micros((point.time.as_micros() as f32).log2() as u64),
+                bytes: point.bytes.map(|bytes| (bytes as f32).log2() as usize),
+                stddev: None,
+                samples: Vec::new(),
+                timestamp: point.timestamp,
+                allocations: point.allocations,
+                peak_memory: point.peak_memory,
             });
         }
         new_measurement
@@ -471,6 +1174,30 @@ impl Measurement {
 }
 
 impl Measurements {
+    /// Creates a new set of measurements, without the seed, the generation time, the environment
+    /// and the configuration of the inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `measurements` - The measurements of the algorithms
+    /// * `relative_error` - The relative error of the measurements
+    /// * `resolution` - The resolution of the clock
+    pub fn new(
+        measurements: Vec<Measurement>,
+        relative_error: f32,
+        resolution: Duration,
+    ) -> Measurements {
+        Measurements {
+            measurements,
+            relative_error,
+            resolution,
+            seed: None,
+            generation_time: None,
+            environment: None,
+            input_builder: None,
+        }
+    }
+
     /// Get the maximum time it took to run the functions
     pub fn max_time(&self) -> Duration {
         self.measurements
@@ -507,6 +1234,62 @@ impl Measurements {
             .min_length()
     }
 
+    /// Get the maximum size in bytes of the inputs passed to the functions, if known for all of
+    /// them
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.measurements
+            .iter()
+            .map(Measurement::max_bytes)
+            .collect::<Option<Vec<usize>>>()?
+            .into_iter()
+            .max()
+    }
+
+    /// Get the minimum size in bytes of the inputs passed to the functions, if known for all of
+    /// them
+    pub fn min_bytes(&self) -> Option<usize> {
+        self.measurements
+            .iter()
+            .map(Measurement::min_bytes)
+            .collect::<Option<Vec<usize>>>()?
+            .into_iter()
+            .min()
+    }
+
+    /// Attaches the configuration of the generation of the inputs to the [`Measurements`], e.g.
+    /// the [`InputBuilder`](crate::input::InputBuilder) with its distribution, its generator and
+    /// its seed, so that the file of the measurements describes how to reproduce them.
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - The configuration of the inputs.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the configuration cannot be serialized.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chrono_probe::input::distribution::Uniform;
+    /// use chrono_probe::input::generators::vec::{U32Vec, VecOrder};
+    /// use chrono_probe::input::InputBuilder;
+    /// use chrono_probe::measurements::measure;
+    ///
+    /// let builder: InputBuilder<U32Vec, _> =
+    ///     InputBuilder::new(Uniform::new(1000..=100_000).unwrap(), VecOrder::default())
+    ///         .with_seed(42);
+    /// let inputs = builder.build(20);
+    ///
+    /// let sum = |v: &U32Vec| v.0.iter().map(|x| *x as u64).sum::<u64>();
+    /// let measurements = measure(&inputs, &[(sum, "sum")], 0.01).with_input_builder(&builder);
+    /// measurements.serialize_json("results/measurements.json");
+    /// ```
+    pub fn with_input_builder<B: Serialize>(mut self, builder: &B) -> Measurements {
+        self.input_builder = Some(serde_json::to_value(builder).unwrap());
+        self
+    }
+
     /// Returns a new [`Measurements`] where the size and time of every [`Point`] is
     /// the logarithm in base 2 of the original ones.
     pub fn log_log_scale(&self) -> Self {
@@ -514,6 +1297,10 @@ impl Measurements {
             measurements: Vec::with_capacity(self.measurements.len()),
             relative_error: self.relative_error,
             resolution: self.resolution,
+            seed: self.seed,
+            generation_time: self.generation_time.clone(),
+            environment: self.environment.clone(),
+            input_builder: self.input_builder.clone(),
         };
         for measurement in &self.measurements {
             new_measurements
@@ -523,9 +1310,686 @@ impl Measurements {
         new_measurements
     }
 
-    /// Serialize the [`Measurements`] to a JSON file
+    /// Merges the [`Measurements`] obtained measuring different inputs, for example the shards
+    /// created with [`InputSet::shard`].
+    ///
+    /// The points of the measurements of algorithms with the same name are put together and
+    /// sorted by size, while the measurements of algorithms present only in `other` are appended.
+    /// The relative error and the resolution of the result are the largest of the two, while the
+    /// seed, the environment and the configuration of the inputs are kept only if they are the
+    /// same for both. The generation times of the inputs are added together, if known for both.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The measurements to merge with these ones.
+    pub fn merge(mut self, other: Measurements) -> Self {
+        for measurement in other.measurements {
+            match self
+                .measurements
+                .iter_mut()
+                .find(|m| m.algorithm_name == measurement.algorithm_name)
+            {
+                Some(existing) => {
+                    existing.measurement.extend(measurement.measurement);
+                    existing.measurement.sort_by_key(|point| point.size);
+                }
+                None => self.measurements.push(measurement),
+            }
+        }
+        self.relative_error = self.relative_error.max(other.relative_error);
+        self.resolution = self.resolution.max(other.resolution);
+        if self.seed != other.seed {
+            self.seed = None;
+        }
+        if self.environment != other.environment {
+            self.environment = None;
+        }
+        if self.input_builder != other.input_builder {
+            self.input_builder = None;
+        }
+        self.generation_time = match (self.generation_time, other.generation_time) {
+            (Some(time), Some(other_time)) => Some(time.merge(other_time)),
+            _ => None,
+        };
+        self
+    }
+
+    /// Imports the results of the benchmarks of [criterion](https://docs.rs/criterion) found in
+    /// the given directory, usually `target/criterion`, so that they can be analyzed and plotted
+    /// like the measurements taken by chrono-probe.
+    ///
+    /// Every benchmark whose parameter is a number, like the ones created with
+    /// `BenchmarkId::new(function, size)` or `BenchmarkId::from_parameter(size)`, becomes a
+    /// [`Point`] of the size of the parameter, while the other benchmarks are ignored. The points
+    /// of the same function of a group belong to the same [`Measurement`], named
+    /// `group/function`, or just `group` if the function has no name.
+    ///
+    /// The time of each point is the mean estimated by criterion, together with its standard
+    /// deviation and the mean time of an iteration in each sample. The relative error is the
+    /// largest of the standard errors of the means relative to the means, while the resolution
+    /// of the clock, the seed and the environment are unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The directory containing the results of criterion.
+    ///
+    /// # Errors
+    ///
+    /// * [`ImportError::Io`] - If a directory or a file of the results can't be read.
+    /// * [`ImportError::Format`] - If a file of the results isn't written as criterion does.
+    /// * [`ImportError::Empty`] - If no benchmark with a numeric parameter is found.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chrono_probe::measurements::Measurements;
+    /// use chrono_probe::plot::{time_plot, PlotConfig};
+    ///
+    /// let measurements = Measurements::from_criterion_dir("target/criterion").unwrap();
+    /// time_plot("plots/criterion.svg", measurements, &PlotConfig::default()).unwrap();
+    /// ```
+    pub fn from_criterion_dir<P: AsRef<Path>>(path: P) -> Result<Measurements, ImportError> {
+        let path = path.as_ref();
+        let mut results = Vec::new();
+        criterion_results(path, &mut results)?;
+
+        let mut measurements: Vec<Measurement> = Vec::new();
+        let mut relative_error: f32 = 0.0;
+        for dir in results {
+            let benchmark: CriterionBenchmark = read_json(&dir.join("benchmark.json"))?;
+            let Some(size) = benchmark
+                .value_str
+                .and_then(|value| value.trim().parse::<usize>().ok())
+            else {
+                continue;
+            };
+            let estimates: CriterionEstimates = read_json(&dir.join("estimates.json"))?;
+            let sample_path = dir.join("sample.json");
+            let sample: CriterionSample = read_json(&sample_path)?;
+            if sample.iters.len() != sample.times.len() {
+                return Err(ImportError::Format {
+                    path: sample_path,
+                    reason: "the numbers of iterations and of times differ".to_string(),
+                });
+            }
+
+            let nanos = |nanos: f64| Duration::from_secs_f64(nanos.max(0.0) / 1e9);
+            let mean = estimates.mean.point_estimate;
+            if mean > 0.0 {
+                relative_error = relative_error.max((estimates.mean.standard_error / mean) as f32);
+            }
+            let point = Point {
+                stddev: Some(nanos(estimates.std_dev.point_estimate)),
+                samples: sample
+                    .iters
+                    .iter()
+                    .zip(&sample.times)
+                    .filter(|(iters, _)| **iters > 0.0)
+                    .map(|(iters, time)| nanos(time / iters))
+                    .collect(),
+                ..Point::new(size, nanos(mean))
+            };
+
+            let algorithm_name = match benchmark.function_id {
+                Some(function) => format!("{}/{}", benchmark.group_id, function),
+                None => benchmark.group_id,
+            };
+            match measurements
+                .iter_mut()
+                .find(|m| m.algorithm_name == algorithm_name)
+            {
+                Some(existing) => existing.measurement.push(point),
+                None => measurements.push(Measurement {
+                    algorithm_name,
+                    measurement: vec![point],
+                }),
+            }
+        }
+
+        if measurements.is_empty() {
+            return Err(ImportError::Empty {
+                path: path.to_path_buf(),
+            });
+        }
+        for measurement in &mut measurements {
+            measurement.measurement.sort_by_key(|point| point.size);
+        }
+        Ok(Measurements {
+            measurements,
+            relative_error,
+            resolution: Duration::ZERO,
+            seed: None,
+            generation_time: None,
+            environment: None,
+            input_builder: None,
+        })
+    }
+
+    /// Serializes the points of the [`Measurements`] to a [Parquet](https://parquet.apache.org)
+    /// file, one row per point with the columns `algorithm`, `size`, `time_ns`, `bytes`,
+    /// `stddev_ns` and `timestamp_ns`. The relative error, the resolution of the clock and the
+    /// seed are stored in the key-value metadata of the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written. The missing parent
+    ///   directories of the file are created.
+    /// * [`ExportError::Format`] - If the measurements can't be encoded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use chrono_probe::measurements::Measurements;
+    /// # fn example(measurements: &Measurements) {
+    /// measurements.serialize_parquet("results/points.parquet").unwrap();
+    /// measurements.serialize_samples_parquet("results/samples.parquet").unwrap();
+    /// # }
+    /// ```
+    ///
+    /// The files can then be queried, for example with DuckDB:
+    ///
+    /// ```sql
+    /// SELECT algorithm, size, median(time_ns) FROM 'results/samples.parquet' GROUP BY ALL;
+    /// ```
+    #[cfg(feature = "parquet")]
+    pub fn serialize_parquet<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        parquet::write_points(self, path.as_ref())
+    }
+
+    /// Serializes the samples of the [`Measurements`] to a [Parquet](https://parquet.apache.org)
+    /// file, one row per sample with the columns `algorithm`, `size`, `sample` (the index of the
+    /// sample in its point) and `time_ns`. The metadata of the file are the same of
+    /// [`Measurements::serialize_parquet`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written. The missing parent
+    ///   directories of the file are created.
+    /// * [`ExportError::Format`] - If the measurements can't be encoded.
+    #[cfg(feature = "parquet")]
+    pub fn serialize_samples_parquet<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        parquet::write_samples(self, path.as_ref())
+    }
+
+    /// Serializes the [`Measurements`] to a binary file, much smaller and faster to write and
+    /// to read than the JSON of [`Measurements::serialize_json`]. The file will be created if it
+    /// doesn't exist, otherwise it will be overwritten.
+    ///
+    /// The file is encoded with [bincode](https://docs.rs/bincode), which doesn't describe the
+    /// structure of the data: it can only be read by [`Measurements::deserialize_bin`], with a
+    /// version of chrono-probe whose measurements have the same fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written.
+    /// * [`ExportError::Format`] - If the measurements can't be encoded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use chrono_probe::measurements::Measurements;
+    /// # fn example(measurements: &Measurements) {
+    /// measurements.serialize_bin("results/measurements.bin").unwrap();
+    /// let loaded = Measurements::deserialize_bin("results/measurements.bin").unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "bincode")]
+    pub fn serialize_bin<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        write_bin(self, path.as_ref())
+    }
+
+    /// Loads the [`Measurements`] from a binary file written by
+    /// [`Measurements::serialize_bin`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ImportError::Io`] - If the file can't be read.
+    /// * [`ImportError::Format`] - If the file doesn't contain measurements, or it is truncated.
+    #[cfg(feature = "bincode")]
+    pub fn deserialize_bin<P: AsRef<Path>>(path: P) -> Result<Measurements, ImportError> {
+        read_bin(path.as_ref())
+    }
+
+    /// Loads the [`Measurements`] from a JSON file written by [`Measurements::serialize_json`].
+    ///
+    /// The file can also be compressed with gzip or zstd, like the ones written by
+    /// `Measurements::serialize_json_gz` and `Measurements::serialize_json_zst`: the compression
+    /// is detected from the first bytes of the file, and requires the `gzip` or the `zstd`
+    /// feature respectively.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ImportError::Io`] - If the file can't be read.
+    /// * [`ImportError::Format`] - If the file doesn't contain measurements, or it is compressed
+    ///   and the feature to decompress it is disabled.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use chrono_probe::measurements::Measurements;
+    ///
+    /// let measurements = Measurements::from_json("results/measurements.json").unwrap();
+    /// ```
+    pub fn from_json<P: AsRef<Path>>(path: P) -> Result<Measurements, ImportError> {
+        read_json(path.as_ref())
+    }
+
+    /// Loads the [`Measurements`] from a JSON Lines file written by a
+    /// [`JsonLinesSink`](crate::sink::JsonLinesSink), e.g. the partial results of a run that was
+    /// aborted.
+    ///
+    /// The points are grouped by algorithm, in the order in which the algorithms appear in the
+    /// file, and sorted by size. A last line that is incomplete, because the process writing
+    /// it was killed, is ignored. Since the file contains only the points, the relative error
+    /// and the resolution of the result are 0, while the seed and the other metadata are
+    /// unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ImportError::Io`] - If the file can't be read.
+    /// * [`ImportError::Format`] - If a line doesn't contain a point.
+    /// * [`ImportError::Empty`] - If the file contains no point.
+    pub fn from_json_lines<P: AsRef<Path>>(path: P) -> Result<Measurements, ImportError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|source| ImportError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        // The lines are written whole, only the last one can be cut by a crash
+        let complete = match contents.rfind('\n') {
+            Some(end) => &contents[..end],
+            None => "",
+        };
+
+        let mut measurements: Vec<Measurement> = Vec::new();
+        for (i, line) in complete.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line: PointLine =
+                serde_json::from_str(line).map_err(|error| ImportError::Format {
+                    path: path.to_path_buf(),
+                    reason: format!("line {}: {}", i + 1, error),
+                })?;
+            match measurements
+                .iter_mut()
+                .find(|m| m.algorithm_name == line.algorithm)
+            {
+                Some(existing) => existing.measurement.push(line.point),
+                None => measurements.push(Measurement {
+                    algorithm_name: line.algorithm,
+                    measurement: vec![line.point],
+                }),
+            }
+        }
+
+        if measurements.is_empty() {
+            return Err(ImportError::Empty {
+                path: path.to_path_buf(),
+            });
+        }
+        for measurement in &mut measurements {
+            measurement.measurement.sort_by_key(|point| point.size);
+        }
+        Ok(Measurements {
+            measurements,
+            relative_error: 0.0,
+            resolution: Duration::ZERO,
+            seed: None,
+            generation_time: None,
+            environment: None,
+            input_builder: None,
+        })
+    }
+
+    /// Renders a summary of the [`Measurements`] in the [OpenMetrics](https://openmetrics.io)
+    /// text format, also understood by Prometheus, e.g. to expose the results of scheduled runs
+    /// to a monitoring dashboard.
+    ///
+    /// For each size of each algorithm, the gauges `chrono_probe_time_seconds`,
+    /// `chrono_probe_time_stddev_seconds`, `chrono_probe_input_bytes` and `chrono_probe_samples`
+    /// have a sample with the labels `algorithm` and `size`, if the value is known. The points
+    /// of a size measured more than once (e.g. with repetitions) are aggregated in a single
+    /// sample: the mean of the times and of the bytes, the standard deviation of all the times
+    /// and the total number of samples. The gauge
+    /// `chrono_probe_growth_exponent` has the exponent of the power law fitted to each algorithm
+    /// (see [`Fit`](crate::analysis::Fit)), and `chrono_probe_relative_error` the relative error
+    /// of the measurements.
+    ///
+    /// # Arguments
+    ///
+    /// * `labels` - The labels added to every sample, as (name, value) pairs, e.g. the name of
+    ///   the experiment or of the machine.
+    ///
+    /// # Errors
+    ///
+    /// * [`OpenMetricsError::InvalidLabel`] if the name of a label isn't valid.
+    /// * [`OpenMetricsError::ReservedLabel`] if the name of a label is `algorithm` or `size`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use chrono_probe::measurements::{Measurement, Measurements, Point};
+    ///
+    /// let point = Point::new(1000, Duration::from_micros(25));
+    /// let measurements = Measurements::new(
+    ///     vec![Measurement::new("sort", vec![point])],
+    ///     0.01,
+    ///     Duration::from_nanos(20),
+    /// );
+    ///
+    /// let text = measurements.to_openmetrics(&[("experiment", "nightly")]).unwrap();
+    /// assert!(text.contains(
+    ///     "chrono_probe_time_seconds{algorithm=\"sort\",size=\"1000\",experiment=\"nightly\"} 0.000025"
+    /// ));
+    /// assert!(text.ends_with("# EOF\n"));
+    /// assert!(measurements.to_openmetrics(&[("size", "large")]).is_err());
+    /// ```
+    pub fn to_openmetrics(&self, labels: &[(&str, &str)]) -> Result<String, OpenMetricsError> {
+        openmetrics::render(self, labels)
+    }
+
+    /// Serializes the [`Measurements`] to a JSON file in the format of
+    /// [Google Benchmark](https://github.com/google/benchmark), e.g. to compare them with its
+    /// `compare.py` script. The file will be created if it doesn't exist, otherwise it will be
+    /// overwritten.
+    ///
+    /// Each point is written as a benchmark named `<algorithm>/<size>`, with its mean time in
+    /// nanoseconds as both the real and the CPU time, the number of its samples as the number of
+    /// iterations, and the throughput in bytes per second if the size in bytes of the inputs is
+    /// known. The standard deviations and the samples themselves are not written.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written. The missing parent
+    ///   directories of the file are created.
+    /// * [`ExportError::Format`] - If the measurements can't be encoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use chrono_probe::measurements::{Measurement, Measurements, Point};
+    ///
+    /// let point = Point {
+    ///     bytes: Some(4000),
+    ///     ..Point::new(1000, Duration::from_micros(20))
+    /// };
+    /// let measurements = Measurements::new(
+    ///     vec![Measurement::new("sort", vec![point])],
+    ///     0.001,
+    ///     Duration::from_nanos(20),
+    /// );
+    ///
+    /// let path = std::env::temp_dir().join("chrono_probe_google_benchmark.json");
+    /// measurements.serialize_google_benchmark(&path).unwrap();
+    ///
+    /// let json: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    /// let benchmark = &json["benchmarks"][0];
+    /// assert_eq!(benchmark["name"], "sort/1000");
+    /// assert_eq!(benchmark["real_time"], 20000.0);
+    /// assert_eq!(benchmark["time_unit"], "ns");
+    /// assert_eq!(benchmark["bytes_per_second"], 2e8);
+    /// ```
+    pub fn serialize_google_benchmark<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        google_benchmark::write(self, path.as_ref())
+    }
+
+    /// Serializes the sizes and the times of the [`Measurements`] to a NumPy `.npz` file, which
+    /// can be loaded in Python with `numpy.load`. The file will be created if it doesn't exist,
+    /// otherwise it will be overwritten.
+    ///
+    /// The file contains the array `algorithms`, with the names of the algorithms, and for each
+    /// algorithm the arrays `<algorithm>/sizes` (unsigned integers), `<algorithm>/times` and
+    /// `<algorithm>/stddevs` (in seconds, NaN if not known), one element per point.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written.
+    /// * [`ExportError::Format`] - If the arrays take more than 4 GiB.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use chrono_probe::measurements::Measurements;
+    /// # fn example(measurements: &Measurements) {
+    /// measurements.serialize_npz("results/measurements.npz").unwrap();
+    /// # }
+    /// ```
+    ///
+    /// The arrays can then be plotted, for example with matplotlib:
+    ///
+    /// ```python
+    /// import matplotlib.pyplot as plt
+    /// import numpy as np
+    ///
+    /// data = np.load("results/measurements.npz")
+    /// for algorithm in data["algorithms"]:
+    ///     plt.loglog(data[f"{algorithm}/sizes"], data[f"{algorithm}/times"], label=algorithm)
+    /// plt.legend()
+    /// plt.show()
+    /// ```
+    pub fn serialize_npz<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        numpy::write_npz(self, path.as_ref())
+    }
+
+    /// Returns a table comparing the times of the algorithms, one row per algorithm and one
+    /// column per selected size, as text with the columns aligned.
+    ///
+    /// Each cell contains the time of the algorithm on the inputs of the size and, after the ×,
+    /// how many times slower it is than the fastest algorithm on the same size. The algorithms
+    /// without a point with the size of a column have a `-` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration of the table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use chrono_probe::measurements::{Measurement, Measurements, Point, TableConfig};
+    ///
+    /// let measurement = |name: &str, nanos: u64| {
+    ///     Measurement::new(name, vec![Point::new(1000, Duration::from_nanos(nanos))])
+    /// };
+    /// let measurements = Measurements::new(
+    ///     vec![measurement("merge sort", 2500), measurement("insertion sort", 10_000)],
+    ///     0.001,
+    ///     Duration::from_nanos(20),
+    /// );
+    ///
+    /// let table = measurements.to_table(&TableConfig::default().with_color(false));
+    /// assert_eq!(
+    ///     table,
+    ///     "algorithm             n = 1k\n\
+    ///      ----------------------------\n\
+    ///      merge sort      2.5 \u{3bc}s ×1.00\n\
+    ///      insertion sort   10 \u{3bc}s ×4.00\n"
+    /// );
+    /// ```
+    pub fn to_table(&self, config: &TableConfig) -> String {
+        table::render(self, config)
+    }
+
+    /// Prints the table of [`Measurements::to_table`] to the standard output, with the default
+    /// configuration: at most 6 sizes, colored if the standard output is a terminal.
+    pub fn print_table(&self) {
+        print!("{}", self.to_table(&TableConfig::default()));
+    }
+
+    /// Displays the [`Measurements`] in a Jupyter notebook running the
+    /// [evcxr](https://github.com/evcxr/evcxr) kernel, as the plot of
+    /// [`time_plot`](crate::plot::time_plot) followed by the table of [`Measurements::to_table`],
+    /// where the fastest and the slowest algorithm of each size are colored.
+    ///
+    /// The kernel calls this method when a cell evaluates to a [`Measurements`] value, so that it
+    /// doesn't need to be called directly. Outside of a notebook, it prints the HTML of the output
+    /// to the standard output.
+    ///
+    /// # Example
+    ///
+    /// In a cell of a notebook:
+    ///
+    /// ```no_run
+    /// use chrono_probe::measurements::Measurements;
+    ///
+    /// let measurements = Measurements::from_json("results/measurements.json").unwrap();
+    /// measurements
+    /// # ;
+    /// ```
+    #[cfg(feature = "evcxr")]
+    pub fn evcxr_display(&self) {
+        let mut html = String::new();
+        let empty = self
+            .measurements
+            .iter()
+            .all(|measurement| measurement.measurement.is_empty());
+        if !empty {
+            // A plot that can't be rendered is left out, the table is still useful
+            if let Ok(svg) =
+                crate::plot::time_plot_to_string(self, &crate::plot::PlotConfig::default())
+            {
+                html.push_str(&svg);
+                html.push('\n');
+            }
+        }
+        html.push_str(&table::render_html(self, &TableConfig::default()));
+        println!("EVCXR_BEGIN_CONTENT text/html\n{}EVCXR_END_CONTENT", html);
+    }
+
+    /// Serializes the [`Measurements`] to a JSON file compressed with gzip, usually with the
+    /// `.json.gz` extension, which can be loaded with [`Measurements::from_json`]. The file will
+    /// be created if it doesn't exist, otherwise it will be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written.
+    /// * [`ExportError::Format`] - If the measurements can't be encoded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use chrono_probe::measurements::Measurements;
+    /// # fn example(measurements: &Measurements) {
+    /// measurements.serialize_json_gz("results/measurements.json.gz").unwrap();
+    /// let measurements = Measurements::from_json("results/measurements.json.gz").unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "gzip")]
+    pub fn serialize_json_gz<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        compression::write_json_gz(self, path.as_ref())
+    }
+
+    /// Serializes the [`Measurements`] to a JSON file compressed with zstd, usually with the
+    /// `.json.zst` extension, which can be loaded with [`Measurements::from_json`]. It is
+    /// faster and smaller than the gzip file of `Measurements::serialize_json_gz`. The file will
+    /// be created if it doesn't exist, otherwise it will be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written.
+    /// * [`ExportError::Format`] - If the measurements can't be encoded.
+    #[cfg(feature = "zstd")]
+    pub fn serialize_json_zst<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        compression::write_json_zst(self, path.as_ref())
+    }
+
+    /// Writes the [`Measurements`] as compact JSON to the given writer, e.g. a socket, a buffer
+    /// in memory or the standard output.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer, or a mutable reference to it.
+    ///
+    /// # Errors
+    ///
+    /// * If the writer fails, or the measurements can't be encoded, with the kind
+    ///   [`io::ErrorKind::InvalidData`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use chrono_probe::measurements::Measurements;
+    /// # fn example(measurements: &Measurements) -> std::io::Result<()> {
+    /// let mut buffer = Vec::new();
+    /// measurements.to_writer(&mut buffer)?;
+    ///
+    /// measurements.to_writer_pretty(std::io::stdout().lock())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_writer<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Writes the [`Measurements`] as indented JSON to the given writer, like
+    /// [`Measurements::to_writer`].
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer, or a mutable reference to it.
+    ///
+    /// # Errors
+    ///
+    /// * If the writer fails, or the measurements can't be encoded, with the kind
+    ///   [`io::ErrorKind::InvalidData`].
+    pub fn to_writer_pretty<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::from)
+    }
+
+    /// Serialize the [`Measurements`] to a JSON file, see [`Measurements::to_writer`].
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the file cannot be created or written.
     pub fn serialize_json(&self, filename: &str) {
-        let mut file = File::create(filename).unwrap();
-        serde_json::to_writer(&mut file, &self).unwrap();
+        let mut file = BufWriter::new(File::create(filename).unwrap());
+        self.to_writer(&mut file).unwrap();
+        file.flush().unwrap();
     }
 }
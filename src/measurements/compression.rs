@@ -0,0 +1,153 @@
+//! # Compression
+//!
+//! This module reads and writes the compressed result files, enabled by the `gzip` and `zstd`
+//! features, since the JSON of the measurements with all their samples can take hundreds of
+//! megabytes.
+//!
+//! The format of a file is detected from its first bytes, so that the compressed files can be
+//! loaded by the same functions as the uncompressed ones, whatever their extension.
+
+use std::fs;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use std::fs::File;
+#[cfg(feature = "gzip")]
+use std::io::Read;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use crate::measurements::ExportError;
+use crate::measurements::ImportError;
+
+/// The first bytes of a file compressed with gzip.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// The first bytes of a file compressed with zstd.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Reads the given file, decompressing it if it is compressed with gzip or zstd.
+pub(crate) fn read(path: &Path) -> Result<Vec<u8>, ImportError> {
+    let contents = fs::read(path).map_err(|source| ImportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let decompressed = if contents.starts_with(&GZIP_MAGIC) {
+        gunzip(&contents)
+    } else if contents.starts_with(&ZSTD_MAGIC) {
+        unzstd(&contents)
+    } else {
+        return Ok(contents);
+    };
+    decompressed.map_err(|reason| ImportError::Format {
+        path: path.to_path_buf(),
+        reason,
+    })
+}
+
+/// Returns the reason why a file compressed with the given format can't be decompressed.
+#[cfg(not(all(feature = "gzip", feature = "zstd")))]
+fn disabled(format: &str) -> String {
+    format!(
+        "the file is compressed with {}, which requires the `{}` feature",
+        format, format
+    )
+}
+
+/// Decompresses the given contents, compressed with gzip.
+#[cfg(feature = "gzip")]
+fn gunzip(contents: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decompressed = Vec::new();
+    flate2::read::MultiGzDecoder::new(contents)
+        .read_to_end(&mut decompressed)
+        .map_err(|error| error.to_string())?;
+    Ok(decompressed)
+}
+
+/// Decompresses the given contents, compressed with gzip.
+#[cfg(not(feature = "gzip"))]
+fn gunzip(_contents: &[u8]) -> Result<Vec<u8>, String> {
+    Err(disabled("gzip"))
+}
+
+/// Decompresses the given contents, compressed with zstd.
+#[cfg(feature = "zstd")]
+fn unzstd(contents: &[u8]) -> Result<Vec<u8>, String> {
+    zstd::stream::decode_all(contents).map_err(|error| error.to_string())
+}
+
+/// Decompresses the given contents, compressed with zstd.
+#[cfg(not(feature = "zstd"))]
+fn unzstd(_contents: &[u8]) -> Result<Vec<u8>, String> {
+    Err(disabled("zstd"))
+}
+
+/// Writes the given value as JSON to the given file, compressed with gzip.
+#[cfg(feature = "gzip")]
+pub(crate) fn write_json_gz<T: serde::Serialize>(
+    value: &T,
+    path: &Path,
+) -> Result<(), ExportError> {
+    let file = create(path)?;
+    let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    write_json(value, path, &mut encoder)?;
+    encoder
+        .finish()
+        .and_then(|mut file| file.flush())
+        .map_err(|source| ExportError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Writes the given value as JSON to the given file, compressed with zstd.
+#[cfg(feature = "zstd")]
+pub(crate) fn write_json_zst<T: serde::Serialize>(
+    value: &T,
+    path: &Path,
+) -> Result<(), ExportError> {
+    let io_error = |source| ExportError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let file = create(path)?;
+    let mut encoder = zstd::stream::write::Encoder::new(file, 0).map_err(io_error)?;
+    write_json(value, path, &mut encoder)?;
+    encoder
+        .finish()
+        .and_then(|mut file| file.flush())
+        .map_err(io_error)
+}
+
+/// Creates the given file, buffering the writes.
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+fn create(path: &Path) -> Result<BufWriter<File>, ExportError> {
+    File::create(path)
+        .map(BufWriter::new)
+        .map_err(|source| ExportError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Writes the given value as JSON to the given writer.
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+fn write_json<T: serde::Serialize, W: Write>(
+    value: &T,
+    path: &Path,
+    writer: W,
+) -> Result<(), ExportError> {
+    serde_json::to_writer(writer, value).map_err(|error| {
+        if error.is_io() {
+            ExportError::Io {
+                path: path.to_path_buf(),
+                source: error.into(),
+            }
+        } else {
+            ExportError::Format {
+                path: path.to_path_buf(),
+                reason: error.to_string(),
+            }
+        }
+    })
+}
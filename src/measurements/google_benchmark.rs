@@ -0,0 +1,126 @@
+//! # Google Benchmark
+//!
+//! This module writes the [`Measurements`] in the JSON format of
+//! [Google Benchmark](https://github.com/google/benchmark), so that the many tools reading it,
+//! like its `compare.py` script or the continuous benchmarking services, can be used with the
+//! results of chrono-probe.
+//!
+//! Each point becomes a benchmark named `<algorithm>/<size>`, the convention of Google Benchmark
+//! for the arguments of a benchmark, with the mean time of the point in nanoseconds. Since only
+//! the wall-clock time is measured, the CPU time is the same as the real time.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::measurements::{ExportError, Measurements};
+
+/// The root object of the file.
+#[derive(Serialize)]
+struct Report<'a> {
+    context: Context<'a>,
+    benchmarks: Vec<Benchmark>,
+}
+
+/// The context of the run, with the fields of the environment that are known.
+#[derive(Serialize)]
+struct Context<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_cpus: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mhz_per_cpu: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    library_build_type: Option<&'a str>,
+}
+
+/// A benchmark, i.e. a point of a measurement.
+#[derive(Serialize)]
+struct Benchmark {
+    name: String,
+    family_index: usize,
+    per_family_instance_index: usize,
+    run_name: String,
+    run_type: &'static str,
+    repetitions: usize,
+    repetition_index: usize,
+    threads: usize,
+    iterations: usize,
+    real_time: f64,
+    cpu_time: f64,
+    time_unit: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes_per_second: Option<f64>,
+}
+
+/// Writes the measurements to the given file in the JSON format of Google Benchmark.
+pub(crate) fn write(measurements: &Measurements, path: &Path) -> Result<(), ExportError> {
+    let environment = measurements.environment.as_ref();
+    let report =
+        Report {
+            context: Context {
+                num_cpus: environment.and_then(|environment| environment.logical_cores),
+                mhz_per_cpu: environment.and_then(|environment| environment.cpu_frequency_mhz),
+                library_build_type: environment.map(|environment| {
+                    if environment.debug_assertions {
+                        "debug"
+                    } else {
+                        "release"
+                    }
+                }),
+            },
+            benchmarks: measurements
+                .measurements
+                .iter()
+                .enumerate()
+                .flat_map(|(family_index, measurement)| {
+                    measurement.measurement.iter().enumerate().map(
+                        move |(instance_index, point)| {
+                            let name = format!("{}/{}", measurement.algorithm_name, point.size);
+                            let time = point.time.as_nanos() as f64;
+                            Benchmark {
+                                run_name: name.clone(),
+                                name,
+                                family_index,
+                                per_family_instance_index: instance_index,
+                                run_type: "iteration",
+                                repetitions: 1,
+                                repetition_index: 0,
+                                threads: 1,
+                                iterations: point.samples.len().max(1),
+                                real_time: time,
+                                cpu_time: time,
+                                time_unit: "ns",
+                                bytes_per_second: point
+                                    .bytes
+                                    .filter(|_| !point.time.is_zero())
+                                    .map(|bytes| bytes as f64 * 1e9 / time),
+                            }
+                        },
+                    )
+                })
+                .collect(),
+        };
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|source| ExportError::Io {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+    let io_error = |source| ExportError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+    serde_json::to_writer_pretty(&mut writer, &report).map_err(|error| ExportError::Format {
+        path: path.to_path_buf(),
+        reason: error.to_string(),
+    })?;
+    writer.write_all(b"\n").map_err(io_error)?;
+    writer.flush().map_err(io_error)
+}
@@ -0,0 +1,153 @@
+//! # Nanoseconds
+//!
+//! This module serializes the durations of the results as integer numbers of nanoseconds, e.g.
+//! `2500` instead of `{"secs": 0, "nanos": 2500}`, which is how serde encodes a [`Duration`] by
+//! default and is awkward to read from other languages.
+//!
+//! Only the human readable formats, like JSON, use the nanoseconds: the binary ones keep the
+//! default encoding, since they can't tell a number from a structure when reading. Both shapes
+//! are accepted when deserializing, so that the files written by the previous versions can still
+//! be loaded.
+//!
+//! The functions of this module are used with `#[serde(with = "...")]`, while the submodules
+//! handle the optional durations, the lists of durations and the lists of (size, duration)
+//! pairs.
+
+use std::fmt::Formatter;
+use std::time::Duration;
+
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A duration serialized as nanoseconds.
+struct Nanos(Duration);
+
+impl Serialize for Nanos {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Nanos {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(Nanos)
+    }
+}
+
+/// Reads a duration either as nanoseconds or as the default encoding of serde.
+struct NanosVisitor;
+
+impl<'de> Visitor<'de> for NanosVisitor {
+    type Value = Duration;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a number of nanoseconds or a duration with `secs` and `nanos`")
+    }
+
+    fn visit_u64<E: de::Error>(self, nanos: u64) -> Result<Duration, E> {
+        Ok(Duration::from_nanos(nanos))
+    }
+
+    fn visit_i64<E: de::Error>(self, nanos: i64) -> Result<Duration, E> {
+        u64::try_from(nanos)
+            .map(Duration::from_nanos)
+            .map_err(|_| E::custom("a duration can't be negative"))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Duration, A::Error> {
+        Duration::deserialize(MapAccessDeserializer::new(map))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Duration, A::Error> {
+        Duration::deserialize(SeqAccessDeserializer::new(seq))
+    }
+}
+
+pub(crate) fn serialize<S: Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        let nanos = u64::try_from(duration.as_nanos()).map_err(|_| {
+            S::Error::custom("the duration is too long to be encoded in nanoseconds")
+        })?;
+        serializer.serialize_u64(nanos)
+    } else {
+        duration.serialize(serializer)
+    }
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Duration, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(NanosVisitor)
+    } else {
+        Duration::deserialize(deserializer)
+    }
+}
+
+/// Serializes an optional duration as nanoseconds.
+pub(crate) mod option {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        duration.map(Nanos).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<Nanos>::deserialize(deserializer)?.map(|nanos| nanos.0))
+    }
+}
+
+/// Serializes a list of durations as nanoseconds.
+pub(crate) mod vec {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        durations: &[Duration],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(durations.iter().map(|duration| Nanos(*duration)))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Duration>, D::Error> {
+        let durations = Vec::<Nanos>::deserialize(deserializer)?;
+        Ok(durations.into_iter().map(|nanos| nanos.0).collect())
+    }
+}
+
+/// Serializes a list of (size, duration) pairs with the durations as nanoseconds.
+pub(crate) mod sized {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        pairs: &[(usize, Duration)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            pairs
+                .iter()
+                .map(|(size, duration)| (*size, Nanos(*duration))),
+        )
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(usize, Duration)>, D::Error> {
+        let pairs = Vec::<(usize, Nanos)>::deserialize(deserializer)?;
+        Ok(pairs
+            .into_iter()
+            .map(|(size, nanos)| (size, nanos.0))
+            .collect())
+    }
+}
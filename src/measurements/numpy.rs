@@ -0,0 +1,223 @@
+//! # NumPy
+//!
+//! This module writes the [`Measurements`] to a NumPy `.npz` file, so that they can be loaded in
+//! Python with `numpy.load` and analyzed with the scientific libraries, without any parser.
+//!
+//! An `.npz` file is a ZIP archive of arrays in the `.npy` format. The arrays are stored without
+//! compression, so that no compression library is needed.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::measurements::{ExportError, Measurements};
+
+/// An array of the `.npy` format, with its type and its little-endian data.
+struct Array {
+    /// The type of the elements, in the notation of NumPy
+    descr: String,
+    /// The number of elements
+    len: usize,
+    /// The elements
+    data: Vec<u8>,
+}
+
+impl Array {
+    /// Returns an array of unsigned 64-bit integers.
+    fn u64<V: IntoIterator<Item = u64>>(values: V) -> Array {
+        let values: Vec<u64> = values.into_iter().collect();
+        Array {
+            descr: "<u8".to_owned(),
+            len: values.len(),
+            data: values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect(),
+        }
+    }
+
+    /// Returns an array of 64-bit floats.
+    fn f64<V: IntoIterator<Item = f64>>(values: V) -> Array {
+        let values: Vec<f64> = values.into_iter().collect();
+        Array {
+            descr: "<f8".to_owned(),
+            len: values.len(),
+            data: values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect(),
+        }
+    }
+
+    /// Returns an array of strings, as fixed-length unicode strings of NumPy.
+    fn str<'a, V: IntoIterator<Item = &'a str>>(values: V) -> Array {
+        let values: Vec<Vec<char>> = values
+            .into_iter()
+            .map(|value| value.chars().collect())
+            .collect();
+        let width = values.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        Array {
+            descr: format!("<U{}", width),
+            len: values.len(),
+            data: values
+                .iter()
+                .flat_map(|value| {
+                    (0..width).flat_map(|i| (value.get(i).map_or(0, |c| *c as u32)).to_le_bytes())
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the array in the `.npy` format, version 1.0.
+    fn to_npy(&self) -> Vec<u8> {
+        let mut header = format!(
+            "{{'descr': '{}', 'fortran_order': False, 'shape': ({},), }}",
+            self.descr, self.len
+        );
+        // The header is padded with spaces and ends with a newline, so that the data is aligned
+        // to 64 bytes after the magic string (6 bytes), the version (2) and the length (2)
+        let padding = 63 - (10 + header.len()) % 64;
+        header.extend(std::iter::repeat_n(' ', padding));
+        header.push('\n');
+
+        let mut npy = Vec::with_capacity(10 + header.len() + self.data.len());
+        npy.extend_from_slice(b"\x93NUMPY\x01\x00");
+        npy.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        npy.extend_from_slice(header.as_bytes());
+        npy.extend_from_slice(&self.data);
+        npy
+    }
+}
+
+/// The table of the CRC-32 checksum of ZIP archives, for each byte.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Returns the CRC-32 checksum of the given data.
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, byte| {
+        CRC_TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Returns a ZIP archive containing the given files, without compression.
+///
+/// Returns `None` if the archive is too large for the ZIP format without its 64-bit extension.
+fn zip(files: &[(String, Vec<u8>)]) -> Option<Vec<u8>> {
+    // The flag of the names encoded in UTF-8
+    const UTF8: u16 = 1 << 11;
+    // 1980-01-01, the first date of the format
+    const DATE: u16 = (1 << 5) | 1;
+
+    let mut archive = Vec::new();
+    let mut directory = Vec::new();
+    for (name, data) in files {
+        let offset = u32::try_from(archive.len()).ok()?;
+        let size = u32::try_from(data.len()).ok()?;
+        let crc = crc32(data);
+        // The fields shared by the local header and the entry in the central directory
+        let mut fields = Vec::new();
+        fields.extend_from_slice(&20u16.to_le_bytes()); // Version needed to extract
+        fields.extend_from_slice(&UTF8.to_le_bytes());
+        fields.extend_from_slice(&0u16.to_le_bytes()); // Stored, without compression
+        fields.extend_from_slice(&0u16.to_le_bytes()); // Time
+        fields.extend_from_slice(&DATE.to_le_bytes());
+        fields.extend_from_slice(&crc.to_le_bytes());
+        fields.extend_from_slice(&size.to_le_bytes()); // Compressed size
+        fields.extend_from_slice(&size.to_le_bytes()); // Uncompressed size
+        fields.extend_from_slice(&u16::try_from(name.len()).ok()?.to_le_bytes());
+        fields.extend_from_slice(&0u16.to_le_bytes()); // Length of the extra field
+
+        archive.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        archive.extend_from_slice(&fields);
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(data);
+
+        directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        directory.extend_from_slice(&20u16.to_le_bytes()); // Version made by
+        directory.extend_from_slice(&fields);
+        directory.extend_from_slice(&[0; 6]); // Comment, disk and internal attributes
+        directory.extend_from_slice(&0u32.to_le_bytes()); // External attributes
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+    }
+
+    let entries = u16::try_from(files.len()).ok()?;
+    let directory_offset = u32::try_from(archive.len()).ok()?;
+    let directory_size = u32::try_from(directory.len()).ok()?;
+    archive.extend_from_slice(&directory);
+    archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    archive.extend_from_slice(&[0; 4]); // Number of the disk and of the disk of the directory
+    archive.extend_from_slice(&entries.to_le_bytes());
+    archive.extend_from_slice(&entries.to_le_bytes());
+    archive.extend_from_slice(&directory_size.to_le_bytes());
+    archive.extend_from_slice(&directory_offset.to_le_bytes());
+    archive.extend_from_slice(&0u16.to_le_bytes()); // Length of the comment
+    Some(archive)
+}
+
+/// Writes the measurements to the given file in the `.npz` format.
+pub(crate) fn write_npz(measurements: &Measurements, path: &Path) -> Result<(), ExportError> {
+    let mut arrays = vec![(
+        "algorithms".to_owned(),
+        Array::str(
+            measurements
+                .measurements
+                .iter()
+                .map(|measurement| measurement.algorithm_name.as_str()),
+        ),
+    )];
+    for measurement in &measurements.measurements {
+        let points = &measurement.measurement;
+        let name = &measurement.algorithm_name;
+        arrays.push((
+            format!("{}/sizes", name),
+            Array::u64(points.iter().map(|point| point.size as u64)),
+        ));
+        arrays.push((
+            format!("{}/times", name),
+            Array::f64(points.iter().map(|point| point.time.as_secs_f64())),
+        ));
+        arrays.push((
+            format!("{}/stddevs", name),
+            Array::f64(
+                points
+                    .iter()
+                    .map(|point| point.stddev.map_or(f64::NAN, |stddev| stddev.as_secs_f64())),
+            ),
+        ));
+    }
+    let files: Vec<(String, Vec<u8>)> = arrays
+        .iter()
+        .map(|(name, array)| (format!("{}.npy", name), array.to_npy()))
+        .collect();
+    let archive = zip(&files).ok_or_else(|| ExportError::Format {
+        path: path.to_path_buf(),
+        reason: "the measurements are too large for a ZIP archive".to_owned(),
+    })?;
+
+    let io_error = |source| ExportError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+    writer.write_all(&archive).map_err(io_error)?;
+    writer.flush().map_err(io_error)
+}
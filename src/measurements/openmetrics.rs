@@ -0,0 +1,231 @@
+//! # OpenMetrics
+//!
+//! This module renders a summary of the [`Measurements`] in the
+//! [OpenMetrics](https://openmetrics.io) text format, also understood by Prometheus, so that
+//! scheduled runs can feed existing monitoring dashboards.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::analysis::Fit;
+use crate::measurements::{Measurements, OpenMetricsError, Point};
+
+/// The prefix of the names of the metrics.
+const PREFIX: &str = "chrono_probe";
+
+/// Returns whether the given name is a valid name of a label.
+fn is_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Returns the given value of a label, with the backslashes, the quotes and the newlines
+/// escaped.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// A metric family with a sample for each size of each algorithm that has a value, computed
+/// from all the points of the size.
+struct PointMetric {
+    name: &'static str,
+    unit: Option<&'static str>,
+    help: &'static str,
+    value: fn(&[&Point]) -> Option<f64>,
+}
+
+/// Returns the mean of the given values.
+fn mean(values: impl ExactSizeIterator<Item = f64>) -> f64 {
+    let n = values.len() as f64;
+    values.sum::<f64>() / n
+}
+
+/// Returns the standard deviation of the times of the given points, including both the spread
+/// of the points and the standard deviation of each of them, if known.
+fn stddev(points: &[&Point]) -> Option<f64> {
+    if let [point] = points {
+        return point.stddev.map(|stddev| stddev.as_secs_f64());
+    }
+    let time = mean(points.iter().map(|point| point.time.as_secs_f64()));
+    let variance = mean(points.iter().map(|point| {
+        let stddev = point.stddev.unwrap_or_default().as_secs_f64();
+        (point.time.as_secs_f64() - time).powi(2) + stddev.powi(2)
+    }));
+    Some(variance.sqrt())
+}
+
+/// The metrics of the points.
+const POINT_METRICS: [PointMetric; 4] = [
+    PointMetric {
+        name: "time_seconds",
+        unit: Some("seconds"),
+        help: "The mean time taken by the algorithm on the inputs of the size.",
+        value: |points| Some(mean(points.iter().map(|point| point.time.as_secs_f64()))),
+    },
+    PointMetric {
+        name: "time_stddev_seconds",
+        unit: Some("seconds"),
+        help:
+            "The standard deviation of the times taken by the algorithm on the inputs of the size.",
+        value: stddev,
+    },
+    PointMetric {
+        name: "input_bytes",
+        unit: Some("bytes"),
+        help: "The mean size in bytes of the inputs of the size.",
+        value: |points| {
+            let bytes: Option<Vec<f64>> = points
+                .iter()
+                .map(|point| point.bytes.map(|bytes| bytes as f64))
+                .collect();
+            Some(mean(bytes?.into_iter()))
+        },
+    },
+    PointMetric {
+        name: "samples",
+        unit: None,
+        help: "The number of inputs of the size on which the algorithm was measured.",
+        value: |points| Some(points.iter().map(|point| point.samples.len() as f64).sum()),
+    },
+];
+
+/// Writes the metadata of a gauge metric family.
+fn header(text: &mut String, name: &str, unit: Option<&str>, help: &str) {
+    writeln!(text, "# TYPE {PREFIX}_{name} gauge").unwrap();
+    if let Some(unit) = unit {
+        writeln!(text, "# UNIT {PREFIX}_{name} {unit}").unwrap();
+    }
+    writeln!(text, "# HELP {PREFIX}_{name} {help}").unwrap();
+}
+
+/// Returns the points of each algorithm grouped by size, in the order of the algorithms, so that
+/// each series has a single sample even if a size was measured more than once.
+fn group(measurements: &Measurements) -> Vec<(&str, BTreeMap<usize, Vec<&Point>>)> {
+    let mut groups: Vec<(&str, BTreeMap<usize, Vec<&Point>>)> = Vec::new();
+    for measurement in &measurements.measurements {
+        let name = measurement.algorithm_name.as_str();
+        let index = match groups.iter().position(|(group, _)| *group == name) {
+            Some(index) => index,
+            None => {
+                groups.push((name, BTreeMap::new()));
+                groups.len() - 1
+            }
+        };
+        for point in &measurement.measurement {
+            groups[index].1.entry(point.size).or_default().push(point);
+        }
+    }
+    groups
+}
+
+/// Renders the measurements in the OpenMetrics text format, see
+/// [`Measurements::to_openmetrics`].
+pub(crate) fn render(
+    measurements: &Measurements,
+    labels: &[(&str, &str)],
+) -> Result<String, OpenMetricsError> {
+    for (name, _) in labels {
+        if !is_label_name(name) {
+            return Err(OpenMetricsError::InvalidLabel {
+                name: name.to_string(),
+            });
+        }
+        if matches!(*name, "algorithm" | "size") {
+            return Err(OpenMetricsError::ReservedLabel {
+                name: name.to_string(),
+            });
+        }
+    }
+    let common: String = labels
+        .iter()
+        .map(|(name, value)| format!(",{}=\"{}\"", name, escape(value)))
+        .collect();
+    let algorithm_labels = |name: &str| format!("algorithm=\"{}\"", escape(name));
+    let groups = group(measurements);
+
+    let mut text = String::new();
+    for metric in &POINT_METRICS {
+        let samples: Vec<(&str, usize, f64)> = groups
+            .iter()
+            .flat_map(|(name, sizes)| {
+                sizes
+                    .iter()
+                    .filter_map(move |(size, points)| Some((*name, *size, (metric.value)(points)?)))
+            })
+            .collect();
+        if samples.is_empty() {
+            continue;
+        }
+        header(&mut text, metric.name, metric.unit, metric.help);
+        for (name, size, value) in samples {
+            writeln!(
+                text,
+                "{PREFIX}_{}{{{},size=\"{}\"{}}} {}",
+                metric.name,
+                algorithm_labels(name),
+                size,
+                common,
+                value
+            )
+            .unwrap();
+        }
+    }
+
+    let fits: Vec<(&str, Fit)> = groups
+        .iter()
+        .filter_map(|(name, sizes)| {
+            let points = sizes.values().flatten();
+            let fit = Fit::try_from_points(points.map(|point| (point.size as f64, point.time)))?;
+            Some((*name, fit))
+        })
+        .collect();
+    if !fits.is_empty() {
+        header(
+            &mut text,
+            "growth_exponent",
+            None,
+            "The exponent of the power law fitted to the times of the algorithm.",
+        );
+        for (name, fit) in fits {
+            writeln!(
+                text,
+                "{PREFIX}_growth_exponent{{{}{}}} {}",
+                algorithm_labels(name),
+                common,
+                fit.exponent
+            )
+            .unwrap();
+        }
+    }
+
+    header(
+        &mut text,
+        "relative_error",
+        None,
+        "The relative error of the measurements.",
+    );
+    let common = common.trim_start_matches(',');
+    if common.is_empty() {
+        writeln!(
+            text,
+            "{PREFIX}_relative_error {}",
+            measurements.relative_error
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            text,
+            "{PREFIX}_relative_error{{{common}}} {}",
+            measurements.relative_error
+        )
+        .unwrap();
+    }
+    text.push_str("# EOF\n");
+    Ok(text)
+}
@@ -0,0 +1,160 @@
+//! # Parquet
+//!
+//! This module writes the [`Measurements`] to [Parquet](https://parquet.apache.org) files,
+//! enabled by the `parquet` feature, so that large sets of measurements can be queried
+//! efficiently with tools like DuckDB, pandas or polars.
+//!
+//! Each file contains a table in long format, one row per point or per sample, compressed with
+//! Snappy. The relative error, the resolution of the clock and the seed of the measurements are
+//! stored in the key-value metadata of the files.
+
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::Arc;
+
+use ::parquet::arrow::ArrowWriter;
+use ::parquet::basic::Compression;
+use ::parquet::file::metadata::KeyValue;
+use ::parquet::file::properties::WriterProperties;
+use arrow_array::{ArrayRef, RecordBatch, StringArray, UInt32Array, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+
+use crate::measurements::{ExportError, Measurement, Measurements};
+
+/// Returns the given duration in nanoseconds.
+fn nanos(duration: std::time::Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+/// Writes a table to the given file, one record batch per measurement.
+fn write<F>(
+    path: &Path,
+    measurements: &Measurements,
+    schema: SchemaRef,
+    columns: F,
+) -> Result<(), ExportError>
+where
+    F: Fn(&Measurement) -> Vec<ArrayRef>,
+{
+    let format_error = |error: &dyn std::error::Error| ExportError::Format {
+        path: path.to_path_buf(),
+        reason: error.to_string(),
+    };
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|source| ExportError::Io {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+    let file = File::create(path).map_err(|source| ExportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let mut metadata = vec![
+        KeyValue::new(
+            "relative_error".to_string(),
+            measurements.relative_error.to_string(),
+        ),
+        KeyValue::new(
+            "resolution_ns".to_string(),
+            nanos(measurements.resolution).to_string(),
+        ),
+    ];
+    if let Some(seed) = measurements.seed {
+        metadata.push(KeyValue::new("seed".to_string(), seed.to_string()));
+    }
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_key_value_metadata(Some(metadata))
+        .build();
+
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(properties))
+        .map_err(|error| format_error(&error))?;
+    for measurement in &measurements.measurements {
+        let batch = RecordBatch::try_new(schema.clone(), columns(measurement))
+            .map_err(|error| format_error(&error))?;
+        writer.write(&batch).map_err(|error| format_error(&error))?;
+    }
+    writer.close().map_err(|error| format_error(&error))?;
+    Ok(())
+}
+
+/// Writes the points of the measurements to the given file, with the columns `algorithm`,
+/// `size`, `time_ns`, `bytes`, `stddev_ns` and `timestamp_ns`.
+pub(crate) fn write_points(measurements: &Measurements, path: &Path) -> Result<(), ExportError> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("algorithm", DataType::Utf8, false),
+        Field::new("size", DataType::UInt64, false),
+        Field::new("time_ns", DataType::UInt64, false),
+        Field::new("bytes", DataType::UInt64, true),
+        Field::new("stddev_ns", DataType::UInt64, true),
+        Field::new("timestamp_ns", DataType::UInt64, true),
+    ]));
+    write(path, measurements, schema, |measurement| {
+        let points = &measurement.measurement;
+        vec![
+            Arc::new(StringArray::from(vec![
+                measurement.algorithm_name.as_str();
+                points.len()
+            ])),
+            Arc::new(UInt64Array::from_iter_values(
+                points.iter().map(|point| point.size as u64),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                points.iter().map(|point| nanos(point.time)),
+            )),
+            Arc::new(UInt64Array::from_iter(
+                points
+                    .iter()
+                    .map(|point| point.bytes.map(|bytes| bytes as u64)),
+            )),
+            Arc::new(UInt64Array::from_iter(
+                points.iter().map(|point| point.stddev.map(nanos)),
+            )),
+            Arc::new(UInt64Array::from_iter(
+                points.iter().map(|point| point.timestamp.map(nanos)),
+            )),
+        ]
+    })
+}
+
+/// Writes the samples of the measurements to the given file, with the columns `algorithm`,
+/// `size`, `sample` (the index of the sample in its point) and `time_ns`.
+pub(crate) fn write_samples(measurements: &Measurements, path: &Path) -> Result<(), ExportError> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("algorithm", DataType::Utf8, false),
+        Field::new("size", DataType::UInt64, false),
+        Field::new("sample", DataType::UInt32, false),
+        Field::new("time_ns", DataType::UInt64, false),
+    ]));
+    write(path, measurements, schema, |measurement| {
+        let samples = || {
+            measurement.measurement.iter().flat_map(|point| {
+                point
+                    .samples
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, sample)| (point.size, i, *sample))
+            })
+        };
+        vec![
+            Arc::new(StringArray::from(vec![
+                measurement.algorithm_name.as_str();
+                samples().count()
+            ])),
+            Arc::new(UInt64Array::from_iter_values(
+                samples().map(|(size, _, _)| size as u64),
+            )),
+            Arc::new(UInt32Array::from_iter_values(
+                samples().map(|(_, i, _)| i as u32),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                samples().map(|(_, _, sample)| nanos(sample)),
+            )),
+        ]
+    })
+}
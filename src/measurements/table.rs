@@ -0,0 +1,268 @@
+//! # Table
+//!
+//! This module renders the [`Measurements`] as a table of text, one row per algorithm and one
+//! column per selected size, so that the results can be compared at a glance in a terminal.
+//!
+//! Each cell contains the time of the algorithm on the inputs of the size, and how many times
+//! slower it is than the fastest algorithm on the same size. If colors are enabled, the fastest
+//! algorithm of each column is green and the slowest is red.
+
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use crate::measurements::Measurements;
+use crate::plot::{format_duration, format_size};
+
+/// The escape sequence resetting the style of the text.
+const RESET: &str = "\x1b[0m";
+
+/// The escape sequence of the header.
+const BOLD: &str = "\x1b[1m";
+
+/// The escape sequence of the fastest algorithm of a column.
+const GREEN: &str = "\x1b[32m";
+
+/// The escape sequence of the slowest algorithm of a column.
+const RED: &str = "\x1b[31m";
+
+/// The configuration of the table of [`Measurements::to_table`].
+#[derive(Clone, Debug)]
+pub struct TableConfig {
+    sizes: Option<Vec<usize>>,
+    max_columns: usize,
+    color: Option<bool>,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        TableConfig {
+            sizes: None,
+            max_columns: 6,
+            color: None,
+        }
+    }
+}
+
+impl TableConfig {
+    /// Sets the sizes of the columns of the table. The algorithms without a point with exactly
+    /// one of these sizes have an empty cell in its column. By default, the sizes are selected
+    /// evenly among the sizes of the points, see [`TableConfig::with_max_columns`].
+    pub fn with_sizes(mut self, sizes: Vec<usize>) -> TableConfig {
+        self.sizes = Some(sizes);
+        self
+    }
+
+    /// Sets the maximum number of columns of sizes, when the sizes are selected automatically:
+    /// if the points have more sizes, the smallest, the largest and evenly spaced ones between
+    /// them are shown. By default, it is 6.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if `max_columns` is 0.
+    pub fn with_max_columns(mut self, max_columns: usize) -> TableConfig {
+        assert!(max_columns > 0, "The table must have at least one column");
+        self.max_columns = max_columns;
+        self
+    }
+
+    /// Sets whether the table is colored with ANSI escape sequences. By default, it is colored
+    /// only if the standard output is a terminal and the `NO_COLOR` environment variable is not
+    /// set.
+    pub fn with_color(mut self, color: bool) -> TableConfig {
+        self.color = Some(color);
+        self
+    }
+
+    /// Returns the sizes of the columns of the table of the given measurements.
+    fn sizes(&self, measurements: &Measurements) -> Vec<usize> {
+        if let Some(sizes) = &self.sizes {
+            return sizes.clone();
+        }
+        let mut sizes: Vec<usize> = measurements
+            .measurements
+            .iter()
+            .flat_map(|measurement| measurement.measurement.iter().map(|point| point.size))
+            .collect();
+        sizes.sort_unstable();
+        sizes.dedup();
+        if sizes.len() <= self.max_columns {
+            return sizes;
+        }
+        if self.max_columns == 1 {
+            return vec![sizes[sizes.len() - 1]];
+        }
+        let last = sizes.len() - 1;
+        (0..self.max_columns)
+            .map(|i| sizes[(i * last + (self.max_columns - 1) / 2) / (self.max_columns - 1)])
+            .collect()
+    }
+
+    /// Returns whether the table is colored.
+    fn color(&self) -> bool {
+        self.color.unwrap_or_else(|| {
+            std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+        })
+    }
+}
+
+/// The style of a cell of the table.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style {
+    /// A cell of the header
+    Header,
+    /// The time of the fastest algorithm of a column
+    Fastest,
+    /// The time of the slowest algorithm of a column
+    Slowest,
+}
+
+/// Returns the cells of the table of the given measurements, the header first, with their style.
+fn cells(measurements: &Measurements, config: &TableConfig) -> Vec<Vec<(String, Option<Style>)>> {
+    let sizes = config.sizes(measurements);
+    // The time of each algorithm on each size, if it has a point with that size
+    let times: Vec<Vec<Option<Duration>>> = measurements
+        .measurements
+        .iter()
+        .map(|measurement| {
+            sizes
+                .iter()
+                .map(|size| {
+                    measurement
+                        .measurement
+                        .iter()
+                        .find(|point| point.size == *size)
+                        .map(|point| point.time)
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut header = vec![("algorithm".to_owned(), Some(Style::Header))];
+    header.extend(
+        sizes
+            .iter()
+            .map(|size| (format!("n = {}", format_size(*size)), Some(Style::Header))),
+    );
+    rows.push(header);
+    for (measurement, algorithm_times) in measurements.measurements.iter().zip(&times) {
+        let mut row = vec![(measurement.algorithm_name.clone(), None)];
+        for (column, time) in algorithm_times.iter().enumerate() {
+            let column_times = times.iter().filter_map(|times| times[column]);
+            let (fastest, slowest) = (column_times.clone().min(), column_times.max());
+            row.push(match time {
+                None => ("-".to_owned(), None),
+                Some(time) => {
+                    let fastest = fastest.unwrap_or(*time);
+                    let factor = if fastest.is_zero() {
+                        String::from("-")
+                    } else {
+                        format!("{:.2}", time.as_secs_f64() / fastest.as_secs_f64())
+                    };
+                    let style = if Some(fastest) == slowest {
+                        None
+                    } else if *time == fastest {
+                        Some(Style::Fastest)
+                    } else if Some(*time) == slowest {
+                        Some(Style::Slowest)
+                    } else {
+                        None
+                    };
+                    (format!("{} ×{}", format_duration(*time), factor), style)
+                }
+            });
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+/// Returns the table of the given measurements.
+pub(crate) fn render(measurements: &Measurements, config: &TableConfig) -> String {
+    let rows = cells(measurements, config);
+    let columns = rows[0].len();
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].0.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let color = config.color();
+    let mut table = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, ((text, style), width))| {
+                let padding = " ".repeat(width - text.chars().count());
+                // The names of the algorithms are aligned to the left, the times to the right
+                let text = if column == 0 {
+                    format!("{}{}", text, padding)
+                } else {
+                    format!("{}{}", padding, text)
+                };
+                let escape = match style {
+                    Some(Style::Header) => BOLD,
+                    Some(Style::Fastest) => GREEN,
+                    Some(Style::Slowest) => RED,
+                    None => return text,
+                };
+                if color {
+                    format!("{}{}{}", escape, text, RESET)
+                } else {
+                    text
+                }
+            })
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+        if i == 0 {
+            let width = widths.iter().sum::<usize>() + 2 * (columns - 1);
+            table.push_str(&"-".repeat(width));
+            table.push('\n');
+        }
+    }
+    table
+}
+
+/// Returns the table of the given measurements as an HTML table, colored like the text one.
+#[cfg(feature = "evcxr")]
+pub(crate) fn render_html(measurements: &Measurements, config: &TableConfig) -> String {
+    let escape = |text: &str| {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+    let mut table = String::from("<table>\n");
+    for row in cells(measurements, config) {
+        table.push_str("<tr>");
+        for (column, (text, style)) in row.iter().enumerate() {
+            let align = match column {
+                0 => "left",
+                _ => "right",
+            };
+            let (tag, color) = match style {
+                Some(Style::Header) => ("th", None),
+                Some(Style::Fastest) => ("td", Some("green")),
+                Some(Style::Slowest) => ("td", Some("red")),
+                None => ("td", None),
+            };
+            let color = color.map_or(String::new(), |color| format!(" color: {};", color));
+            table.push_str(&format!(
+                "<{} style=\"text-align: {};{}\">{}</{}>",
+                tag,
+                align,
+                color,
+                escape(text),
+                tag
+            ));
+        }
+        table.push_str("</tr>\n");
+    }
+    table.push_str("</table>\n");
+    table
+}
@@ -0,0 +1,136 @@
+//! # Memory
+//!
+//! This module counts the allocations made by the measured algorithms and the peak of the memory
+//! they allocate, so that they can be plotted next to the times with
+//! [`stacked_plot`](crate::plot::stacked_plot).
+//!
+//! Rust can't observe the allocations of a program without replacing its global allocator, so
+//! they are only counted when the [`CountingAllocator`] is installed by the binary running the
+//! measurements:
+//!
+//! ```
+//! use chrono_probe::memory::{self, CountingAllocator};
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! fn main() {
+//!     let usage = memory::track(|| vec![0u8; 1024]).unwrap();
+//!     assert_eq!(usage.allocations, 1);
+//!     assert!(usage.peak_bytes >= 1024);
+//! }
+//! ```
+//!
+//! When it is installed, the measurement functions run each algorithm once more on every input,
+//! outside the timed region, and store the allocations and the peak of the memory in the
+//! [`Point`](crate::measurements::Point)s. The counters are shared by all the threads, so the
+//! allocations made by other threads during that run are counted as well.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+/// Whether a [`CountingAllocator`] has allocated memory, i.e. it is the global allocator.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+/// The number of allocations made since the start of the program.
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+/// The number of bytes currently allocated.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+/// The largest number of bytes allocated since the counter was last reset by [`track`].
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator counting the allocations and the allocated bytes, delegating the
+/// allocations to the [`System`] allocator.
+///
+/// See the [module documentation](self) for how to install it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    /// Records an allocation of the given number of bytes.
+    fn allocated(size: usize) {
+        INSTALLED.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    /// Records a deallocation of the given number of bytes.
+    fn deallocated(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::deallocated(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::deallocated(layout.size());
+            Self::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// The memory used by a run of a function, as counted by the [`CountingAllocator`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The number of allocations (and reallocations) made by the function
+    pub allocations: u64,
+    /// The largest number of bytes allocated by the function at the same time, in addition to
+    /// the ones already allocated when it was called
+    pub peak_bytes: usize,
+}
+
+/// Returns true if the [`CountingAllocator`] is the global allocator of the program.
+pub fn is_installed() -> bool {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        // An allocation marks the counting allocator as installed, if it is the global one
+        drop(std::hint::black_box(Box::new(0u8)));
+    }
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Runs the given function and returns the memory it used, or [`None`] if the
+/// [`CountingAllocator`] isn't the global allocator.
+///
+/// The memory allocated by the function and still allocated when it returns, like the value it
+/// returns, is included in the peak.
+pub fn track<F, O>(f: F) -> Option<MemoryUsage>
+where
+    F: FnOnce() -> O,
+{
+    if !is_installed() {
+        return None;
+    }
+    let base = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let output = f();
+    let usage = MemoryUsage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(base),
+    };
+    drop(output);
+    Some(usage)
+}
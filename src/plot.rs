@@ -8,36 +8,758 @@
 //! * a [`Measurements`] struct, which contains the results of the measurements
 //! * a [`PlotConfig`] struct, which contains the configuration for the plot
 //!
+//! To compose the plot with other charts, [`draw_time_plot`] draws it in a drawing area of
+//! [plotters] instead of a file.
+//!
+//! The deviations of a [`Measurement`] from a fitted model can be plotted with [`residual_plot`],
+//! or against the time at which each point was measured with [`residual_timestamp_plot`].
+//!
+//! The distribution of the times of a [`Measurement`] for each size can be plotted with
+//! [`box_plot`], and the distribution of the times of a single size with [`histogram`].
+//!
+//! Two runs of the same algorithms can be compared with [`comparison_plot`], showing where an
+//! algorithm got slower, or with [`regression_plot`], showing for each size whether it got
+//! slower, faster or stayed within the noise.
+//!
+//! The points can be plotted while they are measured with a [`LivePlot`].
+//!
+//! A rough plot can be printed as text to the standard output with [`terminal_plot`].
+//!
+//! The plot can also be rendered to an SVG string, without writing any file, with
+//! [`time_plot_to_string`], or to a PDF file with `time_plot_pdf` if the `pdf` feature is
+//! enabled (see the `pdf` module).
+//!
+//! The plot can be shown in an egui user interface with the widget of the `gui` module, if the
+//! `gui` feature is enabled.
+//!
+//! A [Vega-Lite](https://vega.github.io/vega-lite/) specification of the plot, with the data
+//! inlined, can be exported with [`export_vega_lite`].
+//!
+//! Several [`Measurements`] can be plotted side by side in a grid of charts with [`grid_plot`].
+//!
+//! The dimensions of a run measured for each input (see [`Metric`]) can be stacked in charts
+//! sharing the same x-axis with [`stacked_plot`].
+//!
 //! The [`PlotConfig`] struct can be created using the builder pattern, configurable option are:
 //! * [`PlotConfig::with_x_label`]: Sets the x label for the plot.
 //! * [`PlotConfig::with_y_label`]: Sets the y label for the plot.
 //! * [`PlotConfig::with_title`]: Sets the title for the plot.
 //! * [`PlotConfig::with_caption`]: Sets the caption for the plot.
 //! * [`PlotConfig::with_scale`]: Sets the scale for the plot.
+//! * [`PlotConfig::with_x_axis`]: Sets the size metric used for the x-axis.
+//! * [`PlotConfig::with_series`]: Sets which algorithms are plotted, and in which order.
+//! * [`PlotConfig::with_time_unit`]: Sets the unit of the times on the y-axis.
+//! * [`PlotConfig::with_si_ticks`]: Sets whether the labels of the ticks are written with SI
+//!   prefixes and time units, e.g. "100k" and "1.2 ms".
+//! * [`PlotConfig::with_x_formatter`] and [`PlotConfig::with_y_formatter`]: Set how the labels
+//!   of the ticks are written.
+//! * [`PlotConfig::with_error_bars`]: Draws the standard deviation of each point.
+//! * [`PlotConfig::with_envelope`]: Shades the range of the times of each size.
+//! * [`PlotConfig::with_data_file`]: Writes the plotted data to a CSV file next to the plot.
+//! * [`PlotConfig::with_max_points`]: Downsamples the series with too many points.
+//! * [`PlotConfig::with_streaming`]: Draws the series with many points without collecting them.
+//! * [`PlotConfig::with_smoothing`]: Draws a smoothed curve over the points of each series.
+//! * [`PlotConfig::with_style`] and [`PlotConfig::with_series_style`]: Sets how the points are
+//!   drawn, as a line, as markers or as markers with a trend line.
+//! * [`PlotConfig::with_series_appearance`]: Sets the color, line width, markers and dashes of
+//!   the series of an algorithm.
+//! * [`PlotConfig::with_legend_position`]: Sets the position of the legend, in a corner of the
+//!   chart or outside of it.
+//! * [`PlotConfig::with_legend_details`]: Shows the number of points and the inferred growth
+//!   rate of each algorithm in the legend.
+//! * [`PlotConfig::with_font_family`] and the `with_*_font_size` methods: Set the fonts of the
+//!   texts of the plot.
+//! * [`PlotConfig::with_dimensions`], [`PlotConfig::with_margin`] and the other layout
+//!   methods: Set the size of the plot and of its areas.
+//! * [`PlotConfig::with_reference_curves`]: Draws guide curves of canonical growth rates.
+//! * [`PlotConfig::with_horizontal_line`] and [`PlotConfig::with_vertical_line`]: Draw labeled
+//!   lines at a time or at a size, e.g. a time budget or the size of a cache.
+//! * [`PlotConfig::with_fit`]: Writes the equation of the fit of a series on the chart.
+//! * [`PlotConfig::with_theme`]: Sets the colors of the plot, e.g. a dark [`Theme`].
+//!
+//! The options can also be read from a configuration file shared by several experiments, with
+//! [`PlotConfig::from_json`], or with `PlotConfig::from_toml` if the `toml` feature is enabled.
 
-use std::fmt::{Debug, Formatter};
-use std::time::Duration;
+#[cfg(feature = "gui")]
+pub mod gui;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use plotters::prelude::*;
 
-use crate::measurements::{Measurements, Point};
+use plotters::chart::{MeshStyle, SeriesAnno};
+use plotters::coord::ranged1d::{AsRangedCoord, DefaultFormatting, Ranged, ValueFormatter};
+use plotters::coord::Shift;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+use plotters::style::Color as _;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::analysis::{Complexity, Fit};
+use crate::environment::Environment;
+use crate::measurements::{Measurement, Measurements, Point};
+use crate::progress::{Progress, ProgressHandler, Stage};
+use crate::report::{Baseline, Change};
+use crate::util::civil_date;
 
 /// Configuration for plotting.
 ///
 pub struct PlotConfig<'a> {
-    title: &'a str,
-    caption: &'a str,
-    x_label: &'a str,
-    y_label: &'a str,
+    title: Cow<'a, str>,
+    caption: Cow<'a, str>,
+    x_label: Cow<'a, str>,
+    y_label: Cow<'a, str>,
     scale: Scale,
+    x_axis: XAxis,
+    error_bars: bool,
+    envelope: bool,
+    style: SeriesStyle,
+    series_styles: Vec<(Cow<'a, str>, SeriesStyle)>,
+    series_appearances: Vec<(Cow<'a, str>, SeriesAppearance)>,
+    reference_curves: Vec<Complexity>,
+    reference_anchor: Option<(usize, Duration)>,
+    horizontal_lines: Vec<(Duration, Cow<'a, str>)>,
+    vertical_lines: Vec<(usize, Cow<'a, str>)>,
+    fits: Vec<(&'a str, Fit)>,
+    legend_position: LegendPosition,
+    font_family: Cow<'a, str>,
+    title_font_size: u32,
+    caption_font_size: Option<u32>,
+    label_font_size: Option<u32>,
+    tick_font_size: Option<u32>,
+    width: u32,
+    height: u32,
+    title_area_height: u32,
+    margin: Option<u32>,
+    label_area_sizes: Option<(u32, u32)>,
+    shared_axes: bool,
+    theme: Theme,
+    data_file: bool,
+    time_unit: Option<TimeUnit>,
+    x_formatter: Option<Box<dyn Fn(usize) -> String + 'a>>,
+    y_formatter: Option<Box<dyn Fn(Duration) -> String + 'a>>,
+    max_points: Option<usize>,
+    smoothing: Option<Smoothing>,
+    si_ticks: bool,
+    series: Option<Vec<Cow<'a, str>>>,
+    legend_details: bool,
+    streaming: Option<usize>,
 }
 
 /// The scale of the plot.
+#[derive(Deserialize)]
 pub enum Scale {
     /// Linear scale
     Linear,
     /// Double logarithmic scale
     LogLog,
+    /// Logarithmic scale on the axes whose values span more than two orders of magnitude, linear
+    /// scale on the others
+    Auto,
+}
+
+/// The size metric used for the x-axis of the plot.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum XAxis {
+    /// The size of the inputs, as returned by [`Input::get_size`](crate::input::Input::get_size)
+    #[default]
+    Size,
+    /// The size of the inputs in bytes, as returned by
+    /// [`Input::get_size_bytes`](crate::input::Input::get_size_bytes)
+    Bytes,
+}
+
+/// The unit of the times on the y-axis of the plot.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeUnit {
+    /// Nanoseconds
+    Nanos,
+    /// Microseconds
+    Micros,
+    /// Milliseconds
+    Millis,
+    /// Seconds
+    Secs,
+}
+
+/// An error that occurred while saving a plot or loading its configuration.
+#[derive(Debug)]
+pub enum PlotError {
+    /// A file or a directory could not be read, created or written
+    Io {
+        /// The path of the file or of the directory
+        path: PathBuf,
+        /// The error of the operation
+        source: io::Error,
+    },
+    /// The plot could not be drawn to its file
+    Drawing {
+        /// The path of the file
+        path: PathBuf,
+        /// The description of the error
+        reason: String,
+    },
+    /// The plot could not be rendered in memory
+    Rendering {
+        /// The description of the error
+        reason: String,
+    },
+    /// A configuration file could not be parsed, or it contains invalid settings
+    Config {
+        /// The path of the configuration file
+        path: PathBuf,
+        /// The description of the error
+        reason: String,
+    },
+    /// The x-axis is [`XAxis::Bytes`], but the inputs of an algorithm don't report their size
+    /// in bytes (see [`Input::get_size_bytes`](crate::input::Input::get_size_bytes))
+    MissingBytes {
+        /// The name of the algorithm
+        algorithm_name: String,
+    },
+    /// There is no point to plot: no algorithm matches the names set with
+    /// [`PlotConfig::with_series`], or the measurements are empty, or none of their points can
+    /// be plotted, or no chart was requested
+    NoSeries,
+    /// No point has a value in a [`Metric`] plotted by [`stacked_plot`], e.g. the allocations
+    /// weren't counted or every size was measured on a single input
+    MissingMetric {
+        /// The metric without values
+        metric: Metric,
+    },
+    /// No size of the new run can be compared with the old run in a [`regression_plot`]
+    NoComparableSizes,
+}
+
+/// How the points of a series are drawn.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SeriesStyle {
+    /// The points are connected by a line
+    #[default]
+    Line,
+    /// The points are drawn as markers, without connecting them
+    Scatter,
+    /// The points are drawn as markers, together with the power law fitted to them (see
+    /// [`Fit`])
+    ScatterWithTrend,
+}
+
+/// A smoothed curve drawn over the points of each series, to show the trend of noisy
+/// measurements.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Smoothing {
+    /// The average of the times of the given number of consecutive points, centered on each
+    /// point
+    MovingAverage(usize),
+    /// Locally weighted linear regression (LOESS) on the given fraction of the points nearest to
+    /// each point, between 0 (excluded) and 1
+    Loess(f64),
+}
+
+/// A dimension of the measurements, plotted against the size of the inputs by [`stacked_plot`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// The time taken by the algorithms
+    Time,
+    /// The memory taken by the inputs, for the inputs reporting their size in bytes
+    Bytes,
+    /// The standard deviation of the times, for the sizes with more than one input
+    StdDev,
+    /// The number of allocations made by the algorithms, if counted by the
+    /// [`CountingAllocator`](crate::memory::CountingAllocator)
+    Allocations,
+    /// The peak of the memory allocated by the algorithms, if counted by the
+    /// [`CountingAllocator`](crate::memory::CountingAllocator)
+    Memory,
+}
+
+/// The position of the legend of the plot.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LegendPosition {
+    /// In the upper left corner of the chart
+    UpperLeft,
+    /// In the upper right corner of the chart
+    UpperRight,
+    /// On the right side of the chart, vertically centered
+    #[default]
+    MiddleRight,
+    /// In the lower left corner of the chart
+    LowerLeft,
+    /// In the lower right corner of the chart
+    LowerRight,
+    /// On the right of the chart, outside of it, so that it never covers the points
+    Outside,
+}
+
+/// A color, given by its red, green and blue components.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl From<Color> for RGBColor {
+    fn from(color: Color) -> RGBColor {
+        RGBColor(color.0, color.1, color.2)
+    }
+}
+
+/// The shape of the markers drawn on the points of a series.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Marker {
+    /// A filled circle
+    Circle,
+    /// A filled square
+    Square,
+    /// A filled triangle
+    Triangle,
+    /// A cross
+    Cross,
+}
+
+/// The appearance of the series of an algorithm, set with
+/// [`PlotConfig::with_series_appearance`]. The values that are not set keep their default: a
+/// color of the palette of the theme, a line width of 3, no dashes, and markers only for the
+/// scatter styles.
+///
+/// ```
+/// use chrono_probe::plot::{Color, Marker, PlotConfig, SeriesAppearance};
+///
+/// let config = PlotConfig::default().with_series_appearance(
+///     "Quick sort",
+///     SeriesAppearance::default()
+///         .with_color(Color(200, 30, 30))
+///         .with_line_width(1)
+///         .with_marker(Marker::Square)
+///         .with_dash(6, 3),
+/// );
+/// ```
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeriesAppearance {
+    color: Option<Color>,
+    line_width: Option<u32>,
+    marker: Option<Marker>,
+    dash: Option<(u32, u32)>,
+}
+
+impl SeriesAppearance {
+    /// Sets the color of the series.
+    pub fn with_color(mut self, color: Color) -> SeriesAppearance {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets the width of the line of the series, in pixels.
+    pub fn with_line_width(mut self, line_width: u32) -> SeriesAppearance {
+        self.line_width = Some(line_width);
+        self
+    }
+
+    /// Sets the shape of the markers drawn on the points of the series.
+    pub fn with_marker(mut self, marker: Marker) -> SeriesAppearance {
+        self.marker = Some(marker);
+        self
+    }
+
+    /// Draws the line of the series dashed.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The length of the dashes, in pixels.
+    /// * `spacing` - The space between the dashes, in pixels.
+    pub fn with_dash(mut self, length: u32, spacing: u32) -> SeriesAppearance {
+        self.dash = Some((length, spacing));
+        self
+    }
+}
+
+/// The colors of a plot: the background, the grid, the texts and the palette of the series. The
+/// built-in themes are [`Theme::light`], the default, and [`Theme::dark`].
+///
+/// ```
+/// use chrono_probe::plot::{Color, PlotConfig, Theme};
+///
+/// let config = PlotConfig::default().with_theme(
+///     Theme::dark().with_palette(vec![Color(255, 160, 0), Color(0, 200, 255)]),
+/// );
+/// ```
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    background: Color,
+    grid: Color,
+    text: Color,
+    palette: Vec<Color>,
+}
+
+impl Theme {
+    /// Returns the light theme: black texts on a white background.
+    pub fn light() -> Theme {
+        Theme {
+            background: Color(255, 255, 255),
+            grid: Color(0, 0, 0),
+            text: Color(0, 0, 0),
+            palette: Palette99::COLORS
+                .iter()
+                .map(|(r, g, b)| Color(*r, *g, *b))
+                .collect(),
+        }
+    }
+
+    /// Returns the dark theme: light texts on a dark gray background, with brighter colors for
+    /// the series.
+    pub fn dark() -> Theme {
+        Theme {
+            background: Color(30, 30, 30),
+            grid: Color(255, 255, 255),
+            text: Color(220, 220, 220),
+            palette: vec![
+                Color(255, 99, 132),
+                Color(54, 162, 235),
+                Color(255, 206, 86),
+                Color(75, 192, 192),
+                Color(153, 102, 255),
+                Color(255, 159, 64),
+                Color(102, 255, 102),
+                Color(255, 102, 255),
+                Color(201, 203, 207),
+                Color(0, 230, 230),
+            ],
+        }
+    }
+
+    /// Sets the color of the background.
+    pub fn with_background(mut self, background: Color) -> Theme {
+        self.background = background;
+        self
+    }
+
+    /// Sets the color of the grid. The lines of the grid are drawn translucent.
+    pub fn with_grid(mut self, grid: Color) -> Theme {
+        self.grid = grid;
+        self
+    }
+
+    /// Sets the color of the texts and of the axes.
+    pub fn with_text(mut self, text: Color) -> Theme {
+        self.text = text;
+        self
+    }
+
+    /// Sets the palette of the series. The series without a color set with
+    /// [`PlotConfig::with_series_appearance`] take the colors of the palette in order, starting
+    /// again from the first one when they are exhausted.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the palette is empty.
+    pub fn with_palette(mut self, palette: Vec<Color>) -> Theme {
+        assert!(!palette.is_empty(), "The palette must not be empty");
+        self.palette = palette;
+        self
+    }
+
+    /// Returns the color of the palette for the series with the given index.
+    fn pick(&self, index: usize) -> RGBColor {
+        RGBColor::from(self.palette[index % self.palette.len()])
+    }
+
+    /// Returns the color of the background.
+    fn background(&self) -> RGBColor {
+        RGBColor::from(self.background)
+    }
+
+    /// Returns the color of the texts.
+    fn text(&self) -> RGBColor {
+        RGBColor::from(self.text)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::light()
+    }
+}
+
+impl XAxis {
+    /// Returns the value of the given point on this axis. The points without a size in bytes,
+    /// rejected by [`XAxis::check`], are at 0.
+    fn value(&self, point: &Point) -> usize {
+        match self {
+            XAxis::Size => point.size,
+            XAxis::Bytes => point.bytes.unwrap_or_default(),
+        }
+    }
+
+    /// Checks that all the points of the given measurement have a value on this axis.
+    ///
+    /// # Errors
+    ///
+    /// * Returns a [`PlotError::MissingBytes`] if the axis is [`XAxis::Bytes`] and a point
+    ///   doesn't report the size in bytes of its inputs.
+    fn check(&self, measurement: &Measurement) -> Result<(), PlotError> {
+        match self {
+            XAxis::Bytes if measurement.measurement.iter().any(|p| p.bytes.is_none()) => {
+                Err(PlotError::MissingBytes {
+                    algorithm_name: measurement.algorithm_name.clone(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Scale {
+    /// The minimum ratio between the maximum and the minimum value of an axis for [`Scale::Auto`]
+    /// to make it logarithmic.
+    const AUTO_LOG_RATIO: f64 = 100.0;
+
+    /// Returns whether an axis with the given range of values is logarithmic in this scale.
+    fn is_log(&self, min: f64, max: f64) -> bool {
+        match self {
+            Scale::Linear => false,
+            Scale::LogLog => true,
+            Scale::Auto => min > 0.0 && max / min > Scale::AUTO_LOG_RATIO,
+        }
+    }
+}
+
+impl Smoothing {
+    /// Returns the smoothed value of each of the given points, sorted by their x coordinate.
+    fn apply(&self, points: &[(f64, f64)]) -> Vec<f64> {
+        match *self {
+            Smoothing::MovingAverage(window) => (0..points.len())
+                .map(|i| {
+                    let start = i.saturating_sub(window / 2);
+                    let end = (start + window).min(points.len());
+                    let start = end.saturating_sub(window);
+                    let window = &points[start..end];
+                    window.iter().map(|(_, y)| y).sum::<f64>() / window.len() as f64
+                })
+                .collect(),
+            Smoothing::Loess(bandwidth) => {
+                let neighbours = ((bandwidth * points.len() as f64).ceil() as usize)
+                    .clamp(2.min(points.len()), points.len());
+                points
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &(x, _))| loess(&points[nearest(points, i, neighbours)], x))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Returns the range of the given number of points nearest to the i-th one, in the given points
+/// sorted by their x coordinate.
+fn nearest(points: &[(f64, f64)], i: usize, count: usize) -> std::ops::Range<usize> {
+    let x = points[i].0;
+    let (mut start, mut end) = (i, i + 1);
+    while end - start < count {
+        let left = (start > 0).then(|| x - points[start - 1].0);
+        let right = points.get(end).map(|(next, _)| next - x);
+        match (left, right) {
+            (Some(left), Some(right)) if left <= right => start -= 1,
+            (Some(_), None) => start -= 1,
+            _ => end += 1,
+        }
+    }
+    start..end
+}
+
+/// Returns the value at x of the line fitted to the given points with the least squares method,
+/// weighting them with the tricube function of their distance from x.
+fn loess(points: &[(f64, f64)], x: f64) -> f64 {
+    let max_distance = points
+        .iter()
+        .map(|(px, _)| (px - x).abs())
+        .fold(0.0, f64::max);
+    let weights: Vec<f64> = points
+        .iter()
+        .map(|(px, _)| match max_distance > 0.0 {
+            true => (1.0 - ((px - x).abs() / max_distance).powi(3)).powi(3),
+            false => 1.0,
+        })
+        .collect();
+    let total: f64 = weights.iter().sum();
+    let mean_x = points
+        .iter()
+        .zip(&weights)
+        .map(|((px, _), w)| w * px)
+        .sum::<f64>()
+        / total;
+    let mean_y = points
+        .iter()
+        .zip(&weights)
+        .map(|((_, py), w)| w * py)
+        .sum::<f64>()
+        / total;
+    let (sxx, sxy) = points
+        .iter()
+        .zip(&weights)
+        .fold((0.0, 0.0), |(sxx, sxy), ((px, py), w)| {
+            (
+                sxx + w * (px - mean_x).powi(2),
+                sxy + w * (px - mean_x) * (py - mean_y),
+            )
+        });
+    match sxx > 0.0 {
+        true => mean_y + sxy / sxx * (x - mean_x),
+        false => mean_y,
+    }
+}
+
+impl Metric {
+    /// Returns the value of the given point in this metric, with the times in the given unit.
+    /// The time is plotted by [`draw_chart`] instead.
+    fn value(&self, point: &Point, unit: TimeUnit) -> Option<f64> {
+        match self {
+            Metric::Time => Some(unit.as_f64(point.time)),
+            Metric::Bytes => point.bytes.map(|bytes| bytes as f64),
+            Metric::StdDev => point.stddev.map(|stddev| unit.as_f64(stddev)),
+            Metric::Allocations => point.allocations.map(|allocations| allocations as f64),
+            Metric::Memory => point.peak_memory.map(|bytes| bytes as f64),
+        }
+    }
+
+    /// Returns the description of the y-axis of this metric.
+    fn desc(&self, config: &PlotConfig, unit: TimeUnit) -> String {
+        match self {
+            Metric::Time => config.time_desc(unit),
+            Metric::Bytes if config.si_ticks => "Input size".to_owned(),
+            Metric::Bytes => "Input size (bytes)".to_owned(),
+            Metric::StdDev if config.formats_time() => "Standard deviation".to_owned(),
+            Metric::StdDev => format!("Standard deviation ({unit})"),
+            Metric::Allocations => "Allocations".to_owned(),
+            Metric::Memory if config.si_ticks => "Peak memory".to_owned(),
+            Metric::Memory => "Peak memory (bytes)".to_owned(),
+        }
+    }
+
+    /// Returns the label of the given value of the y-axis of this metric.
+    fn format(&self, value: f64, config: &PlotConfig, unit: TimeUnit) -> String {
+        match self {
+            Metric::Time | Metric::StdDev => config
+                .format_time(value, unit)
+                .unwrap_or_else(|| format!("{value:.2}")),
+            Metric::Bytes | Metric::Memory if config.si_ticks => {
+                format_bytes(value.max(0.0).round() as usize)
+            }
+            Metric::Bytes | Metric::Memory | Metric::Allocations => format!("{value:.0}"),
+        }
+    }
+}
+
+impl TimeUnit {
+    /// Returns the largest unit in which the given time is at least 1, or nanoseconds if the time
+    /// is shorter than 1 ns.
+    pub(crate) fn of(duration: Duration) -> TimeUnit {
+        if duration.as_secs() > 0 {
+            TimeUnit::Secs
+        } else if duration.as_millis() > 0 {
+            TimeUnit::Millis
+        } else if duration.as_micros() > 0 {
+            TimeUnit::Micros
+        } else {
+            TimeUnit::Nanos
+        }
+    }
+
+    /// Returns the time of the given value in this unit, 0 if the value is negative.
+    fn duration(&self, value: f64) -> Duration {
+        let nanos = match self {
+            TimeUnit::Nanos => value,
+            TimeUnit::Micros => value * 1e3,
+            TimeUnit::Millis => value * 1e6,
+            TimeUnit::Secs => value * 1e9,
+        };
+        Duration::from_nanos(nanos.max(0.0).round() as u64)
+    }
+
+    /// Returns the given time in this unit.
+    pub(crate) fn as_f64(&self, duration: Duration) -> f64 {
+        let nanos = duration.as_nanos() as f64;
+        match self {
+            TimeUnit::Nanos => nanos,
+            TimeUnit::Micros => nanos / 1e3,
+            TimeUnit::Millis => nanos / 1e6,
+            TimeUnit::Secs => nanos / 1e9,
+        }
+    }
+}
+
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeUnit::Nanos => write!(f, "ns"),
+            TimeUnit::Micros => write!(f, "μs"),
+            TimeUnit::Millis => write!(f, "ms"),
+            TimeUnit::Secs => write!(f, "s"),
+        }
+    }
+}
+
+impl Display for PlotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlotError::Io { path, source } => {
+                write!(f, "Unable to access '{}': {}", path.display(), source)
+            }
+            PlotError::Drawing { path, reason } => {
+                write!(
+                    f,
+                    "Unable to save the plot to '{}': {}",
+                    path.display(),
+                    reason
+                )
+            }
+            PlotError::Rendering { reason } => write!(f, "Unable to render the plot: {reason}"),
+            PlotError::Config { path, reason } => {
+                write!(
+                    f,
+                    "Invalid plot configuration in '{}': {}",
+                    path.display(),
+                    reason
+                )
+            }
+            PlotError::MissingBytes { algorithm_name } => write!(
+                f,
+                "The inputs of '{algorithm_name}' don't report their size in bytes, used as x-axis"
+            ),
+            PlotError::NoSeries => write!(
+                f,
+                "No point to plot: no series matched the filter, or they are empty"
+            ),
+            PlotError::MissingMetric { metric } => {
+                write!(f, "No point has a value in the metric {metric:?}")
+            }
+            PlotError::NoComparableSizes => {
+                write!(f, "No size of the new run can be compared with the old run")
+            }
+        }
+    }
+}
+
+impl Error for PlotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PlotError::Io { source, .. } => Some(source),
+            PlotError::Drawing { .. }
+            | PlotError::Rendering { .. }
+            | PlotError::Config { .. }
+            | PlotError::MissingBytes { .. }
+            | PlotError::NoSeries
+            | PlotError::MissingMetric { .. }
+            | PlotError::NoComparableSizes => None,
+        }
+    }
 }
 
 impl<'a> PlotConfig<'a> {
@@ -52,227 +774,3752 @@ impl<'a> PlotConfig<'a> {
         scale: Scale,
     ) -> PlotConfig<'a> {
         PlotConfig {
-            title,
-            caption,
-            x_label,
-            y_label,
+            title: title.into(),
+            caption: caption.into(),
+            x_label: x_label.into(),
+            y_label: y_label.into(),
             scale,
+            x_axis: XAxis::Size,
+            error_bars: false,
+            envelope: false,
+            style: SeriesStyle::Line,
+            series_styles: Vec::new(),
+            series_appearances: Vec::new(),
+            reference_curves: Vec::new(),
+            reference_anchor: None,
+            horizontal_lines: Vec::new(),
+            vertical_lines: Vec::new(),
+            fits: Vec::new(),
+            legend_position: LegendPosition::MiddleRight,
+            font_family: "sans-serif".into(),
+            title_font_size: 10,
+            caption_font_size: None,
+            label_font_size: None,
+            tick_font_size: None,
+            width: 1024,
+            height: 768,
+            title_area_height: 18,
+            margin: None,
+            label_area_sizes: None,
+            shared_axes: false,
+            theme: Theme::light(),
+            data_file: false,
+            time_unit: None,
+            x_formatter: None,
+            y_formatter: None,
+            max_points: None,
+            smoothing: None,
+            si_ticks: true,
+            series: None,
+            legend_details: false,
+            streaming: None,
         }
     }
 
+    /// Reads the configuration of a plot from a JSON file, so that the same styling can be
+    /// shared by several experiments instead of being rebuilt in code for each of them.
+    ///
+    /// The settings are named after the methods setting them, without the `with_` prefix, e.g.
+    /// `"scale": "LogLog"`, `"width": 800` and `"height": 600`, or `"theme": "dark"`. The
+    /// settings that are not in the file keep their default, and the others can still be
+    /// changed with the `with_*` methods. The formatters and the fits can only be set in code.
+    ///
+    /// ```no_run
+    /// use chrono_probe::plot::PlotConfig;
+    ///
+    /// // plot.json:
+    /// // {
+    /// //     "title": "Measured on {cpu}",
+    /// //     "scale": "LogLog",
+    /// //     "style": "ScatterWithTrend",
+    /// //     "series_appearances": { "Quick sort": { "color": [200, 30, 30] } },
+    /// //     "theme": "dark"
+    /// // }
+    /// let config = PlotConfig::from_json("plot.json")
+    ///     .unwrap()
+    ///     .with_caption("Sorting algorithms");
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the JSON file.
+    ///
+    /// # Errors
+    ///
+    /// * Returns [`PlotError::Io`] if the file can't be read.
+    /// * Returns [`PlotError::Config`] if the file is not valid JSON, if it contains unknown
+    ///   settings, or if a setting has an invalid value.
+    pub fn from_json<P: AsRef<Path>>(path: P) -> Result<PlotConfig<'a>, PlotError> {
+        PlotConfig::from_file(path.as_ref(), |content| {
+            serde_json::from_str(content).map_err(|error| error.to_string())
+        })
+    }
+
+    /// Reads the configuration of a plot from a TOML file, enabled by the `toml` feature. The
+    /// settings are the same of [`PlotConfig::from_json`].
+    ///
+    /// ```no_run
+    /// use chrono_probe::plot::PlotConfig;
+    ///
+    /// // plot.toml:
+    /// // title = "Measured on {cpu}"
+    /// // scale = "LogLog"
+    /// // width = 800
+    /// // height = 600
+    /// // theme = "dark"
+    /// //
+    /// // [series_appearances."Quick sort"]
+    /// // color = [200, 30, 30]
+    /// // dash = [6, 3]
+    /// let config = PlotConfig::from_toml("plot.toml").unwrap();
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the TOML file.
+    ///
+    /// # Errors
+    ///
+    /// * Returns [`PlotError::Io`] if the file can't be read.
+    /// * Returns [`PlotError::Config`] if the file is not valid TOML, if it contains unknown
+    ///   settings, or if a setting has an invalid value.
+    #[cfg(feature = "toml")]
+    pub fn from_toml<P: AsRef<Path>>(path: P) -> Result<PlotConfig<'a>, PlotError> {
+        PlotConfig::from_file(path.as_ref(), |content| {
+            toml::from_str(content).map_err(|error| error.to_string())
+        })
+    }
+
+    /// Reads the configuration of a plot from the given file, parsed with the given function.
+    fn from_file<F>(path: &Path, parse: F) -> Result<PlotConfig<'a>, PlotError>
+    where
+        F: FnOnce(&str) -> Result<ConfigFile, String>,
+    {
+        let content = fs::read_to_string(path).map_err(|source| PlotError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        parse(&content)
+            .and_then(ConfigFile::into_config)
+            .map_err(|reason| PlotError::Config {
+                path: path.to_path_buf(),
+                reason,
+            })
+    }
+
     /// Sets the x label for the plot.
     pub fn with_x_label(mut self, x_label: &'a str) -> PlotConfig<'a> {
-        self.x_label = x_label;
+        self.x_label = x_label.into();
         self
     }
 
     /// Sets the y label for the plot.
     pub fn with_y_label(mut self, y_label: &'a str) -> PlotConfig<'a> {
-        self.y_label = y_label;
+        self.y_label = y_label.into();
         self
     }
 
     /// Sets the title for the plot.
+    ///
+    /// The title and the caption can contain placeholders, filled when the plot is drawn:
+    /// * `{date}`: The current date, in UTC.
+    /// * `{cpu}`: The model of the CPU of the [`Environment`] of the measurements, or of the
+    ///   running machine if it is unknown, or its architecture.
+    /// * `{relative_error}`: The relative error of the measurements.
+    /// * `{resolution}`: The resolution of the clock.
+    /// * `{n_inputs}`: The number of input sizes measured.
+    /// * `{seed}`: The seed used to generate the inputs, or "none".
+    ///
+    /// The placeholders of the measurements are left as they are in the plots of a single
+    /// [`Measurement`] and in a [`LivePlot`].
+    ///
+    /// ```
+    /// use chrono_probe::plot::PlotConfig;
+    ///
+    /// let config = PlotConfig::default()
+    ///     .with_title("Measured on {cpu}, {date}")
+    ///     .with_caption("Sorting (relative error {relative_error})");
+    /// ```
     pub fn with_title(mut self, title: &'a str) -> PlotConfig<'a> {
-        self.title = title;
+        self.title = title.into();
         self
     }
 
-    /// Sets the caption for the plot.
+    /// Sets the caption for the plot. It can contain the same placeholders as the title, see
+    /// [`PlotConfig::with_title`].
     pub fn with_caption(mut self, caption: &'a str) -> PlotConfig<'a> {
-        self.caption = caption;
+        self.caption = caption.into();
         self
     }
 
-    /// Sets the scale for the plot.
+    /// Sets the scale for the plot. By default, it is [`Scale::Auto`].
     pub fn with_scale(mut self, scale: Scale) -> PlotConfig<'a> {
         self.scale = scale;
         self
     }
-}
 
-impl<'a> Default for PlotConfig<'a> {
-    fn default() -> PlotConfig<'a> {
-        PlotConfig::new(
-            "Measurements plot",
-            "Caption",
-            "Size",
-            "Time",
-            Scale::Linear,
-        )
+    /// Sets the size metric used for the x-axis of the plot. The size in bytes is known only for
+    /// the inputs implementing [`Input::get_size_bytes`](crate::input::Input::get_size_bytes).
+    pub fn with_x_axis(mut self, x_axis: XAxis) -> PlotConfig<'a> {
+        self.x_axis = x_axis;
+        self
     }
-}
-
-enum Precision {
-    Nanoseconds,
-    Microseconds,
-    Milliseconds,
-    Seconds,
-}
 
-impl Precision {
-    const MAX_U32: u128 = u32::MAX as u128;
+    /// Sets which algorithms are plotted and in which order, which is also the order of the
+    /// legend. By default, all the algorithms are plotted, in the order they were measured.
+    ///
+    /// ```
+    /// use chrono_probe::plot::PlotConfig;
+    ///
+    /// let config = PlotConfig::default().with_series(&["Quick sort", "Merge sort"]);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `algorithm_names` - The names of the algorithms to plot. The names of the algorithms
+    ///   that were not measured are ignored, if none of them was measured the plots return a
+    ///   [`PlotError::NoSeries`].
+    pub fn with_series(mut self, algorithm_names: &[&'a str]) -> PlotConfig<'a> {
+        self.series = Some(algorithm_names.iter().map(|name| (*name).into()).collect());
+        self
+    }
 
-    fn get_precision_u32(duration: Duration) -> Self {
-        if duration.as_nanos() < Self::MAX_U32 {
-            Precision::Nanoseconds
-        } else if duration.as_micros() < Self::MAX_U32 {
-            Precision::Microseconds
-        } else if duration.as_millis() < Self::MAX_U32 {
-            Precision::Milliseconds
-        } else {
-            Precision::Seconds
-        }
+    /// Sets whether to draw error bars of ±1 standard deviation around each point of the plot.
+    ///
+    /// The standard deviation is known only for the sizes with more than one input, see
+    /// [`InputBuilder::build_with_repetitions`](crate::input::InputBuilder::build_with_repetitions).
+    pub fn with_error_bars(mut self, error_bars: bool) -> PlotConfig<'a> {
+        self.error_bars = error_bars;
+        self
     }
 
-    fn as_u32(&self, duration: Duration) -> u32 {
-        match self {
-            Precision::Nanoseconds => duration.as_nanos() as u32,
-            Precision::Microseconds => duration.as_micros() as u32,
-            Precision::Milliseconds => duration.as_millis() as u32,
-            Precision::Seconds => duration.as_secs() as u32,
-        }
+    /// Sets whether to shade the envelope between the minimum and the maximum time of each size
+    /// around the series, showing the spread of the times at a glance.
+    ///
+    /// The envelope is collapsed on the sizes measured with a single input, see
+    /// [`InputBuilder::build_with_repetitions`](crate::input::InputBuilder::build_with_repetitions).
+    pub fn with_envelope(mut self, envelope: bool) -> PlotConfig<'a> {
+        self.envelope = envelope;
+        self
     }
-}
 
-impl Debug for Precision {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Precision::Nanoseconds => write!(f, "ns"),
-            Precision::Microseconds => write!(f, "μs"),
-            Precision::Milliseconds => write!(f, "ms"),
-            Precision::Seconds => write!(f, "s"),
-        }
+    /// Sets whether [`time_plot`] writes the plotted data to a CSV file next to the plot, with
+    /// the same name and the `csv` extension.
+    ///
+    /// The file has a row for each point, with the name of the algorithm, the value on the x-axis
+    /// and the time in the unit of the y-axis, in the order they are drawn. The standard
+    /// deviation of the time is added, in the same unit, if the error bars are drawn.
+    pub fn with_data_file(mut self, data_file: bool) -> PlotConfig<'a> {
+        self.data_file = data_file;
+        self
     }
-}
 
-/// Plots the data from the [`Measurements`] using [plotters].
-/// The plot is saved to the file specified by `file_name`, the file created will be an SVG file.
-///
-/// # Arguments
-///
-/// * `file_name` - The name of the file to save the plot to
-/// * `measurements` - The measurements to plot
-/// * `builder` - The builder that was used to generate the measurements
-///
-pub fn time_plot(file_name: &str, measurements: Measurements, config: &PlotConfig) {
-    let x_min = measurements.min_length() as u32;
-    let x_max = measurements.max_length() as u32;
+    /// Sets the unit of the times on the y-axis. By default, it is the largest unit in which the
+    /// longest time is at least 1.
+    pub fn with_time_unit(mut self, time_unit: TimeUnit) -> PlotConfig<'a> {
+        self.time_unit = Some(time_unit);
+        self
+    }
 
-    let max_time = measurements.max_time();
-    let y_precision = Precision::get_precision_u32(max_time);
-    let y_min = y_precision.as_u32(measurements.min_time());
-    let y_max = y_precision.as_u32(max_time);
+    /// Sets whether the labels of the ticks are written in a human-readable way, with
+    /// [`format_size`] on the x-axis and [`format_duration`] on the time axis (e.g. "500k" and
+    /// "1.2 ms"), instead of as plain numbers. By default, they are.
+    ///
+    /// When the x-axis is [`XAxis::Bytes`], its labels are written with [`format_bytes`] instead,
+    /// and the ticks are placed on multiples of powers of 2, e.g. "256 KiB", "512 KiB".
+    ///
+    /// The formatters set with [`PlotConfig::with_x_formatter`] and
+    /// [`PlotConfig::with_y_formatter`] take precedence.
+    pub fn with_si_ticks(mut self, si_ticks: bool) -> PlotConfig<'a> {
+        self.si_ticks = si_ticks;
+        self
+    }
 
-    let mut measurements = measurements.measurements;
+    /// Sets the function formatting the labels of the ticks of the x-axis, e.g. to write 100000
+    /// as "100k".
+    ///
+    /// # Arguments
+    ///
+    /// * `formatter` - The function returning the label of a value of the x-axis.
+    pub fn with_x_formatter<F>(mut self, formatter: F) -> PlotConfig<'a>
+    where
+        F: Fn(usize) -> String + 'a,
+    {
+        self.x_formatter = Some(Box::new(formatter));
+        self
+    }
 
-    // plot setup
-    let root = SVGBackend::new(file_name, (1024, 768)).into_drawing_area();
-    root.fill(&WHITE).unwrap();
+    /// Sets the function formatting the labels of the ticks of the times, e.g. to write them as
+    /// "1.5 ms".
+    ///
+    /// ```
+    /// use chrono_probe::plot::PlotConfig;
+    ///
+    /// let config = PlotConfig::default().with_y_formatter(|time| format!("{time:.1?}"));
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `formatter` - The function returning the label of a time.
+    pub fn with_y_formatter<F>(mut self, formatter: F) -> PlotConfig<'a>
+    where
+        F: Fn(Duration) -> String + 'a,
+    {
+        self.y_formatter = Some(Box::new(formatter));
+        self
+    }
 
-    let (upper, lower) = root.split_vertically(750);
+    /// Sets the maximum number of points drawn for each series. The series with more points are
+    /// downsampled with the Largest-Triangle-Three-Buckets algorithm, which keeps the shape of the
+    /// series, including its peaks, while keeping the size of the plot reasonable. The fits and
+    /// the trend lines still use all the points.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_points` - The maximum number of points of each series, at least 3.
+    pub fn with_max_points(mut self, max_points: usize) -> PlotConfig<'a> {
+        assert!(
+            max_points >= 3,
+            "The maximum number of points must be at least 3"
+        );
+        self.max_points = Some(max_points);
+        self
+    }
 
-    lower
-        .titled(
-            config.title,
-            ("sans-serif", 10).into_font().color(&BLACK.mix(0.5)),
-        )
-        .unwrap();
+    /// Streams the points of the series of the time charts to the backend in chunks of the given
+    /// number of points, instead of collecting them first, so that plotting the hundreds of
+    /// thousands of points of a giant run does not double the memory it takes.
+    ///
+    /// Only the series whose points are sorted by their value on the x-axis are streamed, and
+    /// only if they are not downsampled (see [`PlotConfig::with_max_points`]), smoothed, shaded
+    /// with an envelope or compared with a baseline, as these need all the points at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk_size` - The number of points sent to the backend at once, at least 2.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if `chunk_size` is less than 2.
+    pub fn with_streaming(mut self, chunk_size: usize) -> PlotConfig<'a> {
+        assert!(chunk_size >= 2, "The chunks must contain at least 2 points");
+        self.streaming = Some(chunk_size);
+        self
+    }
 
-    let caption = config.caption.to_string();
+    /// Sets the smoothed curve drawn over the points of each series, see [`Smoothing`]. The curve
+    /// is computed on the points that are drawn, after the downsampling set with
+    /// [`PlotConfig::with_max_points`].
+    ///
+    /// ```
+    /// use chrono_probe::plot::{PlotConfig, SeriesStyle, Smoothing};
+    ///
+    /// let config = PlotConfig::default()
+    ///     .with_style(SeriesStyle::Scatter)
+    ///     .with_smoothing(Smoothing::Loess(0.3));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the window of a [`Smoothing::MovingAverage`] is 0.
+    /// * Panics if the bandwidth of a [`Smoothing::Loess`] is not in (0, 1].
+    pub fn with_smoothing(mut self, smoothing: Smoothing) -> PlotConfig<'a> {
+        match smoothing {
+            Smoothing::MovingAverage(window) => {
+                assert!(
+                    window > 0,
+                    "The window of the moving average must be at least 1"
+                )
+            }
+            Smoothing::Loess(bandwidth) => assert!(
+                bandwidth > 0.0 && bandwidth <= 1.0,
+                "The bandwidth of the LOESS must be between 0 (excluded) and 1"
+            ),
+        }
+        self.smoothing = Some(smoothing);
+        self
+    }
+
+    /// Sets how the points of every series are drawn.
+    ///
+    /// Lines connecting noisy points can be misleading, prefer [`SeriesStyle::Scatter`] or
+    /// [`SeriesStyle::ScatterWithTrend`] when the measurements are noisy.
+    pub fn with_style(mut self, style: SeriesStyle) -> PlotConfig<'a> {
+        self.style = style;
+        self
+    }
+
+    /// Sets how the points of the series of the given algorithm are drawn, overriding the style
+    /// set with [`PlotConfig::with_style`].
+    ///
+    /// # Arguments
+    ///
+    /// * `algorithm_name` - The name of the algorithm.
+    /// * `style` - The style of its series.
+    pub fn with_series_style(
+        mut self,
+        algorithm_name: &'a str,
+        style: SeriesStyle,
+    ) -> PlotConfig<'a> {
+        self.series_styles
+            .retain(|(name, _)| name != algorithm_name);
+        self.series_styles.push((algorithm_name.into(), style));
+        self
+    }
+
+    /// Sets the canonical growth rates drawn as dashed guide curves, so that the measurements can
+    /// be compared with them visually.
+    ///
+    /// The curves are normalized to pass through the same anchor point, by default the point with
+    /// the smallest size greater than 0 of the first algorithm (see
+    /// [`PlotConfig::with_reference_anchor`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `curves` - The growth rates to draw.
+    pub fn with_reference_curves(mut self, curves: &[Complexity]) -> PlotConfig<'a> {
+        self.reference_curves = curves.to_vec();
+        self
+    }
+
+    /// Sets the fit of the series of the given algorithm, e.g. obtained with [`Fit::power_law`].
+    /// Its equation and its R² are written in a box on the chart, and it is used as the trend
+    /// line of the series if the style is [`SeriesStyle::ScatterWithTrend`].
+    ///
+    /// # Arguments
+    ///
+    /// * `algorithm_name` - The name of the algorithm.
+    /// * `fit` - The fit of its series.
+    pub fn with_fit(mut self, algorithm_name: &'a str, fit: Fit) -> PlotConfig<'a> {
+        self.fits.retain(|(name, _)| *name != algorithm_name);
+        self.fits.push((algorithm_name, fit));
+        self
+    }
+
+    /// Sets the point the reference curves pass through (see
+    /// [`PlotConfig::with_reference_curves`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The value on the x-axis of the anchor point.
+    /// * `time` - The time of the anchor point.
+    pub fn with_reference_anchor(mut self, x: usize, time: Duration) -> PlotConfig<'a> {
+        self.reference_anchor = Some((x, time));
+        self
+    }
+
+    /// Adds a labeled horizontal line at the given time on the charts of the times, so that the
+    /// measurements can be read against a constraint, e.g. the budget of a frame. The range of the
+    /// chart is extended to include the line.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use chrono_probe::plot::PlotConfig;
+    ///
+    /// let config = PlotConfig::default()
+    ///     .with_horizontal_line(Duration::from_millis(16), "16 ms frame budget")
+    ///     .with_vertical_line(256 * 1024, "L2 cache size");
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - The time of the line.
+    /// * `label` - The label written above the line, on its right end.
+    pub fn with_horizontal_line(mut self, time: Duration, label: &'a str) -> PlotConfig<'a> {
+        self.horizontal_lines.push((time, label.into()));
+        self
+    }
+
+    /// Adds a labeled vertical line at the given value of the x-axis on the charts of the times,
+    /// e.g. at the size of a cache. The range of the chart is extended to include the line.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The value on the x-axis of the line.
+    /// * `label` - The label written on the right of the line, on its top end.
+    pub fn with_vertical_line(mut self, x: usize, label: &'a str) -> PlotConfig<'a> {
+        self.vertical_lines.push((x, label.into()));
+        self
+    }
+
+    /// Sets the appearance (color, line width, markers and dashes) of the series of the given
+    /// algorithm, e.g. to conform to the style of a paper.
+    ///
+    /// # Arguments
+    ///
+    /// * `algorithm_name` - The name of the algorithm.
+    /// * `appearance` - The appearance of its series.
+    pub fn with_series_appearance(
+        mut self,
+        algorithm_name: &'a str,
+        appearance: SeriesAppearance,
+    ) -> PlotConfig<'a> {
+        self.series_appearances
+            .retain(|(name, _)| name != algorithm_name);
+        self.series_appearances
+            .push((algorithm_name.into(), appearance));
+        self
+    }
+
+    /// Sets the position of the legend.
+    pub fn with_legend_position(mut self, legend_position: LegendPosition) -> PlotConfig<'a> {
+        self.legend_position = legend_position;
+        self
+    }
+
+    /// Sets whether the legend shows, next to the name of each algorithm, the number of points
+    /// measured and the growth rate closest to them (see [`Complexity::infer`]), e.g.
+    /// "Quick sort (200 pts, ~n log n)".
+    pub fn with_legend_details(mut self, legend_details: bool) -> PlotConfig<'a> {
+        self.legend_details = legend_details;
+        self
+    }
+
+    /// Sets the font family of all the texts of the plot, e.g. "serif" or "Helvetica".
+    pub fn with_font_family(mut self, font_family: &'a str) -> PlotConfig<'a> {
+        self.font_family = font_family.into();
+        self
+    }
+
+    /// Sets the font size of the title, under the chart. The default is 10.
+    pub fn with_title_font_size(mut self, size: u32) -> PlotConfig<'a> {
+        self.title_font_size = size;
+        self
+    }
+
+    /// Sets the font size of the caption, above the chart. By default, it is 5% of
+    /// the height of the plot.
+    pub fn with_caption_font_size(mut self, size: u32) -> PlotConfig<'a> {
+        self.caption_font_size = Some(size);
+        self
+    }
+
+    /// Sets the font size of the axis labels and of the legend.
+    pub fn with_label_font_size(mut self, size: u32) -> PlotConfig<'a> {
+        self.label_font_size = Some(size);
+        self
+    }
+
+    /// Sets the font size of the tick labels of the axes.
+    pub fn with_tick_font_size(mut self, size: u32) -> PlotConfig<'a> {
+        self.tick_font_size = Some(size);
+        self
+    }
+
+    /// Sets the size of the plot, in pixels. The default is 1024×768.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The width of the plot.
+    /// * `height` - The height of the plot, including the area of the title.
+    pub fn with_dimensions(mut self, width: u32, height: u32) -> PlotConfig<'a> {
+        assert!(
+            width > 0 && height > 0,
+            "The dimensions of the plot must be greater than 0"
+        );
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the height of the area under the chart containing the title, in pixels. The default
+    /// is 18.
+    pub fn with_title_area_height(mut self, title_area_height: u32) -> PlotConfig<'a> {
+        self.title_area_height = title_area_height;
+        self
+    }
+
+    /// Sets the margin around the chart, in pixels. By default, it is 1% of the size of the
+    /// plot.
+    pub fn with_margin(mut self, margin: u32) -> PlotConfig<'a> {
+        self.margin = Some(margin);
+        self
+    }
+
+    /// Sets the sizes of the areas containing the tick labels and the description of the axes,
+    /// in pixels. By default, they are 8% of the width and 4% of the height of the plot.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - The width of the area on the left of the chart, for the y-axis.
+    /// * `bottom` - The height of the area under the chart, for the x-axis.
+    pub fn with_label_area_sizes(mut self, left: u32, bottom: u32) -> PlotConfig<'a> {
+        self.label_area_sizes = Some((left, bottom));
+        self
+    }
+
+    /// Sets whether the charts of a [`grid_plot`] share the same axes, so that they can be
+    /// compared at a glance.
+    pub fn with_shared_axes(mut self, shared_axes: bool) -> PlotConfig<'a> {
+        self.shared_axes = shared_axes;
+        self
+    }
+
+    /// Sets the colors of the plot, see [`Theme`].
+    pub fn with_theme(mut self, theme: Theme) -> PlotConfig<'a> {
+        self.theme = theme;
+        self
+    }
+
+    /// Returns the label of the given value of the x-axis.
+    fn format_x(&self, x: usize) -> String {
+        match (&self.x_formatter, self.x_axis) {
+            (Some(formatter), _) => formatter(x),
+            (None, XAxis::Size) if self.si_ticks => format_size(x),
+            (None, XAxis::Bytes) if self.si_ticks => format_bytes(x),
+            (None, _) => x.to_string(),
+        }
+    }
+
+    /// Returns whether the labels of the times are formatted as durations, with their own unit.
+    fn formats_time(&self) -> bool {
+        self.y_formatter.is_some() || self.si_ticks
+    }
+
+    /// Returns the label of the given time in the given unit, if the times are formatted as
+    /// durations.
+    fn format_time(&self, value: f64, unit: TimeUnit) -> Option<String> {
+        match &self.y_formatter {
+            Some(formatter) => Some(formatter(unit.duration(value))),
+            None if self.si_ticks => Some(format_duration(unit.duration(value))),
+            None => None,
+        }
+    }
+
+    /// Returns the description of an axis of times in the given unit. The unit is omitted if the
+    /// labels of the times already contain it.
+    fn time_desc(&self, unit: TimeUnit) -> String {
+        if self.formats_time() {
+            self.y_label.to_string()
+        } else {
+            format!("{} ({})", self.y_label, unit)
+        }
+    }
+
+    /// Returns the given measurements that are plotted, in the order they are drawn, see
+    /// [`PlotConfig::with_series`].
+    fn series<'m>(&self, measurements: &'m [Measurement]) -> Vec<&'m Measurement> {
+        match &self.series {
+            Some(algorithm_names) => algorithm_names
+                .iter()
+                .filter_map(|algorithm_name| {
+                    measurements
+                        .iter()
+                        .find(|measurement| measurement.algorithm_name == *algorithm_name)
+                })
+                .collect(),
+            None => measurements.iter().collect(),
+        }
+    }
+
+    /// Returns the number of points of the chunks in which the points of the given measurement
+    /// are streamed to the backend, if they are, see [`PlotConfig::with_streaming`].
+    fn streamed_chunk_size(
+        &self,
+        measurement: &Measurement,
+        baseline: &[Measurement],
+    ) -> Option<usize> {
+        let chunk_size = self.streaming?;
+        let points = &measurement.measurement;
+        let downsampled = self
+            .max_points
+            .is_some_and(|max_points| points.len() > max_points);
+        let compared = baseline
+            .iter()
+            .any(|before| before.algorithm_name == measurement.algorithm_name);
+        let sorted = points
+            .windows(2)
+            .all(|pair| self.x_axis.value(&pair[0]) <= self.x_axis.value(&pair[1]));
+        let streamed =
+            !downsampled && !compared && !self.envelope && self.smoothing.is_none() && sorted;
+        streamed.then_some(chunk_size)
+    }
+
+    /// Returns the points of the given measurement that are drawn, sorted by their value on the
+    /// x-axis and downsampled to the maximum number of points, if set.
+    fn plotted_points<'m>(&self, measurement: &'m Measurement) -> Vec<&'m Point> {
+        let mut points: Vec<&Point> = measurement.measurement.iter().collect();
+        points.sort_by_key(|point| self.x_axis.value(point));
+        match self.max_points {
+            Some(max_points) if points.len() > max_points => {
+                let values: Vec<(f64, f64)> = points
+                    .iter()
+                    .map(|point| (self.x_axis.value(point) as f64, point.time.as_secs_f64()))
+                    .collect();
+                lttb(&values, max_points)
+                    .into_iter()
+                    .map(|i| points[i])
+                    .collect()
+            }
+            _ => points,
+        }
+    }
+
+    /// Returns the label of the given measurement in the legend, see
+    /// [`PlotConfig::with_legend_details`].
+    fn legend_label(&self, measurement: &Measurement) -> String {
+        if !self.legend_details {
+            return measurement.algorithm_name.clone();
+        }
+        let points = measurement.measurement.len();
+        match Complexity::infer(measurement) {
+            Some(complexity) => format!(
+                "{} ({} pts, ~{})",
+                measurement.algorithm_name,
+                points,
+                complexity.growth()
+            ),
+            None => format!("{} ({} pts)", measurement.algorithm_name, points),
+        }
+    }
+
+    /// Returns the fit of the series of the given algorithm, if set.
+    fn fit_of(&self, algorithm_name: &str) -> Option<Fit> {
+        self.fits
+            .iter()
+            .find(|(name, _)| *name == algorithm_name)
+            .map(|(_, fit)| *fit)
+    }
+
+    /// Returns the appearance of the series of the given algorithm.
+    fn appearance_of(&self, algorithm_name: &str) -> SeriesAppearance {
+        self.series_appearances
+            .iter()
+            .find(|(name, _)| *name == algorithm_name)
+            .map_or_else(SeriesAppearance::default, |(_, appearance)| *appearance)
+    }
+
+    /// Returns the style of the series of the given algorithm.
+    fn style_of(&self, algorithm_name: &str) -> SeriesStyle {
+        self.series_styles
+            .iter()
+            .find(|(name, _)| *name == algorithm_name)
+            .map_or(self.style, |(_, style)| *style)
+    }
+}
+
+impl<'a> Default for PlotConfig<'a> {
+    fn default() -> PlotConfig<'a> {
+        PlotConfig::new("Measurements plot", "Caption", "Size", "Time", Scale::Auto)
+    }
+}
+
+/// The settings of a plot read from a configuration file, see [`PlotConfig::from_json`].
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    title: Option<String>,
+    caption: Option<String>,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    scale: Option<Scale>,
+    x_axis: Option<XAxis>,
+    series: Option<Vec<String>>,
+    time_unit: Option<TimeUnit>,
+    si_ticks: Option<bool>,
+    error_bars: Option<bool>,
+    envelope: Option<bool>,
+    data_file: Option<bool>,
+    max_points: Option<usize>,
+    streaming: Option<usize>,
+    smoothing: Option<Smoothing>,
+    style: Option<SeriesStyle>,
+    series_styles: BTreeMap<String, SeriesStyle>,
+    series_appearances: BTreeMap<String, SeriesAppearance>,
+    legend_position: Option<LegendPosition>,
+    legend_details: Option<bool>,
+    font_family: Option<String>,
+    title_font_size: Option<u32>,
+    caption_font_size: Option<u32>,
+    label_font_size: Option<u32>,
+    tick_font_size: Option<u32>,
+    width: Option<u32>,
+    height: Option<u32>,
+    title_area_height: Option<u32>,
+    margin: Option<u32>,
+    label_area_sizes: Option<(u32, u32)>,
+    shared_axes: Option<bool>,
+    reference_curves: Option<Vec<Complexity>>,
+    horizontal_lines: Vec<HorizontalLine>,
+    vertical_lines: Vec<VerticalLine>,
+    theme: Option<ThemeFile>,
+}
+
+/// A horizontal line in a configuration file, see [`PlotConfig::with_horizontal_line`].
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HorizontalLine {
+    /// The time of the line, in seconds
+    time: f64,
+    label: String,
+}
+
+/// A vertical line in a configuration file, see [`PlotConfig::with_vertical_line`].
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VerticalLine {
+    x: usize,
+    label: String,
+}
+
+/// A theme in a configuration file: the name of a built-in theme, or the colors of the light
+/// theme that are changed.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ThemeFile {
+    Builtin(BuiltinTheme),
+    Custom(Theme),
+}
+
+/// The name of a built-in theme in a configuration file.
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum BuiltinTheme {
+    Light,
+    Dark,
+}
+
+impl ConfigFile {
+    /// Returns the configuration with the settings of the file, and the default of the others,
+    /// or the description of the first invalid setting.
+    fn into_config<'a>(self) -> Result<PlotConfig<'a>, String> {
+        let mut config = PlotConfig::default();
+        if self.width == Some(0) || self.height == Some(0) {
+            return Err("The dimensions of the plot must be greater than 0".to_owned());
+        }
+        if self.max_points.is_some_and(|max_points| max_points < 3) {
+            return Err("The maximum number of points must be at least 3".to_owned());
+        }
+        if self.streaming.is_some_and(|chunk_size| chunk_size < 2) {
+            return Err("The chunks must contain at least 2 points".to_owned());
+        }
+        match self.smoothing {
+            Some(Smoothing::MovingAverage(0)) => {
+                return Err("The window of the moving average must be at least 1".to_owned());
+            }
+            Some(Smoothing::Loess(bandwidth)) if !(bandwidth > 0.0 && bandwidth <= 1.0) => {
+                return Err(
+                    "The bandwidth of the LOESS must be between 0 (excluded) and 1".to_owned(),
+                );
+            }
+            _ => {}
+        }
+        let theme = match self.theme {
+            Some(ThemeFile::Builtin(BuiltinTheme::Light)) => Some(Theme::light()),
+            Some(ThemeFile::Builtin(BuiltinTheme::Dark)) => Some(Theme::dark()),
+            Some(ThemeFile::Custom(theme)) if theme.palette.is_empty() => {
+                return Err("The palette must not be empty".to_owned());
+            }
+            Some(ThemeFile::Custom(theme)) => Some(theme),
+            None => None,
+        };
+
+        config.title = self.title.map_or(config.title, Cow::Owned);
+        config.caption = self.caption.map_or(config.caption, Cow::Owned);
+        config.x_label = self.x_label.map_or(config.x_label, Cow::Owned);
+        config.y_label = self.y_label.map_or(config.y_label, Cow::Owned);
+        config.scale = self.scale.unwrap_or(config.scale);
+        config.x_axis = self.x_axis.unwrap_or(config.x_axis);
+        config.series = self
+            .series
+            .map(|series| series.into_iter().map(Cow::Owned).collect());
+        config.time_unit = self.time_unit;
+        config.si_ticks = self.si_ticks.unwrap_or(config.si_ticks);
+        config.error_bars = self.error_bars.unwrap_or(config.error_bars);
+        config.envelope = self.envelope.unwrap_or(config.envelope);
+        config.data_file = self.data_file.unwrap_or(config.data_file);
+        config.max_points = self.max_points;
+        config.streaming = self.streaming;
+        config.smoothing = self.smoothing;
+        config.style = self.style.unwrap_or(config.style);
+        config.series_styles = self
+            .series_styles
+            .into_iter()
+            .map(|(name, style)| (Cow::Owned(name), style))
+            .collect();
+        config.series_appearances = self
+            .series_appearances
+            .into_iter()
+            .map(|(name, appearance)| (Cow::Owned(name), appearance))
+            .collect();
+        config.legend_position = self.legend_position.unwrap_or(config.legend_position);
+        config.legend_details = self.legend_details.unwrap_or(config.legend_details);
+        config.font_family = self.font_family.map_or(config.font_family, Cow::Owned);
+        config.title_font_size = self.title_font_size.unwrap_or(config.title_font_size);
+        config.caption_font_size = self.caption_font_size;
+        config.label_font_size = self.label_font_size;
+        config.tick_font_size = self.tick_font_size;
+        config.width = self.width.unwrap_or(config.width);
+        config.height = self.height.unwrap_or(config.height);
+        config.title_area_height = self.title_area_height.unwrap_or(config.title_area_height);
+        config.margin = self.margin;
+        config.label_area_sizes = self.label_area_sizes;
+        config.shared_axes = self.shared_axes.unwrap_or(config.shared_axes);
+        config.reference_curves = self.reference_curves.unwrap_or_default();
+        config.horizontal_lines = self
+            .horizontal_lines
+            .into_iter()
+            .map(|line| {
+                Duration::try_from_secs_f64(line.time)
+                    .map(|time| (time, Cow::Owned(line.label)))
+                    .map_err(|_| format!("Invalid time of a horizontal line: {}", line.time))
+            })
+            .collect::<Result<_, _>>()?;
+        config.vertical_lines = self
+            .vertical_lines
+            .into_iter()
+            .map(|line| (line.x, Cow::Owned(line.label)))
+            .collect();
+        config.theme = theme.unwrap_or(config.theme);
+        Ok(config)
+    }
+}
+
+/// Writes a size with an SI prefix and at most three significant digits, e.g. 500000 as "500k".
+///
+/// ```
+/// use chrono_probe::plot::format_size;
+///
+/// assert_eq!(format_size(999), "999");
+/// assert_eq!(format_size(1500), "1.5k");
+/// assert_eq!(format_size(500_000), "500k");
+/// assert_eq!(format_size(1_000_000), "1M");
+/// ```
+///
+/// # Arguments
+///
+/// * `size` - The size to write.
+pub fn format_size(size: usize) -> String {
+    const PREFIXES: [&str; 7] = ["", "k", "M", "G", "T", "P", "E"];
+    let mut value = size as f64;
+    let mut prefix = 0;
+    while round_significant(value) >= 1000.0 && prefix + 1 < PREFIXES.len() {
+        value /= 1000.0;
+        prefix += 1;
+    }
+    if prefix == 0 {
+        size.to_string()
+    } else {
+        format!("{}{}", format_significant(value), PREFIXES[prefix])
+    }
+}
+
+/// Writes a size in bytes with a binary prefix and at most three significant digits, e.g.
+/// 1536 as "1.5 KiB".
+///
+/// ```
+/// use chrono_probe::plot::format_bytes;
+///
+/// assert_eq!(format_bytes(512), "512 B");
+/// assert_eq!(format_bytes(1536), "1.5 KiB");
+/// assert_eq!(format_bytes(1 << 20), "1 MiB");
+/// assert_eq!(format_bytes(64 << 20), "64 MiB");
+/// ```
+///
+/// # Arguments
+///
+/// * `bytes` - The size to write.
+pub fn format_bytes(bytes: usize) -> String {
+    const PREFIXES: [&str; 7] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"];
+    let mut value = bytes as f64;
+    let mut prefix = 0;
+    while value >= 1024.0 && prefix + 1 < PREFIXES.len() {
+        value /= 1024.0;
+        prefix += 1;
+    }
+    if prefix == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{} {}B", format_significant(value), PREFIXES[prefix])
+    }
+}
+
+/// Writes a time in the largest unit in which it is at least 1, with at most three significant
+/// digits, e.g. "1.2 ms".
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use chrono_probe::plot::format_duration;
+///
+/// assert_eq!(format_duration(Duration::from_micros(1200)), "1.2 ms");
+/// assert_eq!(format_duration(Duration::from_nanos(250)), "250 ns");
+/// assert_eq!(format_duration(Duration::from_secs(3)), "3 s");
+/// ```
+///
+/// # Arguments
+///
+/// * `duration` - The time to write.
+pub fn format_duration(duration: Duration) -> String {
+    let unit = TimeUnit::of(duration);
+    format!("{} {}", format_significant(unit.as_f64(duration)), unit)
+}
+
+/// Fills the placeholders of a title or a caption (see [`PlotConfig::with_title`]) with the
+/// metadata of the given measurements, if any.
+fn fill_placeholders(text: &str, measurements: Option<&Measurements>) -> String {
+    if !text.contains('{') {
+        return text.to_owned();
+    }
+    let mut text = text.replace("{date}", &current_date());
+    if text.contains("{cpu}") {
+        let cpu_model = measurements
+            .and_then(|measurements| measurements.environment.as_ref())
+            .and_then(|environment| environment.cpu_model.clone())
+            .or_else(|| Environment::capture().cpu_model)
+            .unwrap_or_else(|| std::env::consts::ARCH.to_owned());
+        text = text.replace("{cpu}", &cpu_model);
+    }
+    if let Some(measurements) = measurements {
+        let n_inputs = measurements
+            .measurements
+            .iter()
+            .flat_map(|measurement| &measurement.measurement)
+            .map(|point| point.size)
+            .collect::<BTreeSet<usize>>()
+            .len();
+        let seed = match measurements.seed {
+            Some(seed) => seed.to_string(),
+            None => "none".to_owned(),
+        };
+        text = text
+            .replace("{relative_error}", &measurements.relative_error.to_string())
+            .replace("{resolution}", &format_duration(measurements.resolution))
+            .replace("{n_inputs}", &n_inputs.to_string())
+            .replace("{seed}", &seed);
+    }
+    text
+}
+
+/// Returns the current date in UTC, as YYYY-MM-DD.
+fn current_date() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+    let (year, month, day) = civil_date(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Rounds a positive value to three significant digits.
+fn round_significant(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+    let scale = 10f64.powi(2 - value.log10().floor() as i32);
+    (value * scale).round() / scale
+}
+
+/// Writes a positive value with at most three significant digits, without trailing zeros.
+pub(crate) fn format_significant(value: f64) -> String {
+    let value = round_significant(value);
+    let decimals = if value >= 100.0 {
+        0
+    } else if value >= 10.0 {
+        1
+    } else {
+        2
+    };
+    let text = format!("{value:.decimals$}");
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_owned()
+    } else {
+        text
+    }
+}
+
+/// Plots the data from the [`Measurements`] using [plotters].
+/// The plot is saved to the file specified by `file_name`, the file created will be an SVG file.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `measurements` - The measurements to plot
+/// * `builder` - The builder that was used to generate the measurements
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+///
+pub fn time_plot(
+    file_name: &str,
+    measurements: Measurements,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    render_to_file(file_name, (config.width, config.height), |root| {
+        draw_time_plot(root, &measurements, config)
+    })?;
+
+    if config.data_file {
+        write_data_file(file_name, &measurements, config)?;
+    }
+    Ok(())
+}
+
+/// Writes the data plotted by [`time_plot`] to a CSV file next to the plot, see
+/// [`PlotConfig::with_data_file`].
+fn write_data_file(
+    file_name: &str,
+    measurements: &Measurements,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    let data_file_name = Path::new(file_name).with_extension("csv");
+    let series = config.series(&measurements.measurements);
+    let unit = Bounds::of(series.iter().copied(), config)?.y_unit;
+
+    let io_error = |source| PlotError::Io {
+        path: data_file_name.clone(),
+        source,
+    };
+    let mut file = BufWriter::new(File::create(&data_file_name).map_err(io_error)?);
+    write!(
+        file,
+        "algorithm,{},{} ({})",
+        csv_field(&config.x_label),
+        csv_field(&config.y_label),
+        unit
+    )
+    .map_err(io_error)?;
+    if config.error_bars {
+        write!(file, ",stddev ({unit})").map_err(io_error)?;
+    }
+    writeln!(file).map_err(io_error)?;
+    for measurement in series {
+        for point in config.plotted_points(measurement) {
+            write!(
+                file,
+                "{},{},{}",
+                csv_field(&measurement.algorithm_name),
+                config.x_axis.value(point),
+                unit.as_f64(point.time)
+            )
+            .map_err(io_error)?;
+            if config.error_bars {
+                let stddev = point.stddev.map(|stddev| unit.as_f64(stddev).to_string());
+                write!(file, ",{}", stddev.unwrap_or_default()).map_err(io_error)?;
+            }
+            writeln!(file).map_err(io_error)?;
+        }
+    }
+    file.flush().map_err(io_error)
+}
+
+/// Returns the given text as a field of a CSV file, quoted if needed.
+pub(crate) fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+/// Draws the plot of the [`Measurements`] made by [`time_plot`] in the given drawing area, so
+/// that it can be composed with other charts in a canvas of the caller, with any backend of
+/// [plotters]. The plot fills the whole area, the dimensions set in the configuration are
+/// ignored.
+///
+/// # Arguments
+///
+/// * `area` - The area to draw the plot in
+/// * `measurements` - The measurements to plot
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError::Rendering`] if the backend fails to draw the plot.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+///
+/// # Example
+///
+/// ```
+/// use chrono_probe::input::distribution::Uniform;
+/// use chrono_probe::input::generators::vec::{U32Vec, VecOrder};
+/// use chrono_probe::input::InputBuilder;
+/// use chrono_probe::measurements::measure_mut;
+/// use chrono_probe::plot::{draw_time_plot, PlotConfig};
+/// use plotters::prelude::*;
+///
+/// fn sort(vec: &mut U32Vec) {
+///     vec.sort();
+/// }
+///
+/// let builder: InputBuilder<U32Vec, _> =
+///     InputBuilder::new(Uniform::new(10..=1000).unwrap(), VecOrder::Random);
+/// let measurements = measure_mut(&builder.build(5), &[(sort, "sort")], 0.01);
+///
+/// let mut svg = String::new();
+/// {
+///     let root = SVGBackend::with_string(&mut svg, (1600, 600)).into_drawing_area();
+///     let (left, right) = root.split_horizontally(800);
+///     draw_time_plot(&left, &measurements, &PlotConfig::default()).unwrap();
+///     right.fill(&WHITE).unwrap();
+///     root.present().unwrap();
+/// }
+/// assert!(svg.contains("Measurements plot"));
+/// ```
+pub fn draw_time_plot<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    measurements: &Measurements,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    let series = config.series(&measurements.measurements);
+    let bounds = Bounds::of(series.iter().copied(), config)?;
+
+    area.fill(&config.theme.background()).map_err(rendering)?;
+    let (_, height) = area.dim_in_pixel();
+    let upper = draw_title(area, height, config, Some(measurements))?;
+    let caption = fill_placeholders(&config.caption, Some(measurements));
+
+    draw_chart(&upper, &caption, &series, &[], config, &bounds)
+}
+
+/// Plots the data from the [`Measurements`] like [`time_plot`], returning the SVG document
+/// instead of saving it to a file, e.g. to serve it from a web server or to show it in a
+/// notebook.
+///
+/// # Arguments
+///
+/// * `measurements` - The measurements to plot
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError::Rendering`] if the plot cannot be rendered.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+///
+/// # Example
+///
+/// ```
+/// use chrono_probe::input::distribution::Uniform;
+/// use chrono_probe::input::generators::vec::{U32Vec, VecOrder};
+/// use chrono_probe::input::InputBuilder;
+/// use chrono_probe::measurements::measure_mut;
+/// use chrono_probe::plot::{time_plot_to_string, PlotConfig};
+///
+/// fn sort(vec: &mut U32Vec) {
+///     vec.sort();
+/// }
+///
+/// let builder: InputBuilder<U32Vec, _> =
+///     InputBuilder::new(Uniform::new(10..=1000).unwrap(), VecOrder::Random);
+/// let measurements = measure_mut(&builder.build(5), &[(sort, "sort")], 0.01);
+///
+/// let svg = time_plot_to_string(&measurements, &PlotConfig::default()).unwrap();
+/// assert!(svg.starts_with("<svg"));
+/// ```
+pub fn time_plot_to_string(
+    measurements: &Measurements,
+    config: &PlotConfig,
+) -> Result<String, PlotError> {
+    let mut svg = String::new();
+    {
+        let root =
+            SVGBackend::with_string(&mut svg, (config.width, config.height)).into_drawing_area();
+        draw_time_plot(&root, measurements, config)?;
+        root.present().map_err(|error| PlotError::Rendering {
+            reason: error.to_string(),
+        })?;
+    }
+    Ok(svg)
+}
+
+/// Plots the data from the [`Measurements`] like [`time_plot`], saving it to a vector PDF file
+/// instead of an SVG file, see [`PdfBackend`](pdf::PdfBackend).
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `measurements` - The measurements to plot
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+///
+#[cfg(feature = "pdf")]
+pub fn time_plot_pdf(
+    file_name: &str,
+    measurements: &Measurements,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    let root = pdf::PdfBackend::new(file_name, (config.width, config.height)).into_drawing_area();
+    draw_time_plot(&root, measurements, config)?;
+    save(&root, file_name)
+}
+
+/// Plots several [`Measurements`] side by side in a grid of charts, e.g. the best, the average and
+/// the worst case of some algorithms. The plot is saved to the file specified by `file_name`, the
+/// file created will be an SVG file.
+///
+/// Each chart has the dimensions set in the configuration, and its own caption; the title of the
+/// configuration is written once under the grid. By default, each chart has its own axes, they
+/// can be shared with [`PlotConfig::with_shared_axes`] to compare the charts at a glance.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `panels` - The caption and the measurements of each chart
+/// * `config` - The configuration of the charts
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+/// * Returns a [`PlotError::NoSeries`] if `panels` is empty.
+///
+pub fn grid_plot(
+    file_name: &str,
+    panels: &[(&str, &Measurements)],
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    if panels.is_empty() {
+        return Err(PlotError::NoSeries);
+    }
+    let columns = (panels.len() as f64).sqrt().ceil() as usize;
+    let rows = panels.len().div_ceil(columns);
+    let shared_bounds = config
+        .shared_axes
+        .then(|| {
+            Bounds::of(
+                panels
+                    .iter()
+                    .flat_map(|(_, measurements)| config.series(&measurements.measurements)),
+                config,
+            )
+        })
+        .transpose()?;
+
+    let (width, height) = (config.width * columns as u32, config.height * rows as u32);
+    render_to_file(file_name, (width, height), |root| {
+        root.fill(&config.theme.background()).map_err(rendering)?;
+        let upper = draw_title(root, height, config, Some(panels[0].1))?;
+
+        for ((caption, measurements), area) in
+            panels.iter().zip(upper.split_evenly((rows, columns)))
+        {
+            let series = config.series(&measurements.measurements);
+            let bounds = match &shared_bounds {
+                Some(bounds) => bounds.clone(),
+                None => Bounds::of(series.iter().copied(), config)?,
+            };
+            let caption = fill_placeholders(caption, Some(measurements));
+            draw_chart(&area, &caption, &series, &[], config, &bounds)?;
+        }
+        Ok(())
+    })
+}
+
+/// Plots two runs of the same algorithms, e.g. before and after an optimization, to compare
+/// them. The plot is saved to the file specified by `file_name`, the file created will be an SVG
+/// file.
+///
+/// The series of the new run are drawn as usual, the ones of the old run are dashed, with the
+/// same colors. The intervals where an algorithm got slower are shaded in red. The algorithms of
+/// the old run that are not in the new one are ignored.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `old` - The measurements of the old run
+/// * `new` - The measurements of the new run
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+///
+pub fn comparison_plot(
+    file_name: &str,
+    old: &Measurements,
+    new: &Measurements,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    let series = config.series(&new.measurements);
+    let baseline: Vec<Measurement> = old
+        .measurements
+        .iter()
+        .filter(|before| {
+            series
+                .iter()
+                .any(|after| after.algorithm_name == before.algorithm_name)
+        })
+        .cloned()
+        .collect();
+    let bounds = Bounds::of(baseline.iter().chain(series.iter().copied()), config)?;
+
+    render_to_file(file_name, (config.width, config.height), |root| {
+        root.fill(&config.theme.background()).map_err(rendering)?;
+        let upper = draw_title(root, config.height, config, Some(new))?;
+        let caption = fill_placeholders(&config.caption, Some(new));
+        draw_chart(&upper, &caption, &series, &baseline, config, &bounds)?;
+        Ok(())
+    })
+}
+
+/// Returns a [Vega-Lite](https://vega.github.io/vega-lite/) specification of the plot of the
+/// [`Measurements`] made by [`time_plot`], with the data inlined, so that it can be rendered in a
+/// browser, in Observable or in the Vega viewer of an editor.
+///
+/// The series are drawn with their style and their appearance, the error bars and the trend
+/// lines of [`SeriesStyle::ScatterWithTrend`] included, on the scale and in the unit of time of
+/// the SVG plot. The formatters of the ticks, the smoothing, the envelopes, the reference curves
+/// and the labeled lines are not exported.
+///
+/// # Arguments
+///
+/// * `measurements` - The measurements to plot
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+///
+/// # Example
+///
+/// ```
+/// use chrono_probe::input::distribution::Uniform;
+/// use chrono_probe::input::generators::vec::{U32Vec, VecOrder};
+/// use chrono_probe::input::InputBuilder;
+/// use chrono_probe::measurements::measure_mut;
+/// use chrono_probe::plot::{export_vega_lite, PlotConfig};
+///
+/// fn sort(vec: &mut U32Vec) {
+///     vec.sort();
+/// }
+///
+/// let builder: InputBuilder<U32Vec, _> =
+///     InputBuilder::new(Uniform::new(10..=1000).unwrap(), VecOrder::Random);
+/// let measurements = measure_mut(&builder.build(5), &[(sort, "sort")], 0.01);
+///
+/// let spec = export_vega_lite(&measurements, &PlotConfig::default()).unwrap();
+/// assert_eq!(spec["data"]["values"].as_array().unwrap().len(), 5);
+/// ```
+pub fn export_vega_lite(
+    measurements: &Measurements,
+    config: &PlotConfig,
+) -> Result<serde_json::Value, PlotError> {
+    let series = config.series(&measurements.measurements);
+    let bounds = Bounds::of(series.iter().copied(), config)?;
+    let y_unit = bounds.y_unit;
+    let x_field = match config.x_axis {
+        XAxis::Size => "size",
+        XAxis::Bytes => "bytes",
+    };
+    let scale = |log: bool| json!({ "type": if log { "log" } else { "linear" } });
+
+    let mut values = Vec::new();
+    let mut domain = Vec::new();
+    let mut range = Vec::new();
+    let mut layers = Vec::new();
+    for (i, measurement) in series.iter().enumerate() {
+        let name = &measurement.algorithm_name;
+        for point in config.plotted_points(measurement) {
+            values.push(json!({
+                "algorithm": name,
+                x_field: config.x_axis.value(point),
+                "time": y_unit.as_f64(point.time),
+                "stddev": point.stddev.map(|stddev| y_unit.as_f64(stddev)),
+            }));
+        }
+
+        let style = config.style_of(name);
+        let appearance = config.appearance_of(name);
+        let color = match appearance.color {
+            Some(color) => RGBColor::from(color),
+            None => config.theme.pick(i),
+        };
+        domain.push(json!(name));
+        range.push(json!(hex(color)));
+
+        let filter = json!([{ "filter": { "field": "algorithm", "equal": name } }]);
+        let shape = match appearance.marker.unwrap_or(Marker::Circle) {
+            Marker::Circle => "circle",
+            Marker::Square => "square",
+            Marker::Triangle => "triangle-up",
+            Marker::Cross => "cross",
+        };
+        let mut mark = match style {
+            SeriesStyle::Line => json!({
+                "type": "line",
+                "strokeWidth": appearance.line_width.unwrap_or(3),
+            }),
+            SeriesStyle::Scatter | SeriesStyle::ScatterWithTrend => {
+                json!({ "type": "point", "shape": shape, "filled": true })
+            }
+        };
+        if style == SeriesStyle::Line {
+            if appearance.marker.is_some() {
+                mark["point"] = json!({ "shape": shape, "filled": true });
+            }
+            if let Some((length, spacing)) = appearance.dash {
+                mark["strokeDash"] = json!([length, spacing]);
+            }
+        }
+        layers.push(json!({ "transform": filter, "mark": mark }));
+        if style == SeriesStyle::ScatterWithTrend {
+            layers.push(json!({
+                "transform": [
+                    filter[0],
+                    { "regression": "time", "on": x_field, "method": "pow", "groupby": ["algorithm"] },
+                ],
+                "mark": { "type": "line", "strokeWidth": 1 },
+            }));
+        }
+        if config.error_bars {
+            layers.push(json!({
+                "transform": [filter[0], { "filter": "isValid(datum.stddev)" }],
+                "mark": "errorbar",
+                "encoding": { "yError": { "field": "stddev" } },
+            }));
+        }
+    }
+
+    let text = hex(config.theme.text());
+    Ok(json!({
+        "$schema": "https://vega.github.io/schema/vega-lite/v5.json",
+        "title": {
+            "text": fill_placeholders(&config.caption, Some(measurements)),
+            "subtitle": fill_placeholders(&config.title, Some(measurements)),
+            "color": text,
+            "subtitleColor": text,
+        },
+        "width": config.width,
+        "height": config.height,
+        "background": hex(config.theme.background()),
+        "data": { "values": values },
+        "encoding": {
+            "x": {
+                "field": x_field,
+                "type": "quantitative",
+                "title": config.x_label,
+                "scale": scale(bounds.log_x),
+            },
+            "y": {
+                "field": "time",
+                "type": "quantitative",
+                "title": format!("{} ({})", config.y_label, y_unit),
+                "scale": scale(bounds.log_y),
+            },
+            "color": {
+                "field": "algorithm",
+                "type": "nominal",
+                "title": null,
+                "scale": { "domain": domain, "range": range },
+            },
+        },
+        "layer": layers,
+        "config": {
+            "axis": {
+                "labelColor": text,
+                "titleColor": text,
+                "gridColor": hex(RGBColor::from(config.theme.grid)),
+                "gridOpacity": 0.2,
+            },
+            "legend": { "labelColor": text },
+            "font": config.font_family,
+        },
+    }))
+}
+
+/// Returns the given color in the hexadecimal notation of CSS, e.g. "#ff8000".
+fn hex(color: RGBColor) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+/// Plots how the times of a new run of the same algorithms changed with respect to an old run,
+/// e.g. before and after an optimization, as a regression report in a single image. The plot is
+/// saved to the file specified by `file_name`, the file created will be an SVG file.
+///
+/// Each point is the relative change, in percent, of the time of a size of the new run with
+/// respect to the time of the old run at the same size, interpolated between its points. The
+/// points are red if the algorithm got slower, green if it got faster, and gray if the change is
+/// within the noise: the larger of twice the combined standard deviation of the two runs and the
+/// sum of their relative errors. The algorithms of the new run that are not in the old one, and
+/// the sizes outside of the range of the old run, are ignored.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `old` - The measurements of the old run, the baseline
+/// * `new` - The measurements of the new run
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+/// * Returns a [`PlotError::NoComparableSizes`] if no size of the new run can be compared with
+///   the old run.
+///
+pub fn regression_plot(
+    file_name: &str,
+    old: &Measurements,
+    new: &Measurements,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    let tolerance = (old.relative_error + new.relative_error) as f64;
+    let changes: Vec<(&Measurement, Vec<ChangedPoint>)> = config
+        .series(&new.measurements)
+        .into_iter()
+        .filter_map(|after| {
+            let before = old
+                .measurements
+                .iter()
+                .find(|before| before.algorithm_name == after.algorithm_name)?;
+            Some((before, after))
+        })
+        .map(|(before, after)| {
+            config.x_axis.check(before)?;
+            config.x_axis.check(after)?;
+            Ok((after, changes(before, after, tolerance, config)))
+        })
+        .collect::<Result<_, PlotError>>()?;
+    let points = || changes.iter().flat_map(|(_, points)| points);
+    let x = || points().map(|point| point.x);
+    let (Some(x_min), Some(x_max)) = (x().min(), x().max()) else {
+        return Err(PlotError::NoComparableSizes);
+    };
+    // Symmetric around 0, so that the direction of the changes is evident
+    let y_max = points()
+        .map(|point| point.percent.abs())
+        .fold(1.0, f64::max)
+        * 1.1;
+
+    render_to_file(file_name, (config.width, config.height), |root| {
+        root.fill(&config.theme.background()).map_err(rendering)?;
+        let upper = draw_title(root, config.height, config, Some(new))?;
+        let caption = fill_placeholders(&config.caption, Some(new));
+        let (area, legend_area) = split_legend(&upper, config);
+        let mut chart_builder = chart_builder(&area, &caption, config);
+        let axes = Axes {
+            x: (x_min, x_max),
+            log_x: config.scale.is_log(x_min as f64, x_max as f64),
+            y: (-y_max, y_max),
+            log_y: false,
+        };
+        let contents = ChangesChart {
+            changes: &changes,
+            config,
+        };
+        let legend = build_chart(&mut chart_builder, &axes, config, &contents)?;
+        if let Some(legend_area) = legend_area {
+            draw_legend(&legend_area, &legend, config)?;
+        }
+        Ok(())
+    })
+}
+
+/// A point of a [`regression_plot`]: the relative change of the time of a size.
+struct ChangedPoint {
+    x: u64,
+    /// The relative change, in percent
+    percent: f64,
+    change: Change,
+}
+
+/// Returns the color of the points with the given change.
+fn change_color(change: Change, config: &PlotConfig) -> RGBAColor {
+    match change {
+        Change::Regressed => REGRESSION_COLOR.mix(0.9),
+        Change::Improved => IMPROVEMENT_COLOR.mix(0.9),
+        Change::Unchanged => config.theme.text().mix(0.4),
+    }
+}
+
+/// Returns the relative changes, in percent, of the times of the plotted points of a measurement
+/// with respect to its baseline, with whether each of them is beyond the noise (see
+/// [`regression_plot`]).
+///
+/// # Arguments
+///
+/// * `before` - The measurement of the baseline
+/// * `after` - The measurement to compare with it
+/// * `tolerance` - The relative change within the noise in any case, e.g. 0.02 for 2%
+/// * `config` - The configuration of the plot
+fn changes(
+    before: &Measurement,
+    after: &Measurement,
+    tolerance: f64,
+    config: &PlotConfig,
+) -> Vec<ChangedPoint> {
+    // (x, time, standard deviation), in seconds
+    let seconds = |point: &Point| {
+        (
+            config.x_axis.value(point) as f64,
+            point.time.as_secs_f64(),
+            point.stddev.map_or(0.0, |stddev| stddev.as_secs_f64()),
+        )
+    };
+    let baseline = Baseline::new(before.measurement.iter().map(seconds));
+
+    config
+        .plotted_points(after)
+        .into_iter()
+        .filter_map(|point| {
+            let (x, time, stddev) = seconds(point);
+            let comparison = baseline.compare(x, time, stddev, tolerance)?;
+            Some(ChangedPoint {
+                x: x as u64,
+                percent: comparison.change * 100.0,
+                change: comparison.verdict,
+            })
+        })
+        .collect()
+}
+
+/// The contents of a chart of the changes of some measurements, see [`regression_plot`].
+struct ChangesChart<'c> {
+    changes: &'c [(&'c Measurement, Vec<ChangedPoint>)],
+    config: &'c PlotConfig<'c>,
+}
+
+impl ChartContents for ChangesChart<'_> {
+    fn draw<'a, DB, X, Y>(
+        &self,
+        chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    ) -> Result<Vec<(String, RGBAColor)>, PlotError>
+    where
+        DB: DrawingBackend + 'a,
+        X: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+        Y: Ranged<ValueType = f64> + ValueFormatter<f64>,
+    {
+        draw_changes(chart, self.changes, self.config)
+    }
+}
+
+/// Draws the mesh, the line of no change and the changes of each measurement on the given
+/// chart. The points of a measurement are connected by a thin line of its color, and colored by
+/// their change.
+///
+/// Returns the entries of the legend, as (label, color) pairs.
+fn draw_changes<'a, DB, X, Y>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    changes: &[(&Measurement, Vec<ChangedPoint>)],
+    config: &PlotConfig,
+) -> Result<Vec<(String, RGBAColor)>, PlotError>
+where
+    DB: DrawingBackend + 'a,
+    X: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+    Y: Ranged<ValueType = f64> + ValueFormatter<f64>,
+{
+    let x_formatter = |x: &u64| config.format_x(*x as usize);
+    let mut mesh = chart.configure_mesh();
+    mesh.x_desc(config.x_label.as_ref())
+        .y_desc("Change (%)")
+        .x_label_formatter(&x_formatter);
+    style_mesh(&mut mesh, config);
+    mesh.draw().map_err(rendering)?;
+
+    let x_range = chart.x_range();
+    chart
+        .draw_series(LineSeries::new(
+            [(x_range.start, 0.0), (x_range.end, 0.0)],
+            config.theme.text().mix(0.5).stroke_width(1),
+        ))
+        .map_err(rendering)?;
+
+    let mut legend = Vec::new();
+    for (i, (measurement, points)) in changes.iter().enumerate() {
+        let appearance = config.appearance_of(&measurement.algorithm_name);
+        let color = match appearance.color {
+            Some(color) => RGBColor::from(color).mix(0.9),
+            None => config.theme.pick(i).mix(0.9),
+        };
+        let label = config.legend_label(measurement);
+        chart
+            .draw_series(LineSeries::new(
+                points.iter().map(|point| (point.x, point.percent)),
+                color.stroke_width(1),
+            ))
+            .map_err(rendering)?
+            .label(&label)
+            .legend(move |(x, y)| PathElement::new([(x, y), (x + 10, y)], color));
+        legend.push((label, color));
+
+        let marker = appearance.marker.unwrap_or(Marker::Circle);
+        for change in Change::ALL {
+            let changed: Vec<(u64, f64)> = points
+                .iter()
+                .filter(|point| point.change == change)
+                .map(|point| (point.x, point.percent))
+                .collect();
+            draw_markers(chart, changed, marker, change_color(change, config))?;
+        }
+    }
+
+    // An empty series for the legend entry of each change that occurred
+    for change in Change::ALL {
+        if !changes
+            .iter()
+            .any(|(_, points)| points.iter().any(|point| point.change == change))
+        {
+            continue;
+        }
+        let color = change_color(change, config);
+        chart
+            .draw_series(std::iter::empty::<Circle<(u64, f64), u32>>())
+            .map_err(rendering)?
+            .label(change.label())
+            .legend(move |(x, y)| Circle::new((x + 5, y), 4, color.filled()));
+        legend.push((change.label().to_owned(), color));
+    }
+
+    draw_series_labels(chart, config)?;
+    Ok(legend)
+}
+
+/// Plots several dimensions of the [`Measurements`] in charts stacked vertically, one for each
+/// [`Metric`], sharing the same x-axis so that they are aligned. The plot is saved to the file
+/// specified by `file_name`, the file created will be an SVG file.
+///
+/// Each chart has the height set in the configuration, the caption is written above the first
+/// one. The [`Metric::Allocations`] and [`Metric::Memory`] charts need the measurements to be
+/// taken with the [`CountingAllocator`](crate::memory::CountingAllocator) as the global
+/// allocator.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `measurements` - The measurements to plot
+/// * `metrics` - The metrics to plot, from top to bottom
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+/// * Returns a [`PlotError::NoSeries`] if `metrics` is empty.
+/// * Returns a [`PlotError::MissingMetric`] if no point has a value in one of the metrics.
+///
+pub fn stacked_plot(
+    file_name: &str,
+    measurements: &Measurements,
+    metrics: &[Metric],
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    if metrics.is_empty() {
+        return Err(PlotError::NoSeries);
+    }
+    let series = config.series(&measurements.measurements);
+    let bounds = Bounds::of(series.iter().copied(), config)?;
+    // Check the metrics before creating the file, to not leave a partial plot
+    if let Some(metric) = metrics.iter().find(|metric| {
+        !series
+            .iter()
+            .flat_map(|measurement| config.plotted_points(measurement))
+            .any(|point| metric.value(point, bounds.y_unit).is_some())
+    }) {
+        return Err(PlotError::MissingMetric { metric: *metric });
+    }
+
+    let height = config.height * metrics.len() as u32;
+    render_to_file(file_name, (config.width, height), |root| {
+        root.fill(&config.theme.background()).map_err(rendering)?;
+        let upper = draw_title(root, height, config, Some(measurements))?;
+        let caption = fill_placeholders(&config.caption, Some(measurements));
+
+        for (i, (metric, area)) in metrics
+            .iter()
+            .zip(upper.split_evenly((metrics.len(), 1)))
+            .enumerate()
+        {
+            let caption = if i == 0 { caption.as_str() } else { "" };
+            match metric {
+                Metric::Time => draw_chart(&area, caption, &series, &[], config, &bounds)?,
+                _ => draw_metric(&area, caption, &series, *metric, config, &bounds)?,
+            }
+        }
+        Ok(())
+    })
+}
+
+/// A [`ProgressHandler`] plotting the points while they are measured, so that a long
+/// measurement can be followed, and stopped early if the trend of the times is already clear.
+///
+/// Every time a new point is measured, the plot is saved again to the file, at most once per the
+/// interval set with [`LivePlot::with_interval`]. The plot is drawn as by [`time_plot`].
+///
+/// Since the errors can't be returned while measuring, if the plot cannot be saved the first
+/// error is kept and the plot isn't updated anymore, while the measurements go on: call
+/// [`LivePlot::into_result`] at the end of the measurements to check it.
+///
+/// # Example
+///
+/// ```no_run
+/// use chrono_probe::input::distribution::Uniform;
+/// use chrono_probe::input::generators::vec::{U32Vec, VecOrder};
+/// use chrono_probe::input::InputBuilder;
+/// use chrono_probe::measurements::measure_mut_with_progress;
+/// use chrono_probe::plot::{LivePlot, PlotConfig};
+///
+/// fn sort(vec: &mut U32Vec) {
+///     vec.sort();
+/// }
+///
+/// let builder: InputBuilder<U32Vec, _> =
+///     InputBuilder::new(Uniform::new(1000..=1_000_000).unwrap(), VecOrder::Random);
+/// let inputs = builder.build(100);
+///
+/// let mut live_plot = LivePlot::new("results/live.svg", PlotConfig::default());
+/// let results = measure_mut_with_progress(&inputs, &[(sort, "sort")], 0.01, &mut live_plot);
+/// live_plot.into_result().unwrap();
+/// ```
+pub struct LivePlot<'a> {
+    file_name: String,
+    config: PlotConfig<'a>,
+    interval: Duration,
+    last_draw: Option<Instant>,
+    measurements: Vec<Measurement>,
+    error: Option<PlotError>,
+}
+
+impl<'a> LivePlot<'a> {
+    /// Creates a new [`LivePlot`], saving the plot to the given file at most once per second.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file to save the plot to
+    /// * `config` - The configuration of the plot
+    pub fn new(file_name: &str, config: PlotConfig<'a>) -> LivePlot<'a> {
+        LivePlot {
+            file_name: file_name.to_owned(),
+            config,
+            interval: Duration::from_secs(1),
+            last_draw: None,
+            measurements: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Sets the minimum interval between two updates of the plot. The plot is always updated when
+    /// an algorithm has been measured on all the inputs.
+    pub fn with_interval(mut self, interval: Duration) -> LivePlot<'a> {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the points measured so far, for each algorithm.
+    pub fn measurements(&self) -> &[Measurement] {
+        &self.measurements
+    }
+
+    /// Returns the points measured, for each algorithm, if the plot could always be saved.
+    ///
+    /// # Errors
+    ///
+    /// * The first error of saving the plot, if any.
+    pub fn into_result(self) -> Result<Vec<Measurement>, PlotError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.measurements),
+        }
+    }
+
+    /// Saves the plot of the points measured so far to the file.
+    fn draw(&mut self) -> Result<(), PlotError> {
+        let config = &self.config;
+        let series = config.series(&self.measurements);
+        let bounds = Bounds::of(series.iter().copied(), config)?;
+        render_to_file(&self.file_name, (config.width, config.height), |root| {
+            root.fill(&config.theme.background()).map_err(rendering)?;
+            let upper = draw_title(root, config.height, config, None)?;
+            let caption = fill_placeholders(&config.caption, None);
+            draw_chart(&upper, &caption, &series, &[], config, &bounds)
+        })?;
+        self.last_draw = Some(Instant::now());
+        Ok(())
+    }
+}
+
+impl ProgressHandler for LivePlot<'_> {
+    fn on_progress(&mut self, progress: &Progress) {
+        let (Stage::Measurement, Some(point)) = (progress.stage, progress.point) else {
+            return;
+        };
+        let algorithm_name = progress.label.unwrap_or_default();
+        match self
+            .measurements
+            .iter_mut()
+            .find(|measurement| measurement.algorithm_name == algorithm_name)
+        {
+            Some(measurement) => measurement.measurement.push(point.clone()),
+            None => self.measurements.push(Measurement {
+                algorithm_name: algorithm_name.to_owned(),
+                measurement: vec![point.clone()],
+            }),
+        }
+
+        let due = self
+            .last_draw
+            .is_none_or(|last_draw| last_draw.elapsed() >= self.interval);
+        if self.error.is_none() && (due || progress.completed == progress.total) {
+            self.error = self.draw().err();
+        }
+    }
+}
+
+/// The number of columns of the charts printed by [`terminal_plot`].
+const TERMINAL_WIDTH: usize = 72;
+
+/// The number of rows of the charts printed by [`terminal_plot`].
+const TERMINAL_HEIGHT: usize = 20;
+
+/// The characters drawing the series of the charts printed by [`terminal_plot`].
+const TERMINAL_MARKS: [char; 8] = ['*', '+', 'o', 'x', '#', '@', '%', '&'];
+
+/// Prints a rough plot of the [`Measurements`] to the standard output, as text, so that the
+/// results can be looked at in a terminal or in the logs of a CI job.
+///
+/// Each series is drawn with a different character, listed in the legend under the chart. The
+/// scale, the size metric of the x-axis and the style of the series are taken from the
+/// configuration, while the other options only apply to images.
+///
+/// # Arguments
+///
+/// * `measurements` - The measurements to plot
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+///
+pub fn terminal_plot(measurements: &Measurements, config: &PlotConfig) -> Result<(), PlotError> {
+    print!("{}", terminal_chart(measurements, config)?);
+    Ok(())
+}
+
+/// Returns the text of the chart printed by [`terminal_plot`].
+fn terminal_chart(measurements: &Measurements, config: &PlotConfig) -> Result<String, PlotError> {
+    let series = config.series(&measurements.measurements);
+    let bounds = Bounds::of(series.iter().copied(), config)?;
+    let (x_min, x_max) = bounds.x;
+    let (y_min, y_max) = bounds.y;
+    // The position of a value between the bounds, from 0 to 1
+    let position = |value: f64, min: f64, max: f64, log: bool| -> f64 {
+        let (value, min, max) = match log {
+            false => (value, min, max),
+            true => (
+                value.max(f64::MIN_POSITIVE).ln(),
+                min.max(f64::MIN_POSITIVE).ln(),
+                max.max(f64::MIN_POSITIVE).ln(),
+            ),
+        };
+        if max > min {
+            ((value - min) / (max - min)).clamp(0.0, 1.0)
+        } else {
+            0.5
+        }
+    };
 
-    let mut binding = ChartBuilder::on(&upper);
+    let mut canvas = vec![vec![' '; TERMINAL_WIDTH]; TERMINAL_HEIGHT];
+    for (i, measurement) in series.iter().enumerate() {
+        let mark = TERMINAL_MARKS[i % TERMINAL_MARKS.len()];
+        let mut points: Vec<(f64, f64)> = measurement
+            .measurement
+            .iter()
+            .map(|point| {
+                (
+                    position(
+                        config.x_axis.value(point) as f64,
+                        x_min as f64,
+                        x_max as f64,
+                        bounds.log_x,
+                    ) * (TERMINAL_WIDTH - 1) as f64,
+                    position(bounds.y_unit.as_f64(point.time), y_min, y_max, bounds.log_y)
+                        * (TERMINAL_HEIGHT - 1) as f64,
+                )
+            })
+            .collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
 
-    let chart_builder = binding
-        .caption(&caption, ("sans-serif", (5).percent_height()))
-        .set_label_area_size(LabelAreaPosition::Left, (8).percent())
-        .set_label_area_size(LabelAreaPosition::Bottom, (4).percent())
-        .margin((1).percent());
+        let mut plot = |column: f64, row: f64| {
+            canvas[TERMINAL_HEIGHT - 1 - row.round() as usize][column.round() as usize] = mark;
+        };
+        if config.style_of(&measurement.algorithm_name) == SeriesStyle::Line {
+            for pair in points.windows(2) {
+                let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1.0) as usize;
+                for step in 0..=steps {
+                    let t = step as f64 / steps as f64;
+                    plot(x0 + (x1 - x0) * t, y0 + (y1 - y0) * t);
+                }
+            }
+        }
+        for (column, row) in points {
+            plot(column, row);
+        }
+    }
 
-    match config.scale {
-        Scale::Linear => {
-            let mut chart = chart_builder
-                .build_cartesian_2d(x_min..x_max, y_min..y_max)
-                .unwrap();
-            chart
-                .configure_mesh()
-                .x_desc(config.x_label)
-                .y_desc(format!("{} ({:?})", config.x_label, y_precision))
-                .draw()
-                .unwrap();
-
-            // draw data for each algorithm
-            for (i, measurement) in measurements.iter_mut().enumerate() {
-                measurement.measurement.sort_by_key(|a| a.size);
-
-                let color = Palette99::pick(i).mix(0.9);
-                chart
-                    .draw_series(LineSeries::new(
-                        measurement
-                            .measurement
+    let y_labels = [y_max, y_min].map(|y| {
+        config
+            .format_time(y, bounds.y_unit)
+            .unwrap_or_else(|| format!("{y:.2}"))
+    });
+    let gutter = y_labels.iter().map(String::len).max().unwrap_or(0);
+    let mut chart = format!(
+        "{}\n{}\n",
+        fill_placeholders(&config.title, Some(measurements)),
+        fill_placeholders(&config.caption, Some(measurements))
+    );
+    chart.push_str(&config.time_desc(bounds.y_unit));
+    chart.push('\n');
+    for (row, line) in canvas.iter().enumerate() {
+        let label = match row {
+            0 => y_labels[0].as_str(),
+            _ if row == TERMINAL_HEIGHT - 1 => y_labels[1].as_str(),
+            _ => "",
+        };
+        let line: String = line.iter().collect();
+        chart.push_str(&format!("{label:>gutter$} |{}\n", line.trim_end()));
+    }
+    chart.push_str(&format!(
+        "{:>gutter$} +{}\n",
+        "",
+        "-".repeat(TERMINAL_WIDTH)
+    ));
+    let (x_min, x_max) = (
+        config.format_x(x_min as usize),
+        config.format_x(x_max as usize),
+    );
+    chart.push_str(&format!(
+        "{:>gutter$}  {x_min}{x_max:>width$}\n",
+        "",
+        width = TERMINAL_WIDTH.saturating_sub(x_min.len())
+    ));
+    let x_label = format!("{:>gutter$}  {:^TERMINAL_WIDTH$}", "", config.x_label);
+    chart.push_str(x_label.trim_end());
+    chart.push('\n');
+    for (i, measurement) in series.iter().enumerate() {
+        chart.push_str(&format!(
+            "{:>gutter$}  {} {}\n",
+            "",
+            TERMINAL_MARKS[i % TERMINAL_MARKS.len()],
+            measurement.algorithm_name
+        ));
+    }
+    Ok(chart)
+}
+
+/// Plots the residuals of a fit of a [`Measurement`], i.e. the relative difference between the
+/// measured times and the times predicted by the fit, as a function of the size. The plot is saved
+/// to the file specified by `file_name`, the file created will be an SVG file.
+///
+/// A global fit can hide systematic deviations, like a jump in the times when the inputs stop
+/// fitting in a cache: they show up as a pattern in the residuals, which should otherwise be
+/// scattered around 0. The scale of the configuration only applies to the x-axis, the residuals
+/// are always on a linear scale. The points where the fit predicts a time of 0 are skipped.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `measurement` - The measurement of an algorithm
+/// * `fit` - The fit of the measurement, e.g. obtained with [`Fit::power_law`]
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+/// * Returns a [`PlotError::NoSeries`] if the fit predicts a time of 0 for all the points.
+///
+pub fn residual_plot(
+    file_name: &str,
+    measurement: &Measurement,
+    fit: &Fit,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    assert!(
+        !measurement.measurement.is_empty(),
+        "The measurement must contain at least one point"
+    );
+    config.x_axis.check(measurement)?;
+    let residuals = residuals(measurement, fit, |point| {
+        Some(config.x_axis.value(point) as u64)
+    });
+    draw_residual_plot(
+        file_name,
+        measurement,
+        &residuals,
+        config,
+        ResidualAxis::Size,
+    )
+}
+
+/// Plots the residuals of a fit of a [`Measurement`] like [`residual_plot`], but as a function
+/// of the time at which each point was measured, since the start of the run (see
+/// [`Point::timestamp`]). The plot is saved to the file specified by `file_name`, the file
+/// created will be an SVG file.
+///
+/// A drift of the residuals over time points to a machine whose performance changed during the
+/// run, e.g. because of thermal throttling or of another process, rather than to the algorithm.
+/// Both axes are always on a linear scale. The points without a timestamp, or where the fit
+/// predicts a time of 0, are skipped.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `measurement` - The measurement of an algorithm
+/// * `fit` - The fit of the measurement, e.g. obtained with [`Fit::power_law`]
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+/// * Returns a [`PlotError::NoSeries`] if no point has a timestamp and a predicted time greater
+///   than 0.
+///
+pub fn residual_timestamp_plot(
+    file_name: &str,
+    measurement: &Measurement,
+    fit: &Fit,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    let residuals = residuals(measurement, fit, |point| {
+        Some(point.timestamp?.as_nanos() as u64)
+    });
+    draw_residual_plot(
+        file_name,
+        measurement,
+        &residuals,
+        config,
+        ResidualAxis::Timestamp,
+    )
+}
+
+/// The quantity on the x-axis of a residual plot.
+#[derive(Clone, Copy)]
+enum ResidualAxis {
+    /// The x-axis of the configuration
+    Size,
+    /// The time at which the points were measured, in nanoseconds
+    Timestamp,
+}
+
+/// Returns the residuals of the fit, in percent, of the points of the measurement with a value
+/// on the x-axis and a predicted time greater than 0.
+fn residuals<X>(measurement: &Measurement, fit: &Fit, x: X) -> Vec<(u64, f64)>
+where
+    X: Fn(&Point) -> Option<u64>,
+{
+    measurement
+        .measurement
+        .iter()
+        .filter_map(|point| {
+            let predicted = fit.predict(point.size as f64).as_secs_f64();
+            // The relative difference is undefined where the fit predicts no time
+            if predicted == 0.0 || !predicted.is_finite() {
+                return None;
+            }
+            let residual = (point.time.as_secs_f64() - predicted) / predicted * 100.0;
+            Some((x(point)?, residual))
+        })
+        .collect()
+}
+
+/// Draws the given residuals of a measurement and saves the plot to the file.
+fn draw_residual_plot(
+    file_name: &str,
+    measurement: &Measurement,
+    residuals: &[(u64, f64)],
+    config: &PlotConfig,
+    axis: ResidualAxis,
+) -> Result<(), PlotError> {
+    let x = || residuals.iter().map(|(x, _)| *x);
+    let (Some(x_min), Some(x_max)) = (x().min(), x().max()) else {
+        return Err(PlotError::NoSeries);
+    };
+    // Symmetric around 0, so that the sign of the residuals is evident
+    let y_max = residuals
+        .iter()
+        .map(|(_, residual)| residual.abs())
+        .fold(1.0, f64::max)
+        * 1.1;
+
+    render_to_file(file_name, (config.width, config.height), |root| {
+        root.fill(&config.theme.background()).map_err(rendering)?;
+        let upper = draw_title(root, config.height, config, None)?;
+        let caption = format!("{} (residuals)", measurement.algorithm_name);
+        let mut chart_builder = chart_builder(&upper, &caption, config);
+
+        let appearance = config.appearance_of(&measurement.algorithm_name);
+        let color = match appearance.color {
+            Some(color) => RGBColor::from(color).mix(0.9),
+            None => config.theme.pick(0).mix(0.9),
+        };
+        let marker = appearance.marker.unwrap_or(Marker::Circle);
+        let log_x = match axis {
+            ResidualAxis::Size => config.scale.is_log(x_min as f64, x_max as f64),
+            ResidualAxis::Timestamp => false,
+        };
+        let axes = Axes {
+            x: (x_min, x_max),
+            log_x,
+            y: (-y_max, y_max),
+            log_y: false,
+        };
+        let contents = ResidualsChart {
+            residuals,
+            config,
+            axis,
+            marker,
+            color,
+        };
+        build_chart(&mut chart_builder, &axes, config, &contents)?;
+        Ok(())
+    })
+}
+
+/// The contents of the chart of a [`residual_plot`] or of a [`residual_timestamp_plot`].
+struct ResidualsChart<'r> {
+    residuals: &'r [(u64, f64)],
+    config: &'r PlotConfig<'r>,
+    axis: ResidualAxis,
+    marker: Marker,
+    color: RGBAColor,
+}
+
+impl ChartContents for ResidualsChart<'_> {
+    fn draw<'a, DB, X, Y>(
+        &self,
+        chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    ) -> Result<Vec<(String, RGBAColor)>, PlotError>
+    where
+        DB: DrawingBackend + 'a,
+        X: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+        Y: Ranged<ValueType = f64> + ValueFormatter<f64>,
+    {
+        draw_residuals(
+            chart,
+            self.residuals,
+            self.config,
+            self.axis,
+            self.marker,
+            self.color,
+        )?;
+        Ok(Vec::new())
+    }
+}
+
+/// Draws the mesh, the line of the residuals equal to 0 and the residuals on the given chart.
+fn draw_residuals<'a, DB, X, Y>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    residuals: &[(u64, f64)],
+    config: &PlotConfig,
+    axis: ResidualAxis,
+    marker: Marker,
+    color: RGBAColor,
+) -> Result<(), PlotError>
+where
+    DB: DrawingBackend + 'a,
+    X: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+    Y: Ranged<ValueType = f64> + ValueFormatter<f64>,
+{
+    let x_formatter = |x: &u64| match axis {
+        ResidualAxis::Size => config.format_x(*x as usize),
+        ResidualAxis::Timestamp => format_duration(Duration::from_nanos(*x)),
+    };
+    let x_label = match axis {
+        ResidualAxis::Size => config.x_label.as_ref(),
+        ResidualAxis::Timestamp => "Time since the start of the run",
+    };
+    let mut mesh = chart.configure_mesh();
+    mesh.x_desc(x_label)
+        .y_desc("Residual (%)")
+        .x_label_formatter(&x_formatter);
+    style_mesh(&mut mesh, config);
+    mesh.draw().map_err(rendering)?;
+
+    let x_range = chart.x_range();
+    chart
+        .draw_series(LineSeries::new(
+            [(x_range.start, 0.0), (x_range.end, 0.0)],
+            config.theme.text().mix(0.5).stroke_width(1),
+        ))
+        .map_err(rendering)?;
+    draw_markers(chart, residuals.iter().copied(), marker, color)
+}
+
+/// Plots the distribution of the times of a [`Measurement`] for each size, as box plots of the
+/// times of the single inputs (see [`Point::samples`]). The plot is saved to the file specified by
+/// `file_name`, the file created will be an SVG file.
+///
+/// The average time of a size can hide how the times are distributed, e.g. a few inputs being
+/// much slower than the others because of some occasional event. Each box goes from the first to
+/// the third quartile of the times, with the median marked inside, and the whiskers reach the
+/// fences at 1.5 times the interquartile range from the box: the times beyond the fences are
+/// drawn as single points. The sizes are evenly spaced on the x-axis, and the times are always on
+/// a linear scale. The points without samples are drawn as a box collapsed on their average time.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `measurement` - The measurement of an algorithm
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+/// * Returns a [`PlotError::MissingBytes`] if the x-axis is [`XAxis::Bytes`] and the inputs of
+///   an algorithm don't report their size in bytes.
+///
+pub fn box_plot(
+    file_name: &str,
+    measurement: &Measurement,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    assert!(
+        !measurement.measurement.is_empty(),
+        "The measurement must contain at least one point"
+    );
+    config.x_axis.check(measurement)?;
+    let mut points: Vec<&Point> = measurement.measurement.iter().collect();
+    points.sort_by_key(|point| config.x_axis.value(point));
+    let samples: Vec<&[Duration]> = points
+        .iter()
+        .map(|point| match point.samples.is_empty() {
+            true => std::slice::from_ref(&point.time),
+            false => point.samples.as_slice(),
+        })
+        .collect();
+    let max_time = samples.iter().flat_map(|s| s.iter()).max().unwrap();
+    let unit = config.time_unit.unwrap_or_else(|| TimeUnit::of(*max_time));
+    let boxes: Vec<(Quartiles, Vec<f32>)> = samples
+        .iter()
+        .map(|samples| {
+            let values: Vec<f32> = samples.iter().map(|t| unit.as_f64(*t) as f32).collect();
+            (Quartiles::new(&values), values)
+        })
+        .collect();
+    // The range of the times and of the whiskers
+    let (y_min, y_max) = boxes.iter().fold(
+        (f32::INFINITY, f32::NEG_INFINITY),
+        |(y_min, y_max), (quartiles, values)| {
+            let [lower_fence, .., upper_fence] = quartiles.values();
+            let low = values.iter().copied().fold(lower_fence, f32::min);
+            let high = values.iter().copied().fold(upper_fence, f32::max);
+            (y_min.min(low), y_max.max(high))
+        },
+    );
+    let y_margin = ((y_max - y_min) * 0.05).max(f32::EPSILON);
+
+    render_to_file(file_name, (config.width, config.height), |root| {
+        root.fill(&config.theme.background()).map_err(rendering)?;
+        let upper = draw_title(root, config.height, config, None)?;
+        let caption = format!("{} (distribution of the times)", measurement.algorithm_name);
+        let mut chart = chart_builder(&upper, &caption, config)
+            .build_cartesian_2d(
+                (0..points.len() as u32).into_segmented(),
+                (y_min - y_margin)..(y_max + y_margin),
+            )
+            .map_err(rendering)?;
+
+        let x_label_formatter = |value: &SegmentValue<u32>| match value {
+            SegmentValue::CenterOf(i) | SegmentValue::Exact(i) => points
+                .get(*i as usize)
+                .map(|point| config.format_x(config.x_axis.value(point)))
+                .unwrap_or_default(),
+            SegmentValue::Last => String::new(),
+        };
+        let y_label_formatter = |y: &f32| {
+            config
+                .format_time(*y as f64, unit)
+                .unwrap_or_else(|| y.to_string())
+        };
+        let mut mesh = chart.configure_mesh();
+        mesh.disable_x_mesh()
+            .x_desc(config.x_label.as_ref())
+            .y_desc(config.time_desc(unit))
+            .x_label_formatter(&x_label_formatter)
+            .y_label_formatter(&y_label_formatter);
+        style_mesh(&mut mesh, config);
+        mesh.draw().map_err(rendering)?;
+
+        let color = match config.appearance_of(&measurement.algorithm_name).color {
+            Some(color) => RGBColor::from(color).to_rgba(),
+            None => config.theme.pick(0).to_rgba(),
+        };
+        chart
+            .draw_series(boxes.iter().enumerate().map(|(i, (quartiles, _))| {
+                Boxplot::new_vertical(SegmentValue::CenterOf(i as u32), quartiles).style(color)
+            }))
+            .map_err(rendering)?;
+        // The outliers, beyond the whiskers
+        chart
+            .draw_series(
+                boxes
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, (quartiles, values))| {
+                        let [lower_fence, .., upper_fence] = quartiles.values();
+                        values
                             .iter()
-                            .map(|&Point { size, time, .. }| {
-                                (size as u32, y_precision.as_u32(time))
-                            }),
-                        color.stroke_width(3),
-                    ))
-                    .unwrap()
-                    .label(&measurement.algorithm_name)
-                    .legend(move |(x, y)| {
-                        Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled())
-                    });
+                            .filter(move |value| !(lower_fence..=upper_fence).contains(*value))
+                            .map(move |value| (SegmentValue::CenterOf(i as u32), *value))
+                    })
+                    .map(|point| Circle::new(point, 2, color.filled())),
+            )
+            .map_err(rendering)?;
+        Ok(())
+    })
+}
+
+/// Plots the histogram of the times of the inputs of a [`Measurement`] with the given size (see
+/// [`Point::samples`]). The plot is saved to the file specified by `file_name`, the file created
+/// will be an SVG file.
+///
+/// The histogram shows whether the times of a size are noisy, or split in separate groups, e.g.
+/// because some inputs hit a slow path. The number of bins is the square root of the number of
+/// times, up to 50.
+///
+/// # Arguments
+///
+/// * `file_name` - The name of the file to save the plot to
+/// * `measurement` - The measurement of an algorithm
+/// * `size` - The size of the inputs, as returned by
+///   [`Input::get_size`](crate::input::Input::get_size)
+/// * `config` - The configuration of the plot
+///
+/// # Errors
+///
+/// * Returns a [`PlotError`] if the plot cannot be saved to the file. The missing parent
+///   directories of the file are created.
+///
+/// # Panics
+///
+/// * Panics if the measurement has no point with the given size.
+pub fn histogram(
+    file_name: &str,
+    measurement: &Measurement,
+    size: usize,
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    let times: Vec<Duration> = measurement
+        .measurement
+        .iter()
+        .filter(|point| point.size == size)
+        .flat_map(|point| match point.samples.is_empty() {
+            true => vec![point.time],
+            false => point.samples.clone(),
+        })
+        .collect();
+    assert!(
+        !times.is_empty(),
+        "The measurement has no point with size {size}"
+    );
+    let unit = config
+        .time_unit
+        .unwrap_or_else(|| TimeUnit::of(*times.iter().max().unwrap()));
+    let values: Vec<f32> = times.iter().map(|time| unit.as_f64(*time) as f32).collect();
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+    let bins = ((values.len() as f64).sqrt().ceil() as usize).clamp(1, 50);
+    let width = ((max - min) / bins as f32).max(f32::EPSILON);
+    let mut counts = vec![0u32; bins];
+    for value in &values {
+        let bin = (((value - min) / width) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    let max_count = *counts.iter().max().unwrap();
+
+    render_to_file(file_name, (config.width, config.height), |root| {
+        root.fill(&config.theme.background()).map_err(rendering)?;
+        let upper = draw_title(root, config.height, config, None)?;
+        let caption = format!("{} (times with size {})", measurement.algorithm_name, size);
+        let mut chart = chart_builder(&upper, &caption, config)
+            .build_cartesian_2d(min..(min + width * bins as f32), 0..max_count + 1)
+            .map_err(rendering)?;
+
+        let x_label_formatter = |x: &f32| {
+            config
+                .format_time(*x as f64, unit)
+                .unwrap_or_else(|| x.to_string())
+        };
+        let mut mesh = chart.configure_mesh();
+        mesh.disable_x_mesh()
+            .x_desc(config.time_desc(unit))
+            .y_desc("Inputs")
+            .x_label_formatter(&x_label_formatter);
+        style_mesh(&mut mesh, config);
+        mesh.draw().map_err(rendering)?;
+
+        let color = match config.appearance_of(&measurement.algorithm_name).color {
+            Some(color) => RGBColor::from(color).to_rgba(),
+            None => config.theme.pick(0).to_rgba(),
+        };
+        chart
+            .draw_series(counts.iter().enumerate().map(|(i, count)| {
+                let start = min + width * i as f32;
+                Rectangle::new(
+                    [(start, 0), (start + width, *count)],
+                    color.mix(0.6).filled(),
+                )
+            }))
+            .map_err(rendering)?;
+        Ok(())
+    })
+}
+
+/// Draws a plot of the given dimensions with the given function, and saves it to the SVG file
+/// specified by `file_name`.
+fn render_to_file<F>(file_name: &str, dimensions: (u32, u32), draw: F) -> Result<(), PlotError>
+where
+    F: FnOnce(&DrawingArea<SVGBackend, Shift>) -> Result<(), PlotError>,
+{
+    let root = SVGBackend::new(file_name, dimensions).into_drawing_area();
+    draw(&root)?;
+    save(&root, file_name)
+}
+
+/// Writes the plot drawn in the given root area to its file, creating the missing parent
+/// directories. The file is presented explicitly, so that an IO failure is not ignored silently
+/// when the backend is dropped.
+fn save<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    file_name: &str,
+) -> Result<(), PlotError> {
+    let path = Path::new(file_name);
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|source| PlotError::Io {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+    root.present().map_err(|error| PlotError::Drawing {
+        path: path.to_path_buf(),
+        reason: error.to_string(),
+    })
+}
+
+/// Returns the [`PlotError::Rendering`] of an error of the backend while drawing a chart.
+fn rendering<E: std::fmt::Display>(error: E) -> PlotError {
+    PlotError::Rendering {
+        reason: error.to_string(),
+    }
+}
+
+/// The ranges of the axes of a chart.
+#[derive(Clone)]
+struct Bounds {
+    x: (u64, u64),
+    y: (f64, f64),
+    y_unit: TimeUnit,
+    log_x: bool,
+    log_y: bool,
+}
+
+impl Bounds {
+    /// Returns the ranges of the axes containing all the points of the given measurements and the
+    /// lines set in the configuration, the unit of the times and which axes are logarithmic.
+    ///
+    /// # Errors
+    ///
+    /// * Returns a [`PlotError::MissingBytes`] if a point has no value on the x-axis.
+    /// * Returns a [`PlotError::NoSeries`] if the measurements have no point.
+    fn of<'m, M>(measurements: M, config: &PlotConfig) -> Result<Bounds, PlotError>
+    where
+        M: IntoIterator<Item = &'m Measurement>,
+    {
+        let measurements: Vec<&Measurement> = measurements.into_iter().collect();
+        for measurement in &measurements {
+            config.x_axis.check(measurement)?;
+        }
+        let points = || measurements.iter().flat_map(|m| &m.measurement);
+        let x = || points().map(|point| config.x_axis.value(point));
+        let (Some(x_min), Some(x_max)) = (x().min(), x().max()) else {
+            return Err(PlotError::NoSeries);
+        };
+        let (x_min, x_max) = config
+            .vertical_lines
+            .iter()
+            .fold((x_min, x_max), |(min, max), (x, _)| {
+                (min.min(*x), max.max(*x))
+            });
+
+        let times = || {
+            let lines = config.horizontal_lines.iter().map(|(time, _)| *time);
+            points().map(|point| point.time).chain(lines)
+        };
+        let min_time = times().min().unwrap_or_default();
+        let max_time = times().max().unwrap_or_default();
+        let y_unit = config.time_unit.unwrap_or_else(|| TimeUnit::of(max_time));
+        let (y_min, y_max) = (y_unit.as_f64(min_time), y_unit.as_f64(max_time));
+        Ok(Bounds {
+            x: (x_min as u64, x_max as u64),
+            y: (y_min, y_max),
+            y_unit,
+            log_x: config.scale.is_log(x_min as f64, x_max as f64),
+            log_y: config.scale.is_log(y_min, y_max),
+        })
+    }
+}
+
+/// Writes the title of the plot under the given area, and returns the rest of the area.
+fn draw_title<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    height: u32,
+    config: &PlotConfig,
+    measurements: Option<&Measurements>,
+) -> Result<DrawingArea<DB, Shift>, PlotError> {
+    let (upper, lower) = area.split_vertically(height.saturating_sub(config.title_area_height));
+    lower
+        .titled(
+            &fill_placeholders(&config.title, measurements),
+            (config.font_family.as_ref(), config.title_font_size)
+                .into_font()
+                .color(&config.theme.text().mix(0.5)),
+        )
+        .map_err(rendering)?;
+    Ok(upper)
+}
+
+/// Returns a [`ChartBuilder`] on the given area, with the caption, the label areas and the margin
+/// set in the configuration.
+fn chart_builder<'a, 'b, DB: DrawingBackend>(
+    area: &'a DrawingArea<DB, Shift>,
+    caption: &str,
+    config: &'b PlotConfig<'_>,
+) -> ChartBuilder<'a, 'b, DB> {
+    let mut chart_builder = ChartBuilder::on(area);
+    let color = config.theme.text();
+    match config.caption_font_size {
+        Some(size) => chart_builder.caption(
+            caption,
+            (config.font_family.as_ref(), size).with_color(color),
+        ),
+        None => chart_builder.caption(
+            caption,
+            (config.font_family.as_ref(), (5).percent_height()).with_color(color),
+        ),
+    };
+    match config.label_area_sizes {
+        Some((left, bottom)) => chart_builder
+            .set_label_area_size(LabelAreaPosition::Left, left)
+            .set_label_area_size(LabelAreaPosition::Bottom, bottom),
+        None => chart_builder
+            .set_label_area_size(LabelAreaPosition::Left, (8).percent())
+            .set_label_area_size(LabelAreaPosition::Bottom, (4).percent()),
+    };
+    match config.margin {
+        Some(margin) => chart_builder.margin(margin),
+        None => chart_builder.margin((1).percent()),
+    };
+    chart_builder
+}
+
+/// The ranges of the axes of a chart, and which of them are logarithmic.
+struct Axes {
+    x: (u64, u64),
+    log_x: bool,
+    y: (f64, f64),
+    log_y: bool,
+}
+
+/// What is drawn on a chart, whatever the coordinates of its axes.
+trait ChartContents {
+    /// Draws the mesh and the contents on the given chart.
+    ///
+    /// Returns the entries of the legend, as (label, color) pairs.
+    fn draw<'a, DB, X, Y>(
+        &self,
+        chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    ) -> Result<Vec<(String, RGBAColor)>, PlotError>
+    where
+        DB: DrawingBackend + 'a,
+        X: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+        Y: Ranged<ValueType = f64> + ValueFormatter<f64>;
+}
+
+/// The contents of a chart of the times of some measurements, see [`draw_measurements`].
+struct MeasurementsChart<'m> {
+    measurements: &'m [&'m Measurement],
+    baseline: &'m [Measurement],
+    config: &'m PlotConfig<'m>,
+    y_unit: TimeUnit,
+}
+
+impl ChartContents for MeasurementsChart<'_> {
+    fn draw<'a, DB, X, Y>(
+        &self,
+        chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    ) -> Result<Vec<(String, RGBAColor)>, PlotError>
+    where
+        DB: DrawingBackend + 'a,
+        X: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+        Y: Ranged<ValueType = f64> + ValueFormatter<f64>,
+    {
+        draw_measurements(
+            chart,
+            self.measurements,
+            self.baseline,
+            self.config,
+            self.y_unit,
+        )
+    }
+}
+
+/// The contents of a chart of a metric of some measurements, see [`draw_metric_series`].
+struct MetricChart<'m> {
+    series: &'m [(&'m Measurement, Vec<(u64, f64)>)],
+    metric: Metric,
+    config: &'m PlotConfig<'m>,
+    y_unit: TimeUnit,
+}
+
+impl ChartContents for MetricChart<'_> {
+    fn draw<'a, DB, X, Y>(
+        &self,
+        chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    ) -> Result<Vec<(String, RGBAColor)>, PlotError>
+    where
+        DB: DrawingBackend + 'a,
+        X: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+        Y: Ranged<ValueType = f64> + ValueFormatter<f64>,
+    {
+        draw_metric_series(chart, self.series, self.metric, self.config, self.y_unit)
+    }
+}
+
+/// Builds a chart with the given axes and draws the contents on it. The ticks of an x-axis of
+/// sizes in bytes are placed on multiples of powers of 2.
+///
+/// Returns the entries of the legend, as (label, color) pairs.
+fn build_chart<DB: DrawingBackend, C: ChartContents>(
+    chart_builder: &mut ChartBuilder<DB>,
+    axes: &Axes,
+    config: &PlotConfig,
+    contents: &C,
+) -> Result<Vec<(String, RGBAColor)>, PlotError> {
+    let (x_min, x_max) = axes.x;
+    let binary = config.x_axis == XAxis::Bytes && config.si_ticks;
+    match (axes.log_x, binary) {
+        (false, false) => build_chart_y(chart_builder, x_min..x_max, axes, contents),
+        (true, false) => build_chart_y(chart_builder, (x_min..x_max).log_scale(), axes, contents),
+        (false, true) => {
+            let (bold, light) = binary_key_points(x_min, x_max, false);
+            let x = (x_min..x_max)
+                .with_key_points(bold)
+                .with_light_points(light);
+            build_chart_y(chart_builder, x, axes, contents)
+        }
+        (true, true) => {
+            let (bold, light) = binary_key_points(x_min, x_max, true);
+            let x = (x_min..x_max)
+                .log_scale()
+                .with_key_points(bold)
+                .with_light_points(light);
+            build_chart_y(chart_builder, x, axes, contents)
+        }
+    }
+}
+
+/// Builds a chart with the given x-axis and the y-axis of the given axes, and draws the contents
+/// on it.
+fn build_chart_y<DB, X, C>(
+    chart_builder: &mut ChartBuilder<DB>,
+    x: X,
+    axes: &Axes,
+    contents: &C,
+) -> Result<Vec<(String, RGBAColor)>, PlotError>
+where
+    DB: DrawingBackend,
+    X: AsRangedCoord<Value = u64>,
+    X::CoordDescType: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+    C: ChartContents,
+{
+    let (y_min, y_max) = axes.y;
+    if axes.log_y {
+        let mut chart = chart_builder
+            .build_cartesian_2d(x, (y_min..y_max).log_scale())
+            .map_err(rendering)?;
+        contents.draw(&mut chart)
+    } else {
+        let mut chart = chart_builder
+            .build_cartesian_2d(x, y_min..y_max)
+            .map_err(rendering)?;
+        contents.draw(&mut chart)
+    }
+}
+
+/// Returns the key points of an axis of sizes in bytes between the given bounds, on multiples of
+/// powers of 2: the labeled ones and the ones of the light lines of the mesh.
+fn binary_key_points(min: u64, max: u64, log: bool) -> (Vec<u64>, Vec<u64>) {
+    // The maximum number of labeled key points
+    const MAX_POINTS: u64 = 10;
+    if log {
+        let exponents: Vec<u32> = (0..u64::BITS)
+            .filter(|exponent| (min..=max).contains(&(1 << exponent)))
+            .collect();
+        let every = (exponents.len() as u32).div_ceil(MAX_POINTS as u32).max(1);
+        let bold = exponents
+            .iter()
+            .filter(|exponent| *exponent % every == 0)
+            .map(|exponent| 1 << exponent)
+            .collect();
+        let light = exponents.iter().map(|exponent| 1 << exponent).collect();
+        (bold, light)
+    } else {
+        let mut step: u64 = 1;
+        while (max - min) / step > MAX_POINTS {
+            step *= 2;
+        }
+        let multiples = |step: u64| (min.div_ceil(step)..=max / step).map(move |i| i * step);
+        (
+            multiples(step).collect(),
+            multiples((step / 4).max(1)).collect(),
+        )
+    }
+}
+
+/// Splits the given area in the area of the chart and, if the legend goes outside the chart, the
+/// area of the legend on its right.
+fn split_legend<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    config: &PlotConfig,
+) -> (DrawingArea<DB, Shift>, Option<DrawingArea<DB, Shift>>) {
+    match config.legend_position {
+        LegendPosition::Outside => {
+            let (area, legend_area) = area.split_horizontally((80).percent_width());
+            (area, Some(legend_area))
+        }
+        _ => (area.clone(), None),
+    }
+}
+
+/// Draws a chart of the given measurements, with its caption and its legend, in the given area.
+/// The baseline measurements, if any, are drawn as in [`comparison_plot`].
+fn draw_chart<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    caption: &str,
+    measurements: &[&Measurement],
+    baseline: &[Measurement],
+    config: &PlotConfig,
+    bounds: &Bounds,
+) -> Result<(), PlotError> {
+    let (area, legend_area) = split_legend(area, config);
+
+    let mut chart_builder = chart_builder(&area, caption, config);
+    let axes = Axes {
+        x: bounds.x,
+        log_x: bounds.log_x,
+        y: bounds.y,
+        log_y: bounds.log_y,
+    };
+    let contents = MeasurementsChart {
+        measurements,
+        baseline,
+        config,
+        y_unit: bounds.y_unit,
+    };
+    let legend = build_chart(&mut chart_builder, &axes, config, &contents)?;
+    if let Some(legend_area) = legend_area {
+        draw_legend(&legend_area, &legend, config)?;
+    }
+    Ok(())
+}
+
+/// Draws a chart of the given metric of the measurements, other than the time, with its caption
+/// and its legend, in the given area. The x-axis is the one of the given bounds, the points
+/// without a value in the metric are skipped.
+fn draw_metric<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    caption: &str,
+    measurements: &[&Measurement],
+    metric: Metric,
+    config: &PlotConfig,
+    bounds: &Bounds,
+) -> Result<(), PlotError> {
+    let series: Vec<(&Measurement, Vec<(u64, f64)>)> = measurements
+        .iter()
+        .map(|measurement| {
+            let points = config
+                .plotted_points(measurement)
+                .into_iter()
+                .filter_map(|point| {
+                    let value = metric.value(point, bounds.y_unit)?;
+                    Some((config.x_axis.value(point) as u64, value))
+                })
+                .collect();
+            (*measurement, points)
+        })
+        .collect();
+    let values = series
+        .iter()
+        .flat_map(|(_, points)| points)
+        .map(|(_, y)| *y);
+    let y_min = values.clone().fold(f64::INFINITY, f64::min);
+    let y_max = values.fold(f64::NEG_INFINITY, f64::max);
+    if y_min > y_max {
+        return Err(PlotError::MissingMetric { metric });
+    }
+    // Leave some room when all the values are equal, e.g. all the standard deviations are 0
+    let y_max = if y_max > y_min { y_max } else { y_min + 1.0 };
+
+    let (area, legend_area) = split_legend(area, config);
+    let mut chart_builder = chart_builder(&area, caption, config);
+    let axes = Axes {
+        x: bounds.x,
+        log_x: bounds.log_x,
+        y: (y_min, y_max),
+        log_y: y_min > 0.0 && config.scale.is_log(y_min, y_max),
+    };
+    let contents = MetricChart {
+        series: &series,
+        metric,
+        config,
+        y_unit: bounds.y_unit,
+    };
+    let legend = build_chart(&mut chart_builder, &axes, config, &contents)?;
+    if let Some(legend_area) = legend_area {
+        draw_legend(&legend_area, &legend, config)?;
+    }
+    Ok(())
+}
+
+/// Draws the mesh, the given series of a metric and the legend on the given chart.
+///
+/// Returns the entries of the legend, as (label, color) pairs.
+fn draw_metric_series<'a, DB, X, Y>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    series: &[(&Measurement, Vec<(u64, f64)>)],
+    metric: Metric,
+    config: &PlotConfig,
+    y_unit: TimeUnit,
+) -> Result<Vec<(String, RGBAColor)>, PlotError>
+where
+    DB: DrawingBackend + 'a,
+    X: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+    Y: Ranged<ValueType = f64> + ValueFormatter<f64>,
+{
+    let x_formatter = |x: &u64| config.format_x(*x as usize);
+    let y_formatter = |y: &f64| metric.format(*y, config, y_unit);
+    let mut mesh = chart.configure_mesh();
+    mesh.x_desc(config.x_label.as_ref())
+        .y_desc(metric.desc(config, y_unit))
+        .x_label_formatter(&x_formatter)
+        .y_label_formatter(&y_formatter);
+    style_mesh(&mut mesh, config);
+    mesh.draw().map_err(rendering)?;
+
+    let mut legend = Vec::new();
+    for (i, (measurement, points)) in series.iter().enumerate() {
+        let appearance = config.appearance_of(&measurement.algorithm_name);
+        let color = match appearance.color {
+            Some(color) => RGBColor::from(color).mix(0.9),
+            None => config.theme.pick(i).mix(0.9),
+        };
+        let label = config.legend_label(measurement);
+        chart
+            .draw_series(LineSeries::new(
+                points.iter().copied(),
+                color.stroke_width(appearance.line_width.unwrap_or(3)),
+            ))
+            .map_err(rendering)?
+            .label(&label)
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled()));
+        if let Some(marker) = appearance.marker {
+            draw_markers(chart, points.iter().copied(), marker, color)?;
+        }
+        legend.push((label, color));
+    }
+    draw_series_labels(chart, config)?;
+    Ok(legend)
+}
+
+/// The number of segments of the trend lines.
+const TREND_POINTS: usize = 100;
+
+/// Draws the mesh, the series of each algorithm and the legend on the given chart.
+///
+/// # Arguments
+///
+/// * `chart` - The chart to draw on, with either a linear or a logarithmic scale
+/// * `measurements` - The measurements to draw
+/// * `baseline` - The measurements to compare them with, drawn dashed (see [`comparison_plot`])
+/// * `config` - The configuration of the plot
+/// * `y_unit` - The unit of the times on the y-axis
+///
+/// Returns the entries of the legend, as (label, color) pairs.
+fn draw_measurements<'a, DB, X, Y>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    measurements: &[&Measurement],
+    baseline: &[Measurement],
+    config: &PlotConfig,
+    y_unit: TimeUnit,
+) -> Result<Vec<(String, RGBAColor)>, PlotError>
+where
+    DB: DrawingBackend + 'a,
+    X: Ranged<ValueType = u64, FormatOption = DefaultFormatting>,
+    Y: Ranged<ValueType = f64> + ValueFormatter<f64>,
+{
+    let x_formatter = |x: &u64| config.format_x(*x as usize);
+    let y_formatter = |y: &f64| config.format_time(*y, y_unit).unwrap_or_default();
+    let mut mesh = chart.configure_mesh();
+    mesh.x_desc(config.x_label.as_ref())
+        .y_desc(config.time_desc(y_unit))
+        .x_label_formatter(&x_formatter);
+    if config.formats_time() {
+        mesh.y_label_formatter(&y_formatter);
+    }
+    style_mesh(&mut mesh, config);
+    mesh.draw().map_err(rendering)?;
+    draw_lines(chart, config, y_unit)?;
+
+    let mut legend = Vec::new();
+    let mut annotations = Vec::new();
+
+    let x_range = chart.x_range();
+    let y_range = chart.y_range();
+
+    // draw data for each algorithm
+    for (i, measurement) in measurements.iter().enumerate() {
+        // A streamed series is drawn from the points of the measurement, without collecting them
+        let streamed = config.streamed_chunk_size(measurement, baseline);
+        let plotted = match streamed {
+            Some(_) => Vec::new(),
+            None => config.plotted_points(measurement),
+        };
+
+        let style = config.style_of(&measurement.algorithm_name);
+        let appearance = config.appearance_of(&measurement.algorithm_name);
+        let color = match appearance.color {
+            Some(color) => RGBColor::from(color).mix(0.9),
+            None => config.theme.pick(i).mix(0.9),
+        };
+        let coordinates =
+            |point: &Point| (config.x_axis.value(point) as u64, y_unit.as_f64(point.time));
+        let points: Vec<(u64, f64)> = plotted.iter().map(|point| coordinates(point)).collect();
+
+        if let Some(before) = baseline
+            .iter()
+            .find(|before| before.algorithm_name == measurement.algorithm_name)
+        {
+            let label = format!("{} (before)", measurement.algorithm_name);
+            draw_baseline(chart, before, &points, config, y_unit, color)?
+                .label(&label)
+                .legend(move |(x, y)| PathElement::new([(x, y), (x + 10, y)], color.mix(0.6)));
+            legend.push((label, color.mix(0.6)));
+        }
+
+        if config.envelope && points.len() > 1 {
+            // The maximum times from left to right, then the minimum times back
+            let bound = |point: &Point, time: Option<&Duration>| {
+                let time = time.unwrap_or(&point.time);
+                (
+                    config.x_axis.value(point) as u64,
+                    y_unit.as_f64(*time).clamp(y_range.start, y_range.end),
+                )
+            };
+            let high = plotted
+                .iter()
+                .map(|point| bound(point, point.samples.iter().max()));
+            let low = plotted
+                .iter()
+                .rev()
+                .map(|point| bound(point, point.samples.iter().min()));
+            let outline: Vec<(u64, f64)> = high.chain(low).collect();
+            chart
+                .draw_series(std::iter::once(Polygon::new(
+                    outline,
+                    color.mix(0.2).filled(),
+                )))
+                .map_err(rendering)?;
+        }
+
+        let marker = match style {
+            SeriesStyle::Line => appearance.marker,
+            SeriesStyle::Scatter | SeriesStyle::ScatterWithTrend => {
+                Some(appearance.marker.unwrap_or(Marker::Circle))
             }
+        };
+        let line = (style == SeriesStyle::Line).then(|| {
+            (
+                color.stroke_width(appearance.line_width.unwrap_or(3)),
+                appearance.dash,
+            )
+        });
+        match streamed {
+            Some(chunk_size) => draw_points(
+                chart,
+                measurement.measurement.iter().map(coordinates),
+                Some(chunk_size),
+                line,
+                marker,
+                color,
+            )?,
+            None => draw_points(chart, points.iter().copied(), None, line, marker, color)?,
+        }
+        if let Some(smoothing) = config.smoothing {
+            let values: Vec<(f64, f64)> = points.iter().map(|&(x, y)| (x as f64, y)).collect();
+            let smoothed = smoothing.apply(&values);
+            chart
+                .draw_series(LineSeries::new(
+                    points
+                        .iter()
+                        .zip(smoothed)
+                        .map(|(&(x, _), y)| (x, y.clamp(y_range.start, y_range.end))),
+                    color.stroke_width(2),
+                ))
+                .map_err(rendering)?;
+        }
+        let label = config.legend_label(measurement);
+        // An empty series for the legend entry
+        chart
+            .draw_series(std::iter::empty::<Circle<(u64, f64), u32>>())
+            .map_err(rendering)?
+            .label(&label)
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled()));
+        legend.push((label, color));
 
+        let fit = config.fit_of(&measurement.algorithm_name);
+        if let Some(fit) = fit {
+            annotations.push((format!("{}: {}", measurement.algorithm_name, fit), color));
+        }
+
+        // The trend is skipped if it can't be fitted, e.g. when all the points have the same size
+        let trend = (style == SeriesStyle::ScatterWithTrend).then(|| {
+            fit.or_else(|| {
+                Fit::try_from_points(
+                    measurement
+                        .measurement
+                        .iter()
+                        .map(|point| (config.x_axis.value(point) as f64, point.time)),
+                )
+            })
+        });
+        if let Some(fit) = trend.flatten() {
+            let (start, end) = (x_range.start as f64, x_range.end as f64);
             chart
-                .configure_series_labels()
-                .border_style(BLACK)
-                .draw()
-                .unwrap();
-        }
-        Scale::LogLog => {
-            let mut chart = chart_builder
-                .build_cartesian_2d((x_min..x_max).log_scale(), (y_min..y_max).log_scale())
-                .unwrap();
+                .draw_series(LineSeries::new(
+                    (0..=TREND_POINTS).map(|k| {
+                        let x = start + (end - start) * k as f64 / TREND_POINTS as f64;
+                        (
+                            x as u64,
+                            y_unit
+                                .as_f64(fit.predict(x))
+                                .clamp(y_range.start, y_range.end),
+                        )
+                    }),
+                    color.stroke_width(1),
+                ))
+                .map_err(rendering)?;
+        }
+
+        if config.error_bars {
+            let plotted: Box<dyn Iterator<Item = &Point>> = match streamed {
+                Some(_) => Box::new(measurement.measurement.iter()),
+                None => Box::new(plotted.iter().copied()),
+            };
+            // ±σ whiskers, clamped to the chart
             chart
-                .configure_mesh()
-                .x_desc(config.x_label)
-                .y_desc(format!("{} ({:?})", config.x_label, y_precision))
-                .draw()
-                .unwrap();
-
-            // draw data for each algorithm
-            for (i, measurement) in measurements.iter_mut().enumerate() {
-                measurement.measurement.sort_by_key(|a| a.size);
-
-                let color = Palette99::pick(i).mix(0.9);
-                chart
-                    .draw_series(LineSeries::new(
-                        measurement
-                            .measurement
-                            .iter()
-                            .map(|&Point { size, time, .. }| {
-                                (size as u32, y_precision.as_u32(time))
-                            }),
-                        color.stroke_width(3),
+                .draw_series(plotted.filter_map(|point| {
+                    let stddev = point.stddev?;
+                    let x = config.x_axis.value(point) as u64;
+                    let low = point.time.saturating_sub(stddev);
+                    let high = point.time + stddev;
+                    Some(ErrorBar::new_vertical(
+                        x,
+                        y_unit.as_f64(low).clamp(y_range.start, y_range.end),
+                        y_unit.as_f64(point.time),
+                        y_unit.as_f64(high).clamp(y_range.start, y_range.end),
+                        color.stroke_width(1),
+                        6,
                     ))
-                    .unwrap()
-                    .label(&measurement.algorithm_name)
-                    .legend(move |(x, y)| {
-                        Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled())
-                    });
-            }
+                }))
+                .map_err(rendering)?;
+        }
+    }
 
+    if !baseline.is_empty() {
+        let color = REGRESSION_COLOR.mix(0.3);
+        chart
+            .draw_series(std::iter::empty::<Circle<(u64, f64), u32>>())
+            .map_err(rendering)?
+            .label("slower than before")
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled()));
+        legend.push(("slower than before".to_owned(), color));
+    }
+
+    let anchor = config.reference_anchor.or_else(|| {
+        // The curves can't be normalized at 0, where most of them are 0 too
+        let first = measurements
+            .first()?
+            .measurement
+            .iter()
+            .filter(|point| config.x_axis.value(point) > 0)
+            .min_by_key(|point| config.x_axis.value(point))?;
+        Some((config.x_axis.value(first), first.time))
+    });
+    if let Some((anchor_x, anchor_time)) = anchor {
+        let anchor_y = y_unit.as_f64(anchor_time);
+        let (start, end) = (x_range.start as f64, x_range.end as f64);
+        for (i, complexity) in config.reference_curves.iter().enumerate() {
+            let anchor_value = complexity.evaluate(anchor_x as f64);
+            if anchor_value <= 0.0 {
+                continue;
+            }
+            let color = config
+                .theme
+                .text()
+                .mix(0.3 + 0.5 * i as f64 / config.reference_curves.len() as f64);
             chart
-                .configure_series_labels()
-                .border_style(BLACK)
-                .draw()
-                .unwrap();
+                .draw_series(DashedLineSeries::new(
+                    (0..=TREND_POINTS).map(|k| {
+                        let x = start + (end - start) * k as f64 / TREND_POINTS as f64;
+                        let y = anchor_y * complexity.evaluate(x).max(0.0) / anchor_value;
+                        (x as u64, y.clamp(y_range.start, y_range.end))
+                    }),
+                    5,
+                    5,
+                    color.stroke_width(1),
+                ))
+                .map_err(rendering)?
+                .label(complexity.to_string())
+                .legend(move |(x, y)| PathElement::new([(x, y), (x + 10, y)], color));
+            legend.push((complexity.to_string(), color));
+        }
+    }
+
+    if !annotations.is_empty() {
+        draw_annotations(
+            &chart.plotting_area().strip_coord_spec(),
+            &annotations,
+            config,
+        )?;
+    }
+
+    draw_series_labels(chart, config)?;
+    Ok(legend)
+}
+
+/// Draws the labeled horizontal and vertical lines set in the configuration (see
+/// [`PlotConfig::with_horizontal_line`]), included in the range of the chart by [`Bounds::of`].
+fn draw_lines<'a, DB, X, Y>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    config: &PlotConfig,
+    y_unit: TimeUnit,
+) -> Result<(), PlotError>
+where
+    DB: DrawingBackend + 'a,
+    X: Ranged<ValueType = u64>,
+    Y: Ranged<ValueType = f64>,
+{
+    let (x_range, y_range) = (chart.x_range(), chart.y_range());
+    let color = config.theme.text().mix(0.6);
+    let font = (
+        config.font_family.as_ref(),
+        config.tick_font_size.unwrap_or(12),
+    )
+        .into_font()
+        .color(&color);
+
+    for (time, label) in &config.horizontal_lines {
+        let y = y_unit.as_f64(*time);
+        if !(y_range.start..=y_range.end).contains(&y) {
+            continue;
+        }
+        chart
+            .draw_series(DashedLineSeries::new(
+                [(x_range.start, y), (x_range.end, y)],
+                8,
+                4,
+                color.stroke_width(1),
+            ))
+            .map_err(rendering)?;
+        let font = font.pos(Pos::new(HPos::Right, VPos::Bottom));
+        chart
+            .plotting_area()
+            .draw(
+                &(EmptyElement::at((x_range.end, y))
+                    + Text::new(label.to_string(), (-4, -2), font)),
+            )
+            .map_err(rendering)?;
+    }
+
+    for (x, label) in &config.vertical_lines {
+        let x = *x as u64;
+        if !(x_range.start..=x_range.end).contains(&x) {
+            continue;
         }
+        chart
+            .draw_series(DashedLineSeries::new(
+                [(x, y_range.start), (x, y_range.end)],
+                8,
+                4,
+                color.stroke_width(1),
+            ))
+            .map_err(rendering)?;
+        let font = font.pos(Pos::new(HPos::Left, VPos::Top));
+        chart
+            .plotting_area()
+            .draw(
+                &(EmptyElement::at((x, y_range.end)) + Text::new(label.to_string(), (4, 4), font)),
+            )
+            .map_err(rendering)?;
+    }
+    Ok(())
+}
+
+/// Draws the line and the markers of a series of points, sorted by their x coordinate. The line
+/// is drawn in chunks of the given number of points, if any, so that the points are not
+/// collected (see [`PlotConfig::with_streaming`]).
+///
+/// # Arguments
+///
+/// * `chart` - The chart to draw on
+/// * `points` - The points of the series
+/// * `chunk_size` - The number of points of the chunks of the line, if it is streamed
+/// * `line` - The style and the dashes of the line, if it is drawn
+/// * `marker` - The markers drawn on the points, if any
+/// * `color` - The color of the markers
+fn draw_points<'a, DB, X, Y, P>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    points: P,
+    chunk_size: Option<usize>,
+    line: Option<(ShapeStyle, Option<(u32, u32)>)>,
+    marker: Option<Marker>,
+    color: RGBAColor,
+) -> Result<(), PlotError>
+where
+    DB: DrawingBackend + 'a,
+    X: Ranged<ValueType = u64>,
+    Y: Ranged<ValueType = f64>,
+    P: Iterator<Item = (u64, f64)> + Clone,
+{
+    if let Some((line_style, dash)) = line {
+        match (dash, chunk_size) {
+            (Some((length, spacing)), _) => chart
+                .draw_series(DashedLineSeries::new(
+                    points.clone(),
+                    length,
+                    spacing,
+                    line_style,
+                ))
+                .map_err(rendering)?,
+            (None, None) => chart
+                .draw_series(LineSeries::new(points.clone(), line_style))
+                .map_err(rendering)?,
+            (None, Some(chunk_size)) => chart
+                .draw_series(
+                    chunks(points.clone(), chunk_size)
+                        .map(|chunk| PathElement::new(chunk, line_style)),
+                )
+                .map_err(rendering)?,
+        };
+    }
+    match marker {
+        Some(marker) => draw_markers(chart, points, marker, color),
+        None => Ok(()),
+    }
+}
+
+/// Splits the given points in chunks of the given number of points, each one starting with the
+/// last point of the previous one, so that the lines drawn through them are connected.
+fn chunks<P: Copy>(
+    mut points: impl Iterator<Item = P>,
+    chunk_size: usize,
+) -> impl Iterator<Item = Vec<P>> {
+    let mut last = None;
+    std::iter::from_fn(move || {
+        let mut chunk = Vec::with_capacity(chunk_size);
+        chunk.extend(last);
+        chunk.extend(points.by_ref().take(chunk_size - chunk.len()));
+        last = chunk.last().copied();
+        (chunk.len() > 1).then_some(chunk)
+    })
+}
+
+/// Draws the legend of the labeled series inside the chart, in the position set in the
+/// configuration. A legend outside of the chart is drawn by the caller instead.
+fn draw_series_labels<'a, DB, X, Y>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    config: &PlotConfig,
+) -> Result<(), PlotError>
+where
+    DB: DrawingBackend + 'a,
+    X: Ranged,
+    Y: Ranged,
+{
+    let position = match config.legend_position {
+        LegendPosition::UpperLeft => SeriesLabelPosition::UpperLeft,
+        LegendPosition::UpperRight => SeriesLabelPosition::UpperRight,
+        LegendPosition::MiddleRight => SeriesLabelPosition::MiddleRight,
+        LegendPosition::LowerLeft => SeriesLabelPosition::LowerLeft,
+        LegendPosition::LowerRight => SeriesLabelPosition::LowerRight,
+        LegendPosition::Outside => return Ok(()),
     };
+    let mut labels = chart.configure_series_labels();
+    labels
+        .position(position)
+        .border_style(config.theme.text())
+        .label_font(
+            (
+                config.font_family.as_ref(),
+                config.label_font_size.unwrap_or(12),
+            )
+                .with_color(config.theme.text()),
+        );
+    labels.draw().map_err(rendering)
+}
 
-    // To avoid the IO failure being ignored silently, we manually call the present function
-    root.present().expect(
-        "Unable to write result to file, please make sure 'results' dir exists under current dir",
-    );
-    println!("Result has been saved to {file_name}");
+/// Downsamples the given points, sorted by their x coordinate, with the
+/// Largest-Triangle-Three-Buckets algorithm: the first and the last points are kept, and the
+/// others are split in buckets, keeping from each bucket the point forming the largest triangle
+/// with the point kept from the previous bucket and the average of the next bucket.
+///
+/// Returns the indices of the kept points.
+fn lttb(points: &[(f64, f64)], threshold: usize) -> Vec<usize> {
+    let n = points.len();
+    if threshold >= n || threshold < 3 {
+        return (0..n).collect();
+    }
+    let every = (n - 2) as f64 / (threshold - 2) as f64;
+    let bucket = |i: usize| (i as f64 * every) as usize + 1;
+
+    let mut kept = Vec::with_capacity(threshold);
+    kept.push(0);
+    let mut a = 0;
+    for i in 0..threshold - 2 {
+        let next = &points[bucket(i + 1)..bucket(i + 2).min(n)];
+        let next = if next.is_empty() {
+            &points[n - 1..]
+        } else {
+            next
+        };
+        let avg_x = next.iter().map(|(x, _)| x).sum::<f64>() / next.len() as f64;
+        let avg_y = next.iter().map(|(_, y)| y).sum::<f64>() / next.len() as f64;
+
+        let (ax, ay) = points[a];
+        a = (bucket(i)..bucket(i + 1))
+            .max_by(|&j, &k| {
+                let area =
+                    |(x, y): (f64, f64)| ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+                area(points[j]).total_cmp(&area(points[k]))
+            })
+            .unwrap_or(a);
+        kept.push(a);
+    }
+    kept.push(n - 1);
+    kept
+}
+
+/// The color shading the regressions in a [`comparison_plot`], and of the points where an
+/// algorithm got slower in a [`regression_plot`].
+const REGRESSION_COLOR: RGBColor = RGBColor(220, 40, 40);
+
+/// The color of the points where an algorithm got faster, see [`regression_plot`].
+const IMPROVEMENT_COLOR: RGBColor = RGBColor(40, 160, 60);
+
+/// Draws the series of the baseline of a comparison dashed, and shades the intervals where the
+/// given points are above it, i.e. where the times got longer.
+///
+/// Returns the series of the baseline, to set its legend entry.
+fn draw_baseline<'a, 'c, DB, X, Y>(
+    chart: &'c mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    baseline: &Measurement,
+    points: &[(u64, f64)],
+    config: &PlotConfig,
+    y_unit: TimeUnit,
+    color: RGBAColor,
+) -> Result<&'c mut SeriesAnno<'a, DB>, PlotError>
+where
+    DB: DrawingBackend + 'a,
+    X: Ranged<ValueType = u64>,
+    Y: Ranged<ValueType = f64>,
+{
+    let mut before: Vec<(u64, f64)> = baseline
+        .measurement
+        .iter()
+        .map(|point| (config.x_axis.value(point) as u64, y_unit.as_f64(point.time)))
+        .collect();
+    before.sort_by_key(|(x, _)| *x);
+
+    // The time of the baseline at the given x, interpolated linearly, if it is in its range
+    let interpolate = |x: u64| -> Option<f64> {
+        let i = before.partition_point(|(before_x, _)| *before_x < x);
+        let (x1, y1) = *before.get(i)?;
+        if x1 == x {
+            return Some(y1);
+        }
+        let (x0, y0) = *before.get(i.checked_sub(1)?)?;
+        Some(y0 + (y1 - y0) * (x - x0) as f64 / (x1 - x0) as f64)
+    };
+    let shading = REGRESSION_COLOR.mix(0.3).filled();
+    chart
+        .draw_series(points.windows(2).filter_map(|pair| {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            let (before0, before1) = (interpolate(x0)?, interpolate(x1)?);
+            let (d0, d1) = (y0 - before0, y1 - before1);
+            // The point where the two series cross, if they do
+            let crossing = || {
+                let t = d0 / (d0 - d1);
+                (
+                    x0 + ((x1 - x0) as f64 * t).round() as u64,
+                    before0 + (before1 - before0) * t,
+                )
+            };
+            let polygon = match (d0 > 0.0, d1 > 0.0) {
+                (true, true) => vec![(x0, before0), (x1, before1), (x1, y1), (x0, y0)],
+                (true, false) => vec![(x0, before0), crossing(), (x0, y0)],
+                (false, true) => vec![crossing(), (x1, before1), (x1, y1)],
+                (false, false) => return None,
+            };
+            Some(Polygon::new(polygon, shading))
+        }))
+        .map_err(rendering)?;
+
+    chart
+        .draw_series(DashedLineSeries::new(
+            before,
+            6,
+            4,
+            color.mix(0.6).stroke_width(2),
+        ))
+        .map_err(rendering)
+}
+
+/// Draws the given lines of text in a box in a corner of the given plotting area, the upper left
+/// one or the lower right one if the legend is in the upper left corner.
+fn draw_annotations<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    annotations: &[(String, RGBAColor)],
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    let size = config.label_font_size.unwrap_or(12);
+    let font = TextStyle::from((config.font_family.as_ref(), size).into_font());
+    let padding = 6;
+    let line_height = size as i32 + 4;
+    let mut width = 0;
+    for (text, _) in annotations {
+        let (text_width, _) = area.estimate_text_size(text, &font).map_err(rendering)?;
+        width = width.max(text_width as i32);
+    }
+    let width = width + 2 * padding;
+    let height = line_height * annotations.len() as i32 + 2 * padding;
+
+    let (area_width, area_height) = area.dim_in_pixel();
+    let (x, y) = match config.legend_position {
+        LegendPosition::UpperLeft => (
+            area_width as i32 - width - padding,
+            area_height as i32 - height - padding,
+        ),
+        _ => (padding, padding),
+    };
+    area.draw(&Rectangle::new(
+        [(x, y), (x + width, y + height)],
+        config.theme.background().mix(0.8).filled(),
+    ))
+    .map_err(rendering)?;
+    area.draw(&Rectangle::new(
+        [(x, y), (x + width, y + height)],
+        config.theme.text().stroke_width(1),
+    ))
+    .map_err(rendering)?;
+    for (i, (text, color)) in annotations.iter().enumerate() {
+        let position = (x + padding, y + padding + line_height * i as i32);
+        area.draw(&Text::new(text.as_str(), position, font.color(color)))
+            .map_err(rendering)?;
+    }
+    Ok(())
+}
+
+/// Sets the colors of the grid and the fonts of the labels of the given mesh, as set in the
+/// configuration.
+fn style_mesh<'b, X, Y, DB>(mesh: &mut MeshStyle<'_, 'b, X, Y, DB>, config: &'b PlotConfig<'_>)
+where
+    X: Ranged,
+    Y: Ranged,
+    DB: DrawingBackend,
+{
+    let (grid, text) = (RGBColor::from(config.theme.grid), config.theme.text());
+    let tick_font_size = config.tick_font_size.unwrap_or(12);
+    let label_font_size = config.label_font_size.unwrap_or(tick_font_size);
+    mesh.bold_line_style(grid.mix(0.2))
+        .light_line_style(grid.mix(0.1))
+        .axis_style(text)
+        .label_style((config.font_family.as_ref(), tick_font_size).with_color(text))
+        .axis_desc_style((config.font_family.as_ref(), label_font_size).with_color(text));
+}
+
+/// Draws the given legend entries in the given area, one per line.
+fn draw_legend<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    legend: &[(String, RGBAColor)],
+    config: &PlotConfig,
+) -> Result<(), PlotError> {
+    let size = config.label_font_size.unwrap_or(12);
+    let text = config.theme.text();
+    let font = TextStyle::from((config.font_family.as_ref(), size).into_font())
+        .color(&text)
+        .pos(Pos::new(HPos::Left, VPos::Center));
+    let line_height = size as i32 + 6;
+    for (i, (label, color)) in legend.iter().enumerate() {
+        let y = line_height * (i as i32 + 1);
+        area.draw(&Rectangle::new([(10, y - 5), (20, y + 5)], color.filled()))
+            .map_err(rendering)?;
+        area.draw(&Text::new(label.as_str(), (26, y), font.clone()))
+            .map_err(rendering)?;
+    }
+    Ok(())
+}
+
+/// Draws the given markers on the given points of the chart.
+fn draw_markers<'a, DB, X, Y, P>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<X, Y>>,
+    points: P,
+    marker: Marker,
+    color: RGBAColor,
+) -> Result<(), PlotError>
+where
+    DB: DrawingBackend + 'a,
+    X: Ranged,
+    Y: Ranged,
+    X::ValueType: Copy + 'static,
+    Y::ValueType: Copy + 'static,
+    P: IntoIterator<Item = (X::ValueType, Y::ValueType)>,
+{
+    let points = points.into_iter();
+    match marker {
+        Marker::Circle => {
+            chart.draw_series(points.map(|point| Circle::new(point, 3, color.filled())))
+        }
+        Marker::Square => chart.draw_series(points.map(|point| {
+            EmptyElement::at(point) + Rectangle::new([(-3, -3), (3, 3)], color.filled())
+        })),
+        Marker::Triangle => {
+            chart.draw_series(points.map(|point| TriangleMarker::new(point, 4, color.filled())))
+        }
+        Marker::Cross => {
+            chart.draw_series(points.map(|point| Cross::new(point, 3, color.stroke_width(2))))
+        }
+    }
+    .map_err(rendering)?;
+    Ok(())
 }
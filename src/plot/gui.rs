@@ -0,0 +1,295 @@
+//! # GUI
+//!
+//! This module contains an [egui] widget showing the plot of some [`Measurements`], enabled by
+//! the `gui` feature, so that the desktop tools built around chrono-probe can display their
+//! results without exporting any file.
+//!
+//! The widget draws the same plot as [`time_plot`](crate::plot::time_plot), through a [plotters]
+//! backend painting on the [`Painter`] of egui, that can also be used to draw any other chart of
+//! plotters in a user interface. The plot is drawn again at each frame, so the measurements shown
+//! can change between frames, e.g. while they are measured.
+
+use std::convert::Infallible;
+
+use egui::epaint::TextShape;
+use egui::{
+    Align2, Color32, FontId, Painter, Pos2, Rect, Response, Sense, Shape, Stroke, StrokeKind, Ui,
+    Widget,
+};
+use plotters::backend::DrawingBackend;
+use plotters::drawing::IntoDrawingArea;
+use plotters_backend::text_anchor::{HPos, VPos};
+use plotters_backend::{
+    BackendColor, BackendCoord, BackendStyle, BackendTextStyle, DrawingErrorKind, FontTransform,
+};
+
+use crate::measurements::Measurements;
+use crate::plot::{draw_time_plot, PlotConfig};
+
+/// A widget showing the plot of some [`Measurements`], like [`time_plot`](crate::plot::time_plot).
+/// The plot fills the space available in the [`Ui`], the dimensions set in the configuration
+/// are ignored: use [`Ui::add_sized`] to give it a fixed size.
+///
+/// A placeholder is shown while the measurements are empty, and the error is shown in place of
+/// the plot if it cannot be drawn.
+///
+/// # Example
+///
+/// ```
+/// use chrono_probe::measurements::Measurements;
+/// use chrono_probe::plot::gui::TimePlot;
+/// use chrono_probe::plot::PlotConfig;
+///
+/// fn show(ui: &mut egui::Ui, measurements: &Measurements, config: &PlotConfig) {
+///     ui.add_sized([800.0, 600.0], TimePlot::new(measurements, config));
+/// }
+/// ```
+pub struct TimePlot<'a> {
+    measurements: &'a Measurements,
+    config: &'a PlotConfig<'a>,
+}
+
+impl<'a> TimePlot<'a> {
+    /// Creates a new [`TimePlot`].
+    ///
+    /// # Arguments
+    ///
+    /// * `measurements` - The measurements to plot.
+    /// * `config` - The configuration of the plot.
+    pub fn new(measurements: &'a Measurements, config: &'a PlotConfig<'a>) -> TimePlot<'a> {
+        TimePlot {
+            measurements,
+            config,
+        }
+    }
+}
+
+impl Widget for TimePlot<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::hover());
+        if ui.is_rect_visible(rect) && rect.width() >= 1.0 && rect.height() >= 1.0 {
+            let painter = ui.painter_at(rect);
+            let empty = self
+                .measurements
+                .measurements
+                .iter()
+                .all(|measurement| measurement.measurement.is_empty());
+            if empty {
+                // Nothing measured yet, e.g. at the start of a measurement
+                painter.text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    "No measurements to plot",
+                    FontId::proportional(14.0),
+                    ui.visuals().weak_text_color(),
+                );
+                return response;
+            }
+            let root = EguiBackend::new(&painter, rect).into_drawing_area();
+            // The error is shown in place of the plot, which may be drawn in the next frames
+            if let Err(error) = draw_time_plot(&root, self.measurements, self.config) {
+                painter.text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    error.to_string(),
+                    FontId::proportional(14.0),
+                    ui.visuals().error_fg_color,
+                );
+            }
+        }
+        response
+    }
+}
+
+/// A [plotters] backend painting on a rectangle of an egui [`Painter`], one pixel per point.
+///
+/// # Example
+///
+/// ```
+/// use chrono_probe::plot::gui::EguiBackend;
+/// use plotters::prelude::*;
+///
+/// fn paint(ui: &mut egui::Ui) {
+///     let (rect, _) = ui.allocate_exact_size(egui::vec2(320.0, 240.0), egui::Sense::hover());
+///     let root = EguiBackend::new(ui.painter(), rect).into_drawing_area();
+///     root.fill(&WHITE).unwrap();
+/// }
+/// ```
+pub struct EguiBackend<'p> {
+    painter: &'p Painter,
+    rect: Rect,
+}
+
+impl<'p> EguiBackend<'p> {
+    /// Creates a new [`EguiBackend`].
+    ///
+    /// # Arguments
+    ///
+    /// * `painter` - The painter to paint with.
+    /// * `rect` - The rectangle to paint in, whose upper left corner is the origin of the
+    ///   backend.
+    pub fn new(painter: &'p Painter, rect: Rect) -> EguiBackend<'p> {
+        EguiBackend { painter, rect }
+    }
+
+    /// Returns the given point in the coordinates of the painter.
+    fn pos(&self, (x, y): BackendCoord) -> Pos2 {
+        self.rect.min + egui::vec2(x as f32, y as f32)
+    }
+
+    /// Returns the galley of the given text in the given style, ready to be painted.
+    fn galley<S: BackendTextStyle>(&self, text: &str, style: &S) -> std::sync::Arc<egui::Galley> {
+        // The same size as the SVG backend of plotters
+        let font = FontId::proportional((style.size() / 1.24) as f32);
+        self.painter
+            .layout_no_wrap(text.to_owned(), font, color(style.color()))
+    }
+}
+
+/// Returns the given color of plotters as a color of egui.
+fn color(color: BackendColor) -> Color32 {
+    let (r, g, b) = color.rgb;
+    Color32::from_rgba_unmultiplied(r, g, b, (color.alpha.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+impl DrawingBackend for EguiBackend<'_> {
+    type ErrorType = Infallible;
+
+    fn get_size(&self) -> (u32, u32) {
+        (self.rect.width() as u32, self.rect.height() as u32)
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Infallible>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Infallible>> {
+        Ok(())
+    }
+
+    fn draw_pixel(
+        &mut self,
+        point: BackendCoord,
+        color: BackendColor,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        let min = self.pos(point);
+        self.painter.rect_filled(
+            Rect::from_min_size(min, egui::vec2(1.0, 1.0)),
+            0.0,
+            self::color(color),
+        );
+        Ok(())
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        self.painter.line_segment(
+            [self.pos(from), self.pos(to)],
+            Stroke::new(style.stroke_width() as f32, color(style.color())),
+        );
+        Ok(())
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        let rect = Rect::from_two_pos(self.pos(upper_left), self.pos(bottom_right));
+        if fill {
+            self.painter.rect_filled(rect, 0.0, color(style.color()));
+        } else {
+            self.painter.rect_stroke(
+                rect,
+                0.0,
+                Stroke::new(style.stroke_width() as f32, color(style.color())),
+                StrokeKind::Middle,
+            );
+        }
+        Ok(())
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        let points: Vec<Pos2> = path.into_iter().map(|point| self.pos(point)).collect();
+        self.painter.add(Shape::line(
+            points,
+            Stroke::new(style.stroke_width() as f32, color(style.color())),
+        ));
+        Ok(())
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        let center = self.pos(center);
+        if fill {
+            self.painter
+                .circle_filled(center, radius as f32, color(style.color()));
+        } else {
+            self.painter.circle_stroke(
+                center,
+                radius as f32,
+                Stroke::new(style.stroke_width() as f32, color(style.color())),
+            );
+        }
+        Ok(())
+    }
+
+    // The polygons are left to the rasterizer of plotters, egui only fills convex polygons
+
+    fn draw_text<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<Infallible>> {
+        let galley = self.galley(text, style);
+        let anchor = Align2([
+            match style.anchor().h_pos {
+                HPos::Left => egui::Align::Min,
+                HPos::Center => egui::Align::Center,
+                HPos::Right => egui::Align::Max,
+            },
+            match style.anchor().v_pos {
+                VPos::Top => egui::Align::Min,
+                VPos::Center => egui::Align::Center,
+                VPos::Bottom => egui::Align::Max,
+            },
+        ]);
+        let angle = match style.transform() {
+            FontTransform::Rotate90 => std::f32::consts::FRAC_PI_2,
+            FontTransform::Rotate180 => std::f32::consts::PI,
+            FontTransform::Rotate270 => -std::f32::consts::FRAC_PI_2,
+            _ => 0.0,
+        };
+        // The anchor of the text on the given position, then rotated around it
+        let origin = self.pos(pos) - anchor.pos_in_rect(&galley.rect).to_vec2();
+        let color = color(style.color());
+        self.painter
+            .add(TextShape::new(origin, galley, color).with_angle_and_anchor(angle, anchor));
+        Ok(())
+    }
+
+    fn estimate_text_size<TStyle: BackendTextStyle>(
+        &self,
+        text: &str,
+        style: &TStyle,
+    ) -> Result<(u32, u32), DrawingErrorKind<Infallible>> {
+        let size = self.galley(text, style).size();
+        Ok((size.x.ceil() as u32, size.y.ceil() as u32))
+    }
+}
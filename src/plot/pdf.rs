@@ -0,0 +1,361 @@
+//! # PDF
+//!
+//! This module contains a [plotters] backend writing vector PDF documents, enabled by the `pdf`
+//! feature, so that the plots can be included in LaTeX papers without converting them.
+//!
+//! The texts are written with the Helvetica font shipped with every PDF reader, so that no font
+//! is embedded in the documents. Only the characters of the Latin-1 set can be written, the
+//! others are replaced by question marks.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+use plotters::backend::DrawingBackend;
+use plotters_backend::text_anchor::{HPos, VPos};
+use plotters_backend::{
+    BackendColor, BackendCoord, BackendStyle, BackendTextStyle, DrawingErrorKind, FontTransform,
+};
+
+/// The name of the font in the resources of the page.
+const FONT: Name = Name(b"F1");
+
+/// The factor of the control points of the cubic Bézier curves approximating a quarter of a
+/// circle.
+const KAPPA: f32 = 0.552_284_8;
+
+/// A [plotters] backend drawing on a single page of a PDF document, one point per pixel.
+///
+/// # Example
+///
+/// ```no_run
+/// use chrono_probe::plot::pdf::PdfBackend;
+/// use plotters::prelude::*;
+///
+/// let root = PdfBackend::new("plots/figure.pdf", (640, 480)).into_drawing_area();
+/// root.fill(&WHITE).unwrap();
+/// root.present().unwrap();
+/// ```
+pub struct PdfBackend {
+    path: PathBuf,
+    size: (u32, u32),
+    content: Content,
+    // The opacities used, in percent, each needing a graphics state in the resources
+    alphas: BTreeSet<u8>,
+    saved: bool,
+}
+
+impl PdfBackend {
+    /// Creates a new [`PdfBackend`], writing the document to the given file when it is presented.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the PDF file.
+    /// * `size` - The size of the page, in points.
+    pub fn new<P: AsRef<Path>>(path: P, size: (u32, u32)) -> PdfBackend {
+        PdfBackend {
+            path: path.as_ref().to_path_buf(),
+            size,
+            content: Content::new(),
+            alphas: BTreeSet::new(),
+            saved: false,
+        }
+    }
+
+    /// Returns the given point in the coordinates of the page, whose origin is in the lower
+    /// left corner.
+    fn point(&self, (x, y): BackendCoord) -> (f32, f32) {
+        (x as f32, self.size.1 as f32 - y as f32)
+    }
+
+    /// Saves the graphics state and sets the given color, for the strokes or for the fills.
+    fn begin(&mut self, color: BackendColor, fill: bool) {
+        let (r, g, b) = color.rgb;
+        let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+        self.content.save_state();
+        if fill {
+            self.content.set_fill_rgb(r, g, b);
+        } else {
+            self.content.set_stroke_rgb(r, g, b);
+        }
+        let alpha = (color.alpha.clamp(0.0, 1.0) * 100.0).round() as u8;
+        if alpha < 100 {
+            self.alphas.insert(alpha);
+            self.content
+                .set_parameters(Name(alpha_name(alpha).as_bytes()));
+        }
+    }
+
+    /// Writes the document to the file.
+    fn write(&mut self) -> io::Result<()> {
+        let content = std::mem::replace(&mut self.content, Content::new()).finish();
+        let (width, height) = (self.size.0 as f32, self.size.1 as f32);
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let page_id = Ref::new(3);
+        let font_id = Ref::new(4);
+        let content_id = Ref::new(5);
+        let alpha_ids: Vec<(u8, Ref)> = self
+            .alphas
+            .iter()
+            .enumerate()
+            .map(|(i, alpha)| (*alpha, Ref::new(6 + i as i32)))
+            .collect();
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id).kids([page_id]).count(1);
+        let mut page = pdf.page(page_id);
+        page.media_box(Rect::new(0.0, 0.0, width, height))
+            .parent(page_tree_id)
+            .contents(content_id);
+        let mut resources = page.resources();
+        resources.fonts().pair(FONT, font_id);
+        let mut states = resources.ext_g_states();
+        for (alpha, id) in &alpha_ids {
+            states.pair(Name(alpha_name(*alpha).as_bytes()), *id);
+        }
+        states.finish();
+        resources.finish();
+        page.finish();
+        pdf.type1_font(font_id)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        for (alpha, id) in alpha_ids {
+            let alpha = alpha as f32 / 100.0;
+            pdf.ext_graphics(id)
+                .stroking_alpha(alpha)
+                .non_stroking_alpha(alpha);
+        }
+        pdf.stream(content_id, &content);
+
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, pdf.finish())
+    }
+}
+
+/// Returns the name of the graphics state with the given opacity, in percent.
+fn alpha_name(alpha: u8) -> String {
+    format!("A{alpha}")
+}
+
+/// Returns the given text in the WinAnsi encoding of the font.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+            'μ' => 0xb5,
+            '–' => 0x96,
+            '—' => 0x97,
+            '…' => 0x85,
+            _ => b'?',
+        })
+        .collect()
+}
+
+impl DrawingBackend for PdfBackend {
+    type ErrorType = io::Error;
+
+    fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<io::Error>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<io::Error>> {
+        if !self.saved {
+            self.write().map_err(DrawingErrorKind::DrawingError)?;
+            self.saved = true;
+        }
+        Ok(())
+    }
+
+    fn draw_pixel(
+        &mut self,
+        point: BackendCoord,
+        color: BackendColor,
+    ) -> Result<(), DrawingErrorKind<io::Error>> {
+        if color.alpha == 0.0 {
+            return Ok(());
+        }
+        let (x, y) = self.point(point);
+        self.begin(color, true);
+        self.content.rect(x, y - 1.0, 1.0, 1.0).fill_nonzero();
+        self.content.restore_state();
+        Ok(())
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<io::Error>> {
+        self.draw_path([from, to], style)
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<io::Error>> {
+        if style.color().alpha == 0.0 {
+            return Ok(());
+        }
+        let (x0, y0) = self.point(upper_left);
+        let (x1, y1) = self.point(bottom_right);
+        self.begin(style.color(), fill);
+        self.content.rect(x0, y1, x1 - x0, y0 - y1);
+        if fill {
+            self.content.fill_nonzero();
+        } else {
+            self.content
+                .set_line_width(style.stroke_width() as f32)
+                .stroke();
+        }
+        self.content.restore_state();
+        Ok(())
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<io::Error>> {
+        if style.color().alpha == 0.0 {
+            return Ok(());
+        }
+        let points: Vec<(f32, f32)> = path.into_iter().map(|point| self.point(point)).collect();
+        let Some(((x, y), rest)) = points.split_first() else {
+            return Ok(());
+        };
+        self.begin(style.color(), false);
+        self.content
+            .set_line_width(style.stroke_width() as f32)
+            .move_to(*x, *y);
+        for (x, y) in rest {
+            self.content.line_to(*x, *y);
+        }
+        self.content.stroke().restore_state();
+        Ok(())
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<io::Error>> {
+        if style.color().alpha == 0.0 {
+            return Ok(());
+        }
+        let (x, y) = self.point(center);
+        let r = radius as f32;
+        let k = r * KAPPA;
+        self.begin(style.color(), fill);
+        self.content
+            .move_to(x + r, y)
+            .cubic_to(x + r, y + k, x + k, y + r, x, y + r)
+            .cubic_to(x - k, y + r, x - r, y + k, x - r, y)
+            .cubic_to(x - r, y - k, x - k, y - r, x, y - r)
+            .cubic_to(x + k, y - r, x + r, y - k, x + r, y)
+            .close_path();
+        if fill {
+            self.content.fill_nonzero();
+        } else {
+            self.content
+                .set_line_width(style.stroke_width() as f32)
+                .stroke();
+        }
+        self.content.restore_state();
+        Ok(())
+    }
+
+    fn fill_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<io::Error>> {
+        if style.color().alpha == 0.0 {
+            return Ok(());
+        }
+        let points: Vec<(f32, f32)> = vert.into_iter().map(|point| self.point(point)).collect();
+        let Some(((x, y), rest)) = points.split_first() else {
+            return Ok(());
+        };
+        self.begin(style.color(), true);
+        self.content.move_to(*x, *y);
+        for (x, y) in rest {
+            self.content.line_to(*x, *y);
+        }
+        self.content.close_path().fill_nonzero().restore_state();
+        Ok(())
+    }
+
+    fn draw_text<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<io::Error>> {
+        if style.color().alpha == 0.0 {
+            return Ok(());
+        }
+        // The same size and anchors as the SVG backend of plotters
+        let size = (style.size() / 1.24) as f32;
+        let ((min_x, _), (max_x, _)) = style
+            .layout_box(text)
+            .map_err(|error| DrawingErrorKind::FontError(Box::new(error)))?;
+        let width = (max_x - min_x) as f32;
+        let along = match style.anchor().h_pos {
+            HPos::Left => 0.0,
+            HPos::Center => -width / 2.0,
+            HPos::Right => -width,
+        };
+        let down = match style.anchor().v_pos {
+            VPos::Top => 0.76 * size,
+            VPos::Center => 0.25 * size,
+            VPos::Bottom => -0.25 * size,
+        };
+        let (a, b, c, d) = match style.transform() {
+            FontTransform::Rotate90 => (0.0, -1.0, 1.0, 0.0),
+            FontTransform::Rotate180 => (-1.0, 0.0, 0.0, -1.0),
+            FontTransform::Rotate270 => (0.0, 1.0, -1.0, 0.0),
+            _ => (1.0, 0.0, 0.0, 1.0),
+        };
+        let (x, y) = self.point(pos);
+        let (x, y) = (x + a * along - c * down, y + b * along - d * down);
+
+        self.begin(style.color(), true);
+        self.content
+            .begin_text()
+            .set_font(FONT, size)
+            .set_text_matrix([a, b, c, d, x, y])
+            .show(Str(&encode(text)))
+            .end_text()
+            .restore_state();
+        Ok(())
+    }
+}
+
+impl Drop for PdfBackend {
+    fn drop(&mut self) {
+        if !self.saved {
+            // The errors can't be reported here, call present to handle them
+            let _ = self.present();
+        }
+    }
+}
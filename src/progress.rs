@@ -0,0 +1,169 @@
+//! # Progress
+//!
+//! This module provides a way to follow the progress of long running operations, like the
+//! generation of the inputs and the measurement of the algorithms.
+//!
+//! Functions accepting a [`ProgressHandler`] call it every time some work is completed,
+//! passing a [`Progress`] report. Any closure taking a `&Progress` is a [`ProgressHandler`]:
+//!
+//! ```
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::geometry::{PointSet, PointSetGenerator};
+//! use chrono_probe::input::InputBuilder;
+//! use chrono_probe::progress::Progress;
+//!
+//! let builder: InputBuilder<PointSet, _> =
+//!     InputBuilder::new(Uniform::new(10..=1000).unwrap(), PointSetGenerator::default());
+//!
+//! let inputs = builder.build_with_progress(20, 5, &mut |progress: &Progress| {
+//!     println!("{:.0}%, ETA {:?}", progress.fraction() * 100.0, progress.eta());
+//! });
+//! ```
+//!
+//! When no handler is given, the [`DefaultProgress`] handler is used: it prints the progress in
+//! the console if the `debug` feature is enabled, and does nothing otherwise.
+//!
+//! The handlers passed to the measure functions are also told when the measurements start, with
+//! a [`RunInfo`], and when they end, with their results: the handlers of the
+//! [`sink`](crate::sink) module use them to save the results while they are measured.
+
+use std::time::Duration;
+
+use crate::environment::Environment;
+use crate::measurements::{Measurements, Point};
+
+/// The operation a [`Progress`] report refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// The generation of the inputs.
+    InputGeneration,
+    /// The measurement of an algorithm.
+    Measurement,
+}
+
+/// A progress report.
+#[derive(Debug, Clone)]
+pub struct Progress<'a> {
+    /// The operation the report refers to
+    pub stage: Stage,
+    /// The name of what is being processed (e.g. the name of the algorithm being measured)
+    pub label: Option<&'a str>,
+    /// The number of items completed so far
+    pub completed: usize,
+    /// The total number of items
+    pub total: usize,
+    /// The number of bytes allocated so far, if known
+    pub bytes: Option<usize>,
+    /// The time elapsed since the beginning of the operation
+    pub elapsed: Duration,
+    /// The point just measured, in the [`Stage::Measurement`] stage
+    pub point: Option<&'a Point>,
+}
+
+impl Progress<'_> {
+    /// Returns the fraction of items completed, between 0 and 1.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.completed as f64 / self.total as f64
+        }
+    }
+
+    /// Returns the estimated time remaining to complete the operation, assuming every item takes
+    /// the same time. Returns `None` if no item has been completed yet.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.completed == 0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.completed) as f64;
+        Some(self.elapsed.mul_f64(remaining / self.completed as f64))
+    }
+}
+
+/// The description of a run of measurements, given to the [`ProgressHandler`] when it starts.
+#[derive(Debug, Clone)]
+pub struct RunInfo<'a> {
+    /// The names of the algorithms to measure, in the order in which they are measured
+    pub algorithms: &'a [&'a str],
+    /// The sizes of the inputs, in the order in which they are measured
+    pub sizes: &'a [usize],
+    /// The relative error of the measurements
+    pub relative_error: f32,
+    /// The resolution of the clock
+    pub resolution: Duration,
+    /// The seed used to generate the inputs, if any
+    pub seed: Option<u64>,
+    /// The machine and the build on which the measurements are taken
+    pub environment: &'a Environment,
+}
+
+/// Trait implemented by the types that receive progress reports.
+pub trait ProgressHandler {
+    /// Called every time some work is completed.
+    fn on_progress(&mut self, progress: &Progress);
+
+    /// Called by the measure functions before measuring the first algorithm.
+    ///
+    /// By default, it does nothing.
+    fn on_run_start(&mut self, run: &RunInfo) {
+        let _ = run;
+    }
+
+    /// Called by the measure functions after measuring the last algorithm, with the results.
+    ///
+    /// By default, it does nothing.
+    fn on_run_end(&mut self, measurements: &Measurements) {
+        let _ = measurements;
+    }
+}
+
+impl<F: FnMut(&Progress)> ProgressHandler for F {
+    fn on_progress(&mut self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+/// A [`ProgressHandler`] that ignores every report.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressHandler for NoProgress {
+    fn on_progress(&mut self, _progress: &Progress) {}
+}
+
+/// A [`ProgressHandler`] that prints the progress in the console every 5%.
+#[derive(Debug, Clone, Default)]
+pub struct PrintProgress {
+    last_percentage: Option<usize>,
+}
+
+impl ProgressHandler for PrintProgress {
+    fn on_progress(&mut self, progress: &Progress) {
+        let percentage = (progress.fraction() * 100.0) as usize / 5 * 5;
+        // A new operation has started
+        if progress.completed == 1 {
+            match progress.label {
+                Some(label) => println!("\n{:?}: {}\n", progress.stage, label),
+                None => println!("\n{:?}\n", progress.stage),
+            }
+        }
+        if progress.completed == 1 || self.last_percentage != Some(percentage) {
+            match progress.eta() {
+                Some(eta) => println!("{}% (ETA {:.1?})", percentage, eta),
+                None => println!("{}%", percentage),
+            }
+            self.last_percentage = Some(percentage);
+        }
+    }
+}
+
+/// The handler used when no [`ProgressHandler`] is given: a [`PrintProgress`] if the `debug`
+/// feature is enabled, a [`NoProgress`] otherwise.
+#[cfg(feature = "debug")]
+pub type DefaultProgress = PrintProgress;
+
+/// The handler used when no [`ProgressHandler`] is given: a [`PrintProgress`] if the `debug`
+/// feature is enabled, a [`NoProgress`] otherwise.
+#[cfg(not(feature = "debug"))]
+pub type DefaultProgress = NoProgress;
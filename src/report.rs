@@ -0,0 +1,451 @@
+//! # Report
+//!
+//! This module compares two runs of the same algorithms, e.g. the results saved before and after
+//! an optimization, to track the performance over time.
+//!
+//! [`diff`] loads two result files saved with
+//! [`Measurements::serialize_json`](crate::measurements::Measurements::serialize_json) and returns
+//! a [`DiffReport`], which can also be computed from measurements in memory with
+//! [`DiffReport::new`]. For each algorithm present in both runs, the report contains:
+//!
+//! * The change of the time of each size of the new run, with respect to the time of the old run
+//!   at the same size (interpolated between its sizes, if the old run didn't measure it).
+//! * The change of the exponent of the power law fitted to each run (see [`Fit`]), together
+//!   with the growth rates inferred from them (see [`Complexity::infer`]).
+//!
+//! Each change is classified as a regression, an improvement or neither, if it is within the
+//! noise of the measurements. The report can be rendered in Markdown with
+//! [`DiffReport::to_markdown`], e.g. to comment a pull request, or serialized as JSON.
+//!
+//! The measurements of a single run can also be rendered as LaTeX, ready to be pasted in a
+//! paper: a table of the times with [`latex_table`], and a plot drawn by PGFPlots with
+//! [`pgfplots`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use chrono_probe::report;
+//!
+//! let report = report::diff("results/main.json", "results/branch.json").unwrap();
+//! println!("{}", report.to_markdown());
+//! if report.has_regressions() {
+//!     std::process::exit(1);
+//! }
+//! ```
+
+mod latex;
+
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::analysis::{Complexity, Fit};
+use crate::measurements::{ImportError, Measurement, Measurements};
+use crate::plot::{format_duration, format_size};
+
+pub use latex::{latex_table, pgfplots};
+
+/// How a time or a growth rate changed with respect to a baseline.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// Slower than the baseline, beyond the noise
+    Regressed,
+    /// Faster than the baseline, beyond the noise
+    Improved,
+    /// Within the noise
+    Unchanged,
+}
+
+impl Change {
+    /// All the changes, from the worst to the best.
+    pub(crate) const ALL: [Change; 3] = [Change::Regressed, Change::Improved, Change::Unchanged];
+
+    /// Classifies a difference from the baseline, given the largest difference due to the noise.
+    pub(crate) fn of(difference: f64, noise: f64) -> Change {
+        if difference > noise {
+            Change::Regressed
+        } else if difference < -noise {
+            Change::Improved
+        } else {
+            Change::Unchanged
+        }
+    }
+
+    /// Returns a short description of the change, e.g. "slower".
+    pub fn label(&self) -> &'static str {
+        match self {
+            Change::Regressed => "slower",
+            Change::Improved => "faster",
+            Change::Unchanged => "within noise",
+        }
+    }
+}
+
+/// The times of a baseline, with their standard deviations, as a function of the sizes (or of
+/// any other x-axis) interpolated linearly between the measured ones.
+pub(crate) struct Baseline {
+    // (x, time, standard deviation), in seconds, sorted by x
+    points: Vec<(f64, f64, f64)>,
+}
+
+impl Baseline {
+    /// Creates a new [`Baseline`] from its (x, time, standard deviation) points, in seconds.
+    pub(crate) fn new<P: IntoIterator<Item = (f64, f64, f64)>>(points: P) -> Baseline {
+        let mut points: Vec<(f64, f64, f64)> = points.into_iter().collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Baseline { points }
+    }
+
+    /// Returns the time and the standard deviation of the baseline at the given x, and whether
+    /// they are interpolated, if it is in its range.
+    pub(crate) fn at(&self, x: f64) -> Option<(f64, f64, bool)> {
+        let i = self
+            .points
+            .partition_point(|(before_x, _, _)| *before_x < x);
+        let (x1, time1, stddev1) = *self.points.get(i)?;
+        if x1 == x {
+            return Some((time1, stddev1, false));
+        }
+        let (x0, time0, stddev0) = *self.points.get(i.checked_sub(1)?)?;
+        let t = (x - x0) / (x1 - x0);
+        Some((
+            time0 + (time1 - time0) * t,
+            stddev0 + (stddev1 - stddev0) * t,
+            true,
+        ))
+    }
+
+    /// Compares a time with the time of the baseline at the same x, if it is in its range.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The x of the time
+    /// * `time` - The time, in seconds
+    /// * `stddev` - The standard deviation of the time, in seconds
+    /// * `tolerance` - The relative change within the noise in any case, e.g. 0.02 for 2%
+    pub(crate) fn compare(
+        &self,
+        x: f64,
+        time: f64,
+        stddev: f64,
+        tolerance: f64,
+    ) -> Option<Comparison> {
+        let (before_time, before_stddev, interpolated) = self.at(x)?;
+        if before_time <= 0.0 {
+            return None;
+        }
+        let noise = (2.0 * (stddev * stddev + before_stddev * before_stddev).sqrt())
+            .max(tolerance * before_time);
+        let change = (time - before_time) / before_time;
+        let noise = noise / before_time;
+        Some(Comparison {
+            before_time,
+            interpolated,
+            change,
+            noise,
+            verdict: Change::of(change, noise),
+        })
+    }
+}
+
+/// The comparison of a time with the time of a [`Baseline`].
+pub(crate) struct Comparison {
+    /// The time of the baseline, in seconds
+    pub(crate) before_time: f64,
+    /// Whether the time of the baseline is interpolated
+    pub(crate) interpolated: bool,
+    /// The relative change of the time
+    pub(crate) change: f64,
+    /// The largest relative change due to the noise: the larger of twice the combined standard
+    /// deviation and the tolerance
+    pub(crate) noise: f64,
+    /// Whether the time changed beyond the noise
+    pub(crate) verdict: Change,
+}
+
+/// The comparison of two runs of the same algorithms, see the [module documentation](self).
+#[derive(Serialize, Clone, Debug)]
+pub struct DiffReport {
+    /// The comparisons of the algorithms present in both runs, in the order of the new run
+    pub algorithms: Vec<AlgorithmDiff>,
+    /// The names of the algorithms present only in the new run
+    pub added: Vec<String>,
+    /// The names of the algorithms present only in the old run
+    pub removed: Vec<String>,
+    /// The relative change of the times within the noise in any case: the sum of the relative
+    /// errors of the two runs
+    pub tolerance: f64,
+}
+
+/// The comparison of the two runs of an algorithm.
+#[derive(Serialize, Clone, Debug)]
+pub struct AlgorithmDiff {
+    /// The name of the algorithm
+    pub algorithm_name: String,
+    /// The comparisons of the sizes of the new run in the range of the old one, sorted by size
+    pub points: Vec<PointDiff>,
+    /// The comparison of the power laws fitted to the runs, if both have at least two sizes
+    pub fit: Option<FitDiff>,
+}
+
+/// The comparison of the time of a size of the new run with the time of the old run.
+#[derive(Serialize, Clone, Debug)]
+pub struct PointDiff {
+    /// The size of the input
+    pub size: usize,
+    /// The time of the old run
+    #[serde(with = "crate::measurements::nanos")]
+    pub old_time: Duration,
+    /// The time of the new run
+    #[serde(with = "crate::measurements::nanos")]
+    pub new_time: Duration,
+    /// Whether the old run didn't measure this size, and its time is interpolated between the
+    /// nearest sizes
+    pub interpolated: bool,
+    /// The relative change of the time, e.g. 0.1 if the new run is 10% slower
+    pub change: f64,
+    /// The largest relative change due to the noise: the larger of twice the combined standard
+    /// deviation of the two times and the tolerance of the report
+    pub noise: f64,
+    /// Whether the time changed beyond the noise
+    pub verdict: Change,
+}
+
+/// The comparison of the power laws fitted to the two runs of an algorithm.
+#[derive(Serialize, Clone, Debug)]
+pub struct FitDiff {
+    /// The power law fitted to the old run
+    pub old: Fit,
+    /// The power law fitted to the new run
+    pub new: Fit,
+    /// The growth rate inferred from the old run, if any
+    pub old_complexity: Option<Complexity>,
+    /// The growth rate inferred from the new run, if any
+    pub new_complexity: Option<Complexity>,
+    /// The change of the exponent of the power law
+    pub exponent_change: f64,
+    /// The largest change of the exponent due to the noise: twice the combined standard error of
+    /// the two exponents
+    pub noise: f64,
+    /// Whether the exponent changed beyond the noise
+    pub verdict: Change,
+}
+
+/// Compares the measurements saved in two JSON files, see the [module documentation](self).
+///
+/// # Arguments
+///
+/// * `old_path` - The path of the measurements of the old run, the baseline
+/// * `new_path` - The path of the measurements of the new run
+///
+/// # Errors
+///
+/// * Returns an [`ImportError`] if one of the files can't be read or doesn't contain
+///   measurements, see [`Measurements::from_json`].
+pub fn diff<P: AsRef<Path>, Q: AsRef<Path>>(
+    old_path: P,
+    new_path: Q,
+) -> Result<DiffReport, ImportError> {
+    let old = Measurements::from_json(old_path)?;
+    let new = Measurements::from_json(new_path)?;
+    Ok(DiffReport::new(&old, &new))
+}
+
+/// Returns the measurement of the algorithm with the given name, if any.
+fn find<'m>(measurements: &'m Measurements, name: &str) -> Option<&'m Measurement> {
+    measurements
+        .measurements
+        .iter()
+        .find(|measurement| measurement.algorithm_name == name)
+}
+
+impl DiffReport {
+    /// Compares two runs of the same algorithms, see the [module documentation](self).
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The measurements of the old run, the baseline
+    /// * `new` - The measurements of the new run
+    pub fn new(old: &Measurements, new: &Measurements) -> DiffReport {
+        let tolerance = (old.relative_error + new.relative_error) as f64;
+        let mut algorithms = Vec::new();
+        let mut added = Vec::new();
+        for after in &new.measurements {
+            match find(old, &after.algorithm_name) {
+                Some(before) => algorithms.push(AlgorithmDiff::new(before, after, tolerance)),
+                None => added.push(after.algorithm_name.clone()),
+            }
+        }
+        let removed = old
+            .measurements
+            .iter()
+            .filter(|before| find(new, &before.algorithm_name).is_none())
+            .map(|before| before.algorithm_name.clone())
+            .collect();
+
+        DiffReport {
+            algorithms,
+            added,
+            removed,
+            tolerance,
+        }
+    }
+
+    /// Returns whether a time or the growth rate of an algorithm got worse beyond the noise.
+    pub fn has_regressions(&self) -> bool {
+        self.algorithms.iter().any(|algorithm| {
+            algorithm.count(Change::Regressed) > 0
+                || algorithm
+                    .fit
+                    .as_ref()
+                    .is_some_and(|fit| fit.verdict == Change::Regressed)
+        })
+    }
+
+    /// Renders the report in Markdown: a summary table with a row for each algorithm, followed
+    /// by a table with the changes of the times of each algorithm.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("## Performance diff\n\n");
+        if !self.added.is_empty() {
+            writeln!(markdown, "* Added: {}", self.added.join(", ")).unwrap();
+        }
+        if !self.removed.is_empty() {
+            writeln!(markdown, "* Removed: {}", self.removed.join(", ")).unwrap();
+        }
+        if !self.added.is_empty() || !self.removed.is_empty() {
+            markdown.push('\n');
+        }
+        if self.algorithms.is_empty() {
+            markdown.push_str("No algorithm is present in both runs.\n");
+            return markdown;
+        }
+
+        markdown.push_str("| Algorithm | Slower | Faster | Within noise | Growth rate |\n");
+        markdown.push_str("|---|---:|---:|---:|---|\n");
+        for algorithm in &self.algorithms {
+            let growth = match &algorithm.fit {
+                Some(fit) => {
+                    let complexity = |complexity: Option<Complexity>| {
+                        complexity.map_or_else(|| "?".to_owned(), |c| c.to_string())
+                    };
+                    format!(
+                        "n^{:.2} → n^{:.2} ({} → {}), {}",
+                        fit.old.exponent,
+                        fit.new.exponent,
+                        complexity(fit.old_complexity),
+                        complexity(fit.new_complexity),
+                        fit.verdict.label()
+                    )
+                }
+                None => "-".to_owned(),
+            };
+            writeln!(
+                markdown,
+                "| {} | {} | {} | {} | {} |",
+                // A pipe would end the cell
+                algorithm.algorithm_name.replace('|', "\\|"),
+                algorithm.count(Change::Regressed),
+                algorithm.count(Change::Improved),
+                algorithm.count(Change::Unchanged),
+                growth
+            )
+            .unwrap();
+        }
+
+        for algorithm in &self.algorithms {
+            writeln!(markdown, "\n### {}\n", algorithm.algorithm_name).unwrap();
+            if algorithm.points.is_empty() {
+                markdown.push_str("No size of the new run is in the range of the old run.\n");
+                continue;
+            }
+            markdown.push_str("| Size | Old | New | Change | Noise | |\n");
+            markdown.push_str("|---:|---:|---:|---:|---:|---|\n");
+            for point in &algorithm.points {
+                writeln!(
+                    markdown,
+                    "| {} | {}{} | {} | {:+.1}% | ±{:.1}% | {} |",
+                    format_size(point.size),
+                    format_duration(point.old_time),
+                    if point.interpolated { "*" } else { "" },
+                    format_duration(point.new_time),
+                    point.change * 100.0,
+                    point.noise * 100.0,
+                    point.verdict.label()
+                )
+                .unwrap();
+            }
+            if algorithm.points.iter().any(|point| point.interpolated) {
+                markdown.push_str("\n\\* Interpolated between the nearest sizes of the old run.\n");
+            }
+        }
+        markdown
+    }
+}
+
+impl AlgorithmDiff {
+    /// Compares the two runs of an algorithm.
+    fn new(before: &Measurement, after: &Measurement, tolerance: f64) -> AlgorithmDiff {
+        let seconds = |duration: Option<Duration>| duration.map_or(0.0, |d| d.as_secs_f64());
+        let baseline = Baseline::new(before.measurement.iter().map(|point| {
+            (
+                point.size as f64,
+                point.time.as_secs_f64(),
+                seconds(point.stddev),
+            )
+        }));
+        let mut points: Vec<PointDiff> = after
+            .measurement
+            .iter()
+            .filter_map(|point| {
+                let comparison = baseline.compare(
+                    point.size as f64,
+                    point.time.as_secs_f64(),
+                    seconds(point.stddev),
+                    tolerance,
+                )?;
+                Some(PointDiff {
+                    size: point.size,
+                    old_time: Duration::from_secs_f64(comparison.before_time),
+                    new_time: point.time,
+                    interpolated: comparison.interpolated,
+                    change: comparison.change,
+                    noise: comparison.noise,
+                    verdict: comparison.verdict,
+                })
+            })
+            .collect();
+        points.sort_by_key(|point| point.size);
+
+        let fit = Fit::try_power_law(before)
+            .zip(Fit::try_power_law(after))
+            .map(|(old, new)| {
+                let exponent_change = new.exponent - old.exponent;
+                let noise = 2.0 * old.exponent_error.hypot(new.exponent_error);
+                FitDiff {
+                    old,
+                    new,
+                    old_complexity: Complexity::infer(before),
+                    new_complexity: Complexity::infer(after),
+                    exponent_change,
+                    noise,
+                    verdict: Change::of(exponent_change, noise),
+                }
+            });
+
+        AlgorithmDiff {
+            algorithm_name: after.algorithm_name.clone(),
+            points,
+            fit,
+        }
+    }
+
+    /// Returns the number of sizes whose time had the given change.
+    pub fn count(&self, change: Change) -> usize {
+        self.points
+            .iter()
+            .filter(|point| point.verdict == change)
+            .count()
+    }
+}
@@ -0,0 +1,188 @@
+//! # LaTeX
+//!
+//! This module renders the [`Measurements`] as LaTeX code, ready to be pasted in a paper or a
+//! thesis: a table of the times with [`latex_table`], and a plot drawn by PGFPlots with
+//! [`pgfplots`].
+
+use std::fmt::Write;
+
+use crate::measurements::Measurements;
+use crate::plot::{format_significant, TimeUnit};
+
+/// Returns the given text with the special characters of LaTeX escaped.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the symbol of the given unit in LaTeX.
+fn unit(unit: TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Nanos => "ns",
+        TimeUnit::Micros => "$\\mu$s",
+        TimeUnit::Millis => "ms",
+        TimeUnit::Secs => "s",
+    }
+}
+
+/// Returns a LaTeX table of the times of the [`Measurements`], with a row for each size and a
+/// column for each algorithm.
+///
+/// Each time is written with three significant digits and its own unit, while the algorithms
+/// without a point of a size have a dash instead. The table is a `tabular` environment using the
+/// rules of the `booktabs` package, to be put in a `table` environment with its caption.
+///
+/// # Arguments
+///
+/// * `measurements` - The measurements to render.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use chrono_probe::measurements::{Measurement, Measurements, Point};
+/// use chrono_probe::report;
+///
+/// let measurement = |name: &str, nanos: u64| {
+///     Measurement::new(name, vec![Point::new(1000, Duration::from_nanos(nanos))])
+/// };
+/// let measurements = Measurements::new(
+///     vec![measurement("merge_sort", 2500), measurement("quick_sort", 1_200_000)],
+///     0.001,
+///     Duration::from_nanos(20),
+/// );
+///
+/// assert_eq!(
+///     report::latex_table(&measurements),
+///     "\\begin{tabular}{rrr}\n\
+///      \\toprule\n\
+///      $n$ & merge\\_sort & quick\\_sort \\\\\n\
+///      \\midrule\n\
+///      1000 & 2.5\\,$\\mu$s & 1.2\\,ms \\\\\n\
+///      \\bottomrule\n\
+///      \\end{tabular}\n"
+/// );
+/// ```
+pub fn latex_table(measurements: &Measurements) -> String {
+    let mut sizes: Vec<usize> = measurements
+        .measurements
+        .iter()
+        .flat_map(|measurement| measurement.measurement.iter().map(|point| point.size))
+        .collect();
+    sizes.sort_unstable();
+    sizes.dedup();
+
+    let mut table = String::new();
+    let _ = writeln!(
+        table,
+        "\\begin{{tabular}}{{r{}}}",
+        "r".repeat(measurements.measurements.len())
+    );
+    table.push_str("\\toprule\n$n$");
+    for measurement in &measurements.measurements {
+        let _ = write!(table, " & {}", escape(&measurement.algorithm_name));
+    }
+    table.push_str(" \\\\\n\\midrule\n");
+    for size in sizes {
+        let _ = write!(table, "{}", size);
+        for measurement in &measurements.measurements {
+            match measurement
+                .measurement
+                .iter()
+                .find(|point| point.size == size)
+            {
+                Some(point) => {
+                    let time_unit = TimeUnit::of(point.time);
+                    let _ = write!(
+                        table,
+                        " & {}\\,{}",
+                        format_significant(time_unit.as_f64(point.time)),
+                        unit(time_unit)
+                    );
+                }
+                None => table.push_str(" & --"),
+            }
+        }
+        table.push_str(" \\\\\n");
+    }
+    table.push_str("\\bottomrule\n\\end{tabular}\n");
+    table
+}
+
+/// Returns a LaTeX plot of the times of the [`Measurements`] as a function of the size of the
+/// inputs, drawn by the `pgfplots` package in a double logarithmic scale, with a series and an
+/// entry of the legend for each algorithm.
+///
+/// The times are in seconds, with error bars of one standard deviation where it is known. The
+/// points with a size or a time of 0 can't be drawn in a logarithmic scale and are left out. The
+/// plot is a `tikzpicture` environment, whose options (e.g. the width, or the scale of the axes)
+/// can be changed after pasting it.
+///
+/// # Arguments
+///
+/// * `measurements` - The measurements to plot.
+///
+/// # Example
+///
+/// ```no_run
+/// use chrono_probe::measurements::Measurements;
+/// use chrono_probe::report;
+///
+/// let measurements = Measurements::from_json("results/measurements.json").unwrap();
+/// std::fs::write("figures/times.tex", report::pgfplots(&measurements)).unwrap();
+/// ```
+pub fn pgfplots(measurements: &Measurements) -> String {
+    let mut plot = String::new();
+    plot.push_str("\\begin{tikzpicture}\n");
+    plot.push_str("\\begin{loglogaxis}[\n");
+    plot.push_str("    xlabel={Input size},\n");
+    plot.push_str("    ylabel={Time (s)},\n");
+    plot.push_str("    legend pos=north west,\n");
+    plot.push_str("    grid=major,\n");
+    plot.push_str("]\n");
+    for measurement in &measurements.measurements {
+        let points: Vec<_> = measurement
+            .measurement
+            .iter()
+            .filter(|point| point.size > 0 && !point.time.is_zero())
+            .collect();
+        let error_bars = points.iter().any(|point| point.stddev.is_some());
+        if error_bars {
+            plot.push_str(
+                "\\addplot+[mark=*, error bars/.cd, y dir=both, y explicit] coordinates {\n",
+            );
+        } else {
+            plot.push_str("\\addplot+[mark=*] coordinates {\n");
+        }
+        for point in points {
+            let _ = write!(plot, "    ({}, {:e})", point.size, point.time.as_secs_f64());
+            if error_bars {
+                let stddev = point.stddev.unwrap_or_default().as_secs_f64();
+                let _ = write!(plot, " +- (0, {:e})", stddev);
+            }
+            plot.push('\n');
+        }
+        plot.push_str("};\n");
+        let _ = writeln!(
+            plot,
+            "\\addlegendentry{{{}}}",
+            escape(&measurement.algorithm_name)
+        );
+    }
+    plot.push_str("\\end{loglogaxis}\n");
+    plot.push_str("\\end{tikzpicture}\n");
+    plot
+}
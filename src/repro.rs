@@ -0,0 +1,149 @@
+//! # Repro
+//!
+//! This module provides a way to save an input on which an algorithm behaved unexpectedly (e.g.
+//! it returned a wrong output, or it took much longer than on the other inputs of the same
+//! size), so that the anomaly can be debugged outside the measurement loop.
+//!
+//! A [`ReproBundle`] contains the offending input together with everything needed to understand
+//! where it came from: the reason it was saved, the algorithm, the seed used to generate the
+//! inputs and the configuration of the generator. It is saved as a JSON file with
+//! [`ReproBundle::serialize_json`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::geometry::{PointSet, PointSetGenerator};
+//! use chrono_probe::input::InputBuilder;
+//!
+//! let generator = PointSetGenerator::default();
+//! let builder: InputBuilder<PointSet, _> =
+//!     InputBuilder::new(Uniform::new(10..=1000).unwrap(), generator.clone()).with_seed(42);
+//! let inputs = builder.build(10);
+//!
+//! // The first input of the third group made our algorithm fail: save it
+//! inputs
+//!     .repro_bundle(2, 0, "convex hull is not convex")
+//!     .with_algorithm("graham scan")
+//!     .with_builder(&generator)
+//!     .serialize_json("results/repro.json");
+//! ```
+
+use std::fs::File;
+use std::io::{self, Write};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::input::{Input, InputSet};
+
+/// An input on which an algorithm behaved unexpectedly, with the information needed to reproduce
+/// the anomaly.
+#[derive(Serialize)]
+pub struct ReproBundle<'a, I> {
+    /// Why the input was saved
+    pub reason: String,
+    /// The name of the algorithm that behaved unexpectedly, if known
+    pub algorithm_name: Option<String>,
+    /// The size of the input
+    pub size: usize,
+    /// The seed used to generate the inputs, if any
+    pub seed: Option<u64>,
+    /// The position of the input in its [`InputSet`], as (group, index in the group), if known
+    pub position: Option<(usize, usize)>,
+    /// The configuration of the generator of the input, if known
+    pub builder: Option<Value>,
+    /// The input itself
+    pub input: &'a I,
+}
+
+impl<'a, I: Input + Serialize> ReproBundle<'a, I> {
+    /// Creates a new [`ReproBundle`].
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The offending input.
+    /// * `reason` - Why the input is saved.
+    pub fn new(input: &'a I, reason: &str) -> ReproBundle<'a, I> {
+        ReproBundle {
+            reason: reason.to_owned(),
+            algorithm_name: None,
+            size: input.get_size(),
+            seed: None,
+            position: None,
+            builder: None,
+            input,
+        }
+    }
+
+    /// Sets the name of the algorithm that behaved unexpectedly.
+    pub fn with_algorithm(mut self, algorithm_name: &str) -> ReproBundle<'a, I> {
+        self.algorithm_name = Some(algorithm_name.to_owned());
+        self
+    }
+
+    /// Sets the seed used to generate the inputs.
+    pub fn with_seed(mut self, seed: u64) -> ReproBundle<'a, I> {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the configuration of the generator of the input, e.g. the builder of the input type.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the configuration cannot be serialized.
+    pub fn with_builder<B: Serialize>(mut self, builder: &B) -> ReproBundle<'a, I> {
+        self.builder = Some(serde_json::to_value(builder).unwrap());
+        self
+    }
+
+    /// Writes the bundle as indented JSON to the given writer, e.g. a socket, a buffer in memory
+    /// or the standard output.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer, or a mutable reference to it.
+    ///
+    /// # Errors
+    ///
+    /// * If the writer fails, or the bundle can't be encoded, with the kind
+    ///   [`io::ErrorKind::InvalidData`].
+    pub fn to_writer_pretty<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::from)
+    }
+
+    /// Serializes the bundle in a json file, see [`ReproBundle::to_writer_pretty`].
+    /// The file will be created if it doesn't exist, otherwise it will be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - The name of the file to be created.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the file cannot be created.
+    /// * Panics if the bundle cannot be serialized.
+    ///
+    pub fn serialize_json(&self, filename: &str) {
+        let mut file = File::create(filename).unwrap();
+        self.to_writer_pretty(&mut file).unwrap();
+    }
+}
+
+impl<I: Input + Serialize> InputSet<I> {
+    /// Returns a [`ReproBundle`] for the input in the given position, with the seed of the input
+    /// set.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The index of the group of inputs with the same size.
+    /// * `index` - The index of the input in its group.
+    /// * `reason` - Why the input is saved.
+    pub fn repro_bundle(&self, group: usize, index: usize, reason: &str) -> ReproBundle<'_, I> {
+        let mut bundle = ReproBundle::new(&self.inputs[group][index], reason);
+        bundle.seed = self.seed;
+        bundle.position = Some((group, index));
+        bundle
+    }
+}
@@ -0,0 +1,292 @@
+//! # Runs
+//!
+//! This module organizes the files of the runs of an experiment, so that each run keeps its
+//! measurements, plots and configuration together and the runs never overwrite each other.
+//!
+//! A [`RunManager`] creates a new directory for each run, named after the time at which the run
+//! started, under `results/<experiment>/`, e.g. `results/sorting/2024-05-17T14-03-52/`. The
+//! returned [`Run`] saves the measurements and the configuration files in its directory and
+//! gives the paths of the other files, like the plots. The runs of an experiment can be listed
+//! and loaded again with [`RunManager::runs`] and [`RunManager::latest`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::vec::{U32Vec, VecOrder};
+//! use chrono_probe::input::InputBuilder;
+//! use chrono_probe::measurements::measure;
+//! use chrono_probe::plot::{time_plot, PlotConfig};
+//! use chrono_probe::runs::RunManager;
+//!
+//! let builder: InputBuilder<U32Vec, _> =
+//!     InputBuilder::new(Uniform::new(1000..=100_000).unwrap(), VecOrder::default());
+//! let inputs = builder.build(20);
+//!
+//! let sum = |v: &U32Vec| v.0.iter().map(|x| *x as u64).sum::<u64>();
+//! let measurements = measure(&inputs, &[(sum, "sum")], 0.01);
+//!
+//! let runs = RunManager::new("sum");
+//! let run = runs.start().unwrap();
+//! run.save_measurements(&measurements).unwrap();
+//! run.save_config("inputs", &builder).unwrap();
+//! let config = PlotConfig::default();
+//! time_plot(&run.path("time.svg").to_string_lossy(), measurements, &config).unwrap();
+//!
+//! // Later, e.g. in another program
+//! let latest = runs.latest().unwrap().unwrap();
+//! let measurements = latest.load_measurements().unwrap();
+//! ```
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::measurements::{ExportError, ImportError, Measurements};
+use crate::util::civil_date;
+
+/// The name of the file of the measurements of a run.
+const MEASUREMENTS_FILE: &str = "measurements.json";
+
+/// Manages the directories of the runs of an experiment, under `<root>/<experiment>/`.
+#[derive(Clone, Debug)]
+pub struct RunManager {
+    root: PathBuf,
+    experiment: String,
+}
+
+impl RunManager {
+    /// Creates a new [`RunManager`] for the given experiment, whose runs are saved under
+    /// `results/<experiment>/`.
+    ///
+    /// # Arguments
+    ///
+    /// * `experiment` - The name of the experiment.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the name of the experiment is empty, `.` or `..`, or contains a path
+    ///   separator.
+    pub fn new(experiment: &str) -> RunManager {
+        assert!(
+            !experiment.is_empty()
+                && experiment != "."
+                && experiment != ".."
+                && !experiment.contains(['/', '\\']),
+            "The name of the experiment must be a valid directory name"
+        );
+        RunManager {
+            root: PathBuf::from("results"),
+            experiment: experiment.to_owned(),
+        }
+    }
+
+    /// Sets the directory containing the directories of the experiments. By default, it is
+    /// `results`, relative to the working directory.
+    pub fn with_root<P: AsRef<Path>>(mut self, root: P) -> RunManager {
+        self.root = root.as_ref().to_path_buf();
+        self
+    }
+
+    /// Returns the directory containing the runs of the experiment.
+    pub fn dir(&self) -> PathBuf {
+        self.root.join(&self.experiment)
+    }
+
+    /// Starts a new run, creating its directory, named after the current time in UTC as
+    /// `YYYY-MM-DDTHH-MM-SS`. If a run started in the same second already exists, a counter is
+    /// appended to the name, e.g. `2024-05-17T14-03-52-2`.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the directory can't be created.
+    pub fn start(&self) -> Result<Run, ExportError> {
+        let experiment_dir = self.dir();
+        fs::create_dir_all(&experiment_dir).map_err(|source| ExportError::Io {
+            path: experiment_dir.clone(),
+            source,
+        })?;
+        let timestamp = timestamp(SystemTime::now());
+        let mut counter = 1;
+        loop {
+            let name = match counter {
+                1 => timestamp.clone(),
+                _ => format!("{}-{}", timestamp, counter),
+            };
+            let dir = experiment_dir.join(name);
+            match fs::create_dir(&dir) {
+                Ok(()) => return Ok(Run { dir }),
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists => counter += 1,
+                Err(source) => return Err(ExportError::Io { path: dir, source }),
+            }
+        }
+    }
+
+    /// Returns the runs of the experiment, from the oldest to the latest. If the experiment has
+    /// no run yet, the result is empty.
+    ///
+    /// # Errors
+    ///
+    /// * [`ImportError::Io`] - If the directory of the experiment can't be read.
+    pub fn runs(&self) -> Result<Vec<Run>, ImportError> {
+        let experiment_dir = self.dir();
+        let io_error = |source| ImportError::Io {
+            path: experiment_dir.clone(),
+            source,
+        };
+        let entries = match fs::read_dir(&experiment_dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(io_error(error)),
+        };
+        let mut runs = Vec::new();
+        for entry in entries {
+            let dir = entry.map_err(io_error)?.path();
+            if dir.is_dir() {
+                runs.push(Run { dir });
+            }
+        }
+        // The names start with the time of the run, so that they are sorted chronologically
+        runs.sort_by_cached_key(|run| match run.name().split_at_checked(19) {
+            Some((time, counter)) => (
+                time.to_owned(),
+                counter
+                    .strip_prefix('-')
+                    .and_then(|counter| counter.parse::<u64>().ok())
+                    .unwrap_or(0),
+            ),
+            None => (run.name().to_owned(), 0),
+        });
+        Ok(runs)
+    }
+
+    /// Returns the latest run of the experiment, if any.
+    ///
+    /// # Errors
+    ///
+    /// * [`ImportError::Io`] - If the directory of the experiment can't be read.
+    pub fn latest(&self) -> Result<Option<Run>, ImportError> {
+        Ok(self.runs()?.pop())
+    }
+}
+
+/// A run of an experiment, with its own directory, created by a [`RunManager`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run {
+    dir: PathBuf,
+}
+
+impl Run {
+    /// Returns the directory of the run.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the name of the directory of the run, i.e. the time at which it started.
+    pub fn name(&self) -> &str {
+        self.dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+    }
+
+    /// Returns the path of the file with the given name in the directory of the run, e.g. to
+    /// save a plot.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the file.
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Saves the measurements of the run, as JSON, in the file `measurements.json` of its
+    /// directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `measurements` - The measurements of the run.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written.
+    /// * [`ExportError::Format`] - If the measurements can't be encoded.
+    pub fn save_measurements(&self, measurements: &Measurements) -> Result<(), ExportError> {
+        self.write_json(MEASUREMENTS_FILE, measurements, false)
+    }
+
+    /// Loads the measurements saved with [`Run::save_measurements`].
+    ///
+    /// # Errors
+    ///
+    /// * [`ImportError::Io`] - If the file can't be read.
+    /// * [`ImportError::Format`] - If the file doesn't contain measurements.
+    pub fn load_measurements(&self) -> Result<Measurements, ImportError> {
+        Measurements::from_json(self.path(MEASUREMENTS_FILE))
+    }
+
+    /// Saves a configuration of the run, e.g. the [`InputBuilder`](crate::input::InputBuilder)
+    /// of the inputs, as indented JSON in the file `<name>.json` of its directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the file, without the extension.
+    /// * `config` - The configuration.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created or written.
+    /// * [`ExportError::Format`] - If the configuration can't be encoded.
+    pub fn save_config<T: Serialize>(&self, name: &str, config: &T) -> Result<(), ExportError> {
+        self.write_json(&format!("{}.json", name), config, true)
+    }
+
+    /// Writes the given value as JSON to the file with the given name in the directory.
+    fn write_json<T: Serialize>(
+        &self,
+        name: &str,
+        value: &T,
+        pretty: bool,
+    ) -> Result<(), ExportError> {
+        let path = self.path(name);
+        let io_error = |source| ExportError::Io {
+            path: path.clone(),
+            source,
+        };
+        let mut writer = BufWriter::new(File::create(&path).map_err(io_error)?);
+        let result = match pretty {
+            false => serde_json::to_writer(&mut writer, value),
+            true => serde_json::to_writer_pretty(&mut writer, value),
+        };
+        result.map_err(|error| ExportError::Format {
+            path: path.clone(),
+            reason: error.to_string(),
+        })?;
+        if pretty {
+            writer.write_all(b"\n").map_err(io_error)?;
+        }
+        writer.flush().map_err(io_error)
+    }
+}
+
+/// Returns the given time in UTC as `YYYY-MM-DDTHH-MM-SS`.
+fn timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_date((seconds / 86_400) as i64);
+    let seconds_of_day = seconds % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}-{:02}-{:02}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60
+    )
+}
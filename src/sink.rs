@@ -0,0 +1,417 @@
+//! # Sink
+//!
+//! This module contains the destinations to which the results are written while they are
+//! measured, instead of at the end of the measurements, so that a crashed or aborted run still
+//! leaves usable partial data and external tools can follow the progress.
+//!
+//! A destination implements the [`ResultSink`] trait, receiving the description of the run when
+//! it starts, each [`Point`] as soon as it is measured and all the [`Measurements`] when the run
+//! ends, and is given to the measure functions wrapped in a [`SinkHandler`]. The trait can be
+//! implemented to send the results anywhere, e.g. to a server or a database, while the following
+//! sinks are provided:
+//!
+//! * [`JsonLinesSink`] - Appends each point to a [JSON Lines](https://jsonlines.org) file, that
+//!   can be followed with `tail -f` and loaded with
+//!   [`Measurements::from_json_lines`](crate::measurements::Measurements::from_json_lines).
+//! * [`CsvSink`] - Appends each point to a CSV file.
+//! * [`JsonSink`] - Writes all the measurements to a JSON file at the end of the run, like
+//!   [`Measurements::serialize_json`](crate::measurements::Measurements::serialize_json).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::vec::{U32Vec, VecOrder};
+//! use chrono_probe::input::InputBuilder;
+//! use chrono_probe::measurements::measure_with_progress;
+//! use chrono_probe::sink::{JsonLinesSink, SinkHandler};
+//!
+//! let builder: InputBuilder<U32Vec, _> =
+//!     InputBuilder::new(Uniform::new(1000..=100_000).unwrap(), VecOrder::default());
+//! let inputs = builder.build(20);
+//!
+//! let sum = |v: &U32Vec| v.0.iter().map(|x| *x as u64).sum::<u64>();
+//! let mut sink = SinkHandler::new(JsonLinesSink::create("results/points.jsonl").unwrap());
+//! let measurements = measure_with_progress(&inputs, &[(sum, "sum")], 0.01, &mut sink);
+//! sink.into_result().unwrap();
+//! ```
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::measurements::{ExportError, Measurements, Point};
+use crate::plot::csv_field;
+use crate::progress::{Progress, ProgressHandler, RunInfo};
+
+/// Trait implemented by the destinations of the results of the measurements.
+///
+/// Only [`ResultSink::point`] is required, the other methods do nothing by default.
+///
+/// # Example
+///
+/// ```
+/// use chrono_probe::measurements::Point;
+/// use chrono_probe::sink::ResultSink;
+///
+/// /// Counts the measured points.
+/// struct Counter(usize);
+///
+/// impl ResultSink for Counter {
+///     type Error = std::convert::Infallible;
+///
+///     fn point(&mut self, _algorithm: &str, _point: &Point) -> Result<(), Self::Error> {
+///         self.0 += 1;
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait ResultSink {
+    /// The type of the errors of the sink.
+    type Error;
+
+    /// Called before measuring the first algorithm, with the description of the run.
+    fn begin(&mut self, run: &RunInfo) -> Result<(), Self::Error> {
+        let _ = run;
+        Ok(())
+    }
+
+    /// Called every time a point is measured, with the name of its algorithm.
+    fn point(&mut self, algorithm: &str, point: &Point) -> Result<(), Self::Error>;
+
+    /// Called after measuring the last algorithm, with all the measurements of the run.
+    fn finish(&mut self, measurements: &Measurements) -> Result<(), Self::Error> {
+        let _ = measurements;
+        Ok(())
+    }
+}
+
+/// A [`ProgressHandler`] forwarding the run and the measured points to a [`ResultSink`].
+///
+/// Since the errors can't be returned while measuring, the first one is kept and the sink isn't
+/// called anymore: call [`SinkHandler::into_result`] at the end of the measurements to check it.
+///
+/// To follow the progress in other ways too, call the handler from a closure calling the other
+/// handlers as well.
+pub struct SinkHandler<S: ResultSink> {
+    sink: S,
+    error: Option<S::Error>,
+}
+
+impl<S: ResultSink> SinkHandler<S> {
+    /// Creates a new [`SinkHandler`].
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink receiving the results.
+    pub fn new(sink: S) -> SinkHandler<S> {
+        SinkHandler { sink, error: None }
+    }
+
+    /// Returns the sink, or the first error it returned.
+    ///
+    /// # Errors
+    ///
+    /// * The first error returned by the sink, if any.
+    pub fn into_result(self) -> Result<S, S::Error> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.sink),
+        }
+    }
+
+    /// Keeps the error of the given result, if it is the first one.
+    fn keep(&mut self, result: Result<(), S::Error>) {
+        if let Err(error) = result {
+            self.error = Some(error);
+        }
+    }
+}
+
+impl<S: ResultSink> ProgressHandler for SinkHandler<S> {
+    fn on_progress(&mut self, progress: &Progress) {
+        if self.error.is_some() {
+            return;
+        }
+        if let (Some(algorithm), Some(point)) = (progress.label, progress.point) {
+            let result = self.sink.point(algorithm, point);
+            self.keep(result);
+        }
+    }
+
+    fn on_run_start(&mut self, run: &RunInfo) {
+        if self.error.is_none() {
+            let result = self.sink.begin(run);
+            self.keep(result);
+        }
+    }
+
+    fn on_run_end(&mut self, measurements: &Measurements) {
+        if self.error.is_none() {
+            let result = self.sink.finish(measurements);
+            self.keep(result);
+        }
+    }
+}
+
+/// A line of a JSON Lines file: a point together with the name of its algorithm.
+#[derive(Serialize)]
+struct PointLine<'a> {
+    algorithm: &'a str,
+    #[serde(flatten)]
+    point: &'a Point,
+}
+
+/// A [`ResultSink`] appending each measured [`Point`] to a JSON Lines file, one JSON object per
+/// line with the name of the algorithm and the fields of the point.
+///
+/// Each line is written as soon as the point is measured, in a single write, so that the lines
+/// already written are complete even if the process is killed.
+///
+/// The sink is also a [`ProgressHandler`] itself, so that it can be given directly to the
+/// measure functions. In that case, the first error is kept and the following points are
+/// discarded: call [`JsonLinesSink::finish`] at the end of the measurements to check it.
+pub struct JsonLinesSink {
+    path: PathBuf,
+    file: File,
+    error: Option<ExportError>,
+}
+
+impl JsonLinesSink {
+    /// Creates a new [`JsonLinesSink`] writing to the given file. The file will be created if it
+    /// doesn't exist, otherwise it will be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<JsonLinesSink, ExportError> {
+        JsonLinesSink::open(
+            path.as_ref(),
+            File::options().write(true).create(true).truncate(true),
+        )
+    }
+
+    /// Creates a new [`JsonLinesSink`] appending to the given file, e.g. to resume a run that
+    /// was aborted. The file will be created if it doesn't exist, while if its last line is
+    /// incomplete, because the process writing it was killed, it is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be opened.
+    pub fn append<P: AsRef<Path>>(path: P) -> Result<JsonLinesSink, ExportError> {
+        let path = path.as_ref();
+        let io_error = |source| ExportError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        let sink = JsonLinesSink::open(path, File::options().append(true).create(true))?;
+        let contents = fs::read(path).map_err(io_error)?;
+        let complete = contents
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |end| end + 1);
+        if complete < contents.len() {
+            sink.file.set_len(complete as u64).map_err(io_error)?;
+        }
+        Ok(sink)
+    }
+
+    /// Opens the file of a new [`JsonLinesSink`] with the given options.
+    fn open(path: &Path, options: &OpenOptions) -> Result<JsonLinesSink, ExportError> {
+        let file = options.open(path).map_err(|source| ExportError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(JsonLinesSink {
+            path: path.to_path_buf(),
+            file,
+            error: None,
+        })
+    }
+
+    /// Ends the writing of the points, returning the first error that occurred while the sink
+    /// was used as a [`ProgressHandler`], if any.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If a point couldn't be written to the file.
+    /// * [`ExportError::Format`] - If a point couldn't be encoded.
+    pub fn finish(self) -> Result<(), ExportError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => sync(&self.file, &self.path),
+        }
+    }
+}
+
+impl ProgressHandler for JsonLinesSink {
+    fn on_progress(&mut self, progress: &Progress) {
+        if self.error.is_some() {
+            return;
+        }
+        if let (Some(algorithm), Some(point)) = (progress.label, progress.point) {
+            if let Err(error) = self.point(algorithm, point) {
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+impl ResultSink for JsonLinesSink {
+    type Error = ExportError;
+
+    fn point(&mut self, algorithm: &str, point: &Point) -> Result<(), ExportError> {
+        let mut line = serde_json::to_vec(&PointLine { algorithm, point }).map_err(|error| {
+            ExportError::Format {
+                path: self.path.clone(),
+                reason: error.to_string(),
+            }
+        })?;
+        line.push(b'\n');
+        write_line(&mut self.file, &self.path, &line)
+    }
+
+    fn finish(&mut self, _measurements: &Measurements) -> Result<(), ExportError> {
+        sync(&self.file, &self.path)
+    }
+}
+
+/// A [`ResultSink`] appending each measured [`Point`] to a CSV file, one row per point with the
+/// columns `algorithm`, `size`, `time_ns`, `bytes`, `stddev_ns` and `timestamp_ns`, the last
+/// three empty if not known.
+///
+/// The header is written when the first run starts, and each row as soon as the point is
+/// measured, in a single write, so that the rows of several runs end up in the same table.
+pub struct CsvSink {
+    path: PathBuf,
+    file: File,
+    header: bool,
+}
+
+impl CsvSink {
+    /// Creates a new [`CsvSink`] writing to the given file. The file will be created if it
+    /// doesn't exist, otherwise it will be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    ///
+    /// # Errors
+    ///
+    /// * [`ExportError::Io`] - If the file can't be created.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<CsvSink, ExportError> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|source| ExportError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(CsvSink {
+            path: path.to_path_buf(),
+            file,
+            header: false,
+        })
+    }
+}
+
+impl ResultSink for CsvSink {
+    type Error = ExportError;
+
+    fn begin(&mut self, _run: &RunInfo) -> Result<(), ExportError> {
+        if self.header {
+            return Ok(());
+        }
+        write_line(
+            &mut self.file,
+            &self.path,
+            b"algorithm,size,time_ns,bytes,stddev_ns,timestamp_ns\n",
+        )?;
+        self.header = true;
+        Ok(())
+    }
+
+    fn point(&mut self, algorithm: &str, point: &Point) -> Result<(), ExportError> {
+        let line = format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(algorithm),
+            point.size,
+            point.time.as_nanos(),
+            point.bytes.map_or(String::new(), |bytes| bytes.to_string()),
+            point
+                .stddev
+                .map_or(String::new(), |stddev| stddev.as_nanos().to_string()),
+            point
+                .timestamp
+                .map_or(String::new(), |timestamp| timestamp.as_nanos().to_string()),
+        );
+        write_line(&mut self.file, &self.path, line.as_bytes())
+    }
+
+    fn finish(&mut self, _measurements: &Measurements) -> Result<(), ExportError> {
+        sync(&self.file, &self.path)
+    }
+}
+
+/// A [`ResultSink`] writing all the measurements to a JSON file when the run ends, like
+/// [`Measurements::serialize_json`], ignoring the points while they are measured.
+pub struct JsonSink {
+    path: PathBuf,
+}
+
+impl JsonSink {
+    /// Creates a new [`JsonSink`] writing to the given file when the run ends. The file will be
+    /// created if it doesn't exist, otherwise it will be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    pub fn new<P: AsRef<Path>>(path: P) -> JsonSink {
+        JsonSink {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl ResultSink for JsonSink {
+    type Error = ExportError;
+
+    fn point(&mut self, _algorithm: &str, _point: &Point) -> Result<(), ExportError> {
+        Ok(())
+    }
+
+    fn finish(&mut self, measurements: &Measurements) -> Result<(), ExportError> {
+        let json = serde_json::to_vec(measurements).map_err(|error| ExportError::Format {
+            path: self.path.clone(),
+            reason: error.to_string(),
+        })?;
+        fs::write(&self.path, json).map_err(|source| ExportError::Io {
+            path: self.path.clone(),
+            source,
+        })
+    }
+}
+
+/// Writes the given line to the file in a single write.
+fn write_line(file: &mut File, path: &Path, line: &[u8]) -> Result<(), ExportError> {
+    file.write_all(line).map_err(|source| ExportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Flushes the data written to the file to the disk.
+fn sync(file: &File, path: &Path) -> Result<(), ExportError> {
+    file.sync_data().map_err(|source| ExportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
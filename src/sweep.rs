@@ -0,0 +1,213 @@
+//! # Sweep
+//!
+//! This module contains the tools to measure how the performance of an algorithm changes with a
+//! parameter of the input generator (e.g. the density of a graph, or how much a vector is
+//! sorted), in addition to the size of the inputs.
+//!
+//! A [`ParameterSweep`] holds the values of the parameter. For each value, it creates an
+//! [`InputBuilder`] with a user supplied function, builds an [`InputSet`](crate::input::InputSet)
+//! and measures the algorithms on it. The result is a [`SweepMeasurements`], containing the
+//! [`Measurements`] obtained for each value of the parameter. They can be combined in a single
+//! [`Measurements`] with [`SweepMeasurements::labeled`], having one series for each algorithm and
+//! each value of the parameter, so that they can be plotted together.
+//!
+//! To hold the size fixed, use a distribution generating always the same size. The values of a
+//! continuous parameter can be generated with a
+//! [`ContinuousDistribution`](crate::input::distribution::ContinuousDistribution), see
+//! [`ParameterSweep::from_distribution`], moved to the range of the parameter with
+//! [`Rescaled`](crate::input::distribution::Rescaled).
+//!
+//! # Example
+//!
+//! ```
+//! use chrono_probe::input::distribution::Uniform;
+//! use chrono_probe::input::generators::map::{MapWorkload, MapWorkloadGenerator};
+//! use chrono_probe::input::InputBuilder;
+//! use chrono_probe::sweep::ParameterSweep;
+//!
+//! fn lookups(workload: &MapWorkload) -> usize {
+//!     workload.run_lookups()
+//! }
+//!
+//! let sweep = ParameterSweep::new("hit rate", vec![0.0, 0.5, 1.0]);
+//! let results = sweep.measure(
+//!     |hit_rate| {
+//!         InputBuilder::new(
+//!             Uniform::new(10..=1000).unwrap(),
+//!             MapWorkloadGenerator::Lookups { hit_rate: *hit_rate },
+//!         )
+//!     },
+//!     10,
+//!     &[(lookups, "hash map")],
+//!     0.01,
+//! );
+//!
+//! // One series for each value of the hit rate
+//! let measurements = results.labeled();
+//! assert_eq!(measurements.measurements.len(), 3);
+//! ```
+
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::input::distribution::{ContinuousDistribution, Distribution};
+use crate::input::{Input, InputBuilder};
+use crate::measurements::{measure, measure_mut, Measurements};
+
+/// A sweep over the values of a parameter of the input generator.
+pub struct ParameterSweep<P> {
+    name: String,
+    values: Vec<P>,
+    repetitions: usize,
+}
+
+/// The measurements obtained for each value of the parameter of a [`ParameterSweep`].
+#[derive(Serialize, Clone)]
+pub struct SweepMeasurements<P> {
+    /// The name of the parameter
+    pub parameter: String,
+    /// The value of the parameter and the measurements obtained with it, for each value
+    pub results: Vec<(P, Measurements)>,
+}
+
+impl ParameterSweep<f64> {
+    /// Creates a new [`ParameterSweep`] over `n` values of a continuous parameter, generated with
+    /// the given distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the parameter, used to label the measurements.
+    /// * `distribution` - The distribution of the values of the parameter.
+    /// * `n` - The number of values of the parameter.
+    pub fn from_distribution<C: ContinuousDistribution>(
+        name: &str,
+        distribution: &C,
+        n: usize,
+    ) -> ParameterSweep<f64> {
+        ParameterSweep::new(name, distribution.generate_values(n))
+    }
+}
+
+impl<P: Clone> ParameterSweep<P> {
+    /// Creates a new [`ParameterSweep`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the parameter, used to label the measurements.
+    /// * `values` - The values of the parameter.
+    pub fn new(name: &str, values: Vec<P>) -> ParameterSweep<P> {
+        assert!(!values.is_empty(), "The parameter values must not be empty");
+        ParameterSweep {
+            name: name.to_owned(),
+            values,
+            repetitions: 1,
+        }
+    }
+
+    /// Sets the number of repetitions for each input size.
+    ///
+    /// # Arguments
+    ///
+    /// * `repetitions` - The number of repetitions for each input size.
+    pub fn with_repetitions(mut self, repetitions: usize) -> ParameterSweep<P> {
+        self.repetitions = repetitions;
+        self
+    }
+
+    /// Measures the time it takes to run different functions for each value of the parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - The function creating the [`InputBuilder`] for a value of the parameter
+    /// * `n` - The number of input sizes to be generated for each value of the parameter
+    /// * `algorithms` - The vector of functions to measure
+    /// * `relative_error` - The required relative error of the measurements
+    ///
+    pub fn measure<'a, I, D, O, Alg, B>(
+        &self,
+        builder: B,
+        n: usize,
+        algorithms: &[(Alg, &str)],
+        relative_error: f32,
+    ) -> SweepMeasurements<P>
+    where
+        I: Input + 'a,
+        D: Distribution,
+        Alg: Fn(&I) -> O,
+        B: Fn(&P) -> InputBuilder<'a, I, D>,
+    {
+        self.run(|value| {
+            let inputs = builder(value).build_with_repetitions(n, self.repetitions);
+            measure(&inputs, algorithms, relative_error)
+        })
+    }
+
+    /// Measures the time it takes to run different functions mutating their input, for each
+    /// value of the parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `builder` - The function creating the [`InputBuilder`] for a value of the parameter
+    /// * `n` - The number of input sizes to be generated for each value of the parameter
+    /// * `algorithms` - The vector of functions to measure
+    /// * `relative_error` - The required relative error of the measurements
+    ///
+    pub fn measure_mut<'a, I, D, O, Alg, B>(
+        &self,
+        builder: B,
+        n: usize,
+        algorithms: &[(Alg, &str)],
+        relative_error: f32,
+    ) -> SweepMeasurements<P>
+    where
+        I: Input + Clone + 'a,
+        D: Distribution,
+        Alg: Fn(&mut I) -> O,
+        B: Fn(&P) -> InputBuilder<'a, I, D>,
+    {
+        self.run(|value| {
+            let inputs = builder(value).build_with_repetitions(n, self.repetitions);
+            measure_mut(&inputs, algorithms, relative_error)
+        })
+    }
+
+    /// Obtains the measurements for each value of the parameter with the given function.
+    fn run<F>(&self, mut measure_value: F) -> SweepMeasurements<P>
+    where
+        F: FnMut(&P) -> Measurements,
+    {
+        SweepMeasurements {
+            parameter: self.name.clone(),
+            results: self
+                .values
+                .iter()
+                .map(|value| (value.clone(), measure_value(value)))
+                .collect(),
+        }
+    }
+}
+
+impl<P: Display> SweepMeasurements<P> {
+    /// Combines the measurements obtained for every value of the parameter in a single
+    /// [`Measurements`], which can be plotted as usual.
+    ///
+    /// Each algorithm gets a series for each value of the parameter, named
+    /// "`algorithm` (`parameter` = `value`)".
+    pub fn labeled(&self) -> Measurements {
+        self.results
+            .iter()
+            .map(|(value, measurements)| {
+                let mut measurements = measurements.clone();
+                for measurement in &mut measurements.measurements {
+                    measurement.algorithm_name = format!(
+                        "{} ({} = {})",
+                        measurement.algorithm_name, self.parameter, value
+                    );
+                }
+                measurements
+            })
+            .reduce(Measurements::merge)
+            .expect("The parameter values must not be empty")
+    }
+}